@@ -0,0 +1,143 @@
+//! Borrowing counterparts of the hot-path message types.
+//!
+//! String fields are `Cow<'a, str>` so deserializing from a `&'a str` buffer
+//! borrows them instead of allocating; a string containing escape sequences
+//! still falls back to an owned copy. Params are kept as raw JSON and only
+//! decoded on demand via [RequestMessageRef::params] and
+//! [NotificationMessageRef::params].
+//!
+//! [Position](crate::Position) and [Range](crate::Range) hold no strings and
+//! are already allocation-free, so they are used as is.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::de::{self, Unexpected, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::value::RawValue;
+
+use crate::{DocumentUri, ParseError, RequestId, TextDocumentIdentifier};
+
+/// borrowing [RequestId]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum RequestIdRef<'a> {
+    Integer(i64),
+    String(Cow<'a, str>),
+}
+
+/// hand-written so a string id doesn't pay for the failed `Integer` attempt
+/// an untagged derive would make
+impl<'de: 'a, 'a> Deserialize<'de> for RequestIdRef<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IdVisitor;
+
+        impl<'de> Visitor<'de> for IdVisitor {
+            type Value = RequestIdRef<'de>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an integer or a string")
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(RequestIdRef::Integer(v))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                i64::try_from(v)
+                    .map(RequestIdRef::Integer)
+                    .map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+                Ok(RequestIdRef::String(Cow::Borrowed(v)))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(RequestIdRef::String(Cow::Owned(v.to_owned())))
+            }
+        }
+
+        deserializer.deserialize_any(IdVisitor)
+    }
+}
+
+impl RequestIdRef<'_> {
+    pub fn into_owned(self) -> RequestId {
+        match self {
+            RequestIdRef::Integer(integer) => RequestId::Integer(integer),
+            RequestIdRef::String(string) => RequestId::String(string.into_owned()),
+        }
+    }
+}
+
+/// borrowing [RequestMessage](crate::RequestMessage)
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RequestMessageRef<'a> {
+    #[serde(borrow)]
+    pub jsonrpc: Cow<'a, str>,
+
+    #[serde(borrow)]
+    pub id: RequestIdRef<'a>,
+
+    #[serde(borrow)]
+    pub method: Cow<'a, str>,
+
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub params: Option<&'a RawValue>,
+}
+
+impl<'a> RequestMessageRef<'a> {
+    /// decodes the raw params, `None` if the message has none
+    pub fn params<T: Deserialize<'a>>(&self) -> Result<Option<T>, ParseError> {
+        decode_params(&self.method, self.params)
+    }
+}
+
+/// borrowing [NotificationMessage](crate::NotificationMessage)
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NotificationMessageRef<'a> {
+    #[serde(borrow)]
+    pub jsonrpc: Cow<'a, str>,
+
+    #[serde(borrow)]
+    pub method: Cow<'a, str>,
+
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub params: Option<&'a RawValue>,
+}
+
+impl<'a> NotificationMessageRef<'a> {
+    /// decodes the raw params, `None` if the message has none
+    pub fn params<T: Deserialize<'a>>(&self) -> Result<Option<T>, ParseError> {
+        decode_params(&self.method, self.params)
+    }
+}
+
+fn decode_params<'a, T: Deserialize<'a>>(
+    method: &str,
+    params: Option<&'a RawValue>,
+) -> Result<Option<T>, ParseError> {
+    params
+        .map(|raw| serde_json::from_str(raw.get()))
+        .transpose()
+        .map_err(|source| ParseError::InvalidParams {
+            method: method.to_owned(),
+            source,
+        })
+}
+
+/// borrowing [TextDocumentIdentifier]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TextDocumentIdentifierRef<'a> {
+    #[serde(borrow)]
+    pub uri: Cow<'a, str>,
+}
+
+impl TextDocumentIdentifierRef<'_> {
+    pub fn into_owned(self) -> TextDocumentIdentifier {
+        TextDocumentIdentifier {
+            uri: DocumentUri::from(&*self.uri),
+        }
+    }
+}
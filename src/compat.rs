@@ -0,0 +1,88 @@
+//! Conversions to and from the types of the
+//! [`lsp-types`](https://docs.rs/lsp-types) crate.
+//!
+//! [Position] and [Range] map field by field, in both directions.
+//! [Location], [Diagnostic] and [CompletionItem] convert through their wire
+//! form, so the conversions are `TryFrom` and fail with the
+//! `serde_json::Error` where the two crates disagree about what is valid:
+//!
+//! - a [DocumentUri](crate::DocumentUri) or [URI](crate::URI) is any string
+//!   here but has to parse as a uri in `lsp-types`, so [Location::uri] and
+//!   [CodeDescription::href](crate::CodeDescription::href) can fail going
+//!   there
+//! - `lsp-types` keeps an unknown severity, kind or tag as any `i32`, while
+//!   here it is a `u32`, so a negative one fails coming here
+//! - `lsp-types` has no [CompletionItem::textEditText], so it is dropped
+//!   going there
+
+use ::lsp_types as upstream;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{CompletionItem, Diagnostic, Location, Position, Range};
+
+impl From<upstream::Position> for Position {
+    fn from(position: upstream::Position) -> Self {
+        Position {
+            line: position.line,
+            character: position.character,
+        }
+    }
+}
+
+impl From<Position> for upstream::Position {
+    fn from(position: Position) -> Self {
+        upstream::Position {
+            line: position.line,
+            character: position.character,
+        }
+    }
+}
+
+impl From<upstream::Range> for Range {
+    fn from(range: upstream::Range) -> Self {
+        Range {
+            start: range.start.into(),
+            end: range.end.into(),
+        }
+    }
+}
+
+impl From<Range> for upstream::Range {
+    fn from(range: Range) -> Self {
+        upstream::Range {
+            start: range.start.into(),
+            end: range.end.into(),
+        }
+    }
+}
+
+fn convert<T: Serialize, U: DeserializeOwned>(value: T) -> Result<U, serde_json::Error> {
+    serde_json::to_value(value).and_then(serde_json::from_value)
+}
+
+/// `TryFrom` in both directions between a type and its `lsp-types`
+/// counterpart, through the wire form
+macro_rules! impl_try_from_wire {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl TryFrom<upstream::$name> for $name {
+                type Error = serde_json::Error;
+
+                fn try_from(value: upstream::$name) -> Result<Self, Self::Error> {
+                    convert(value)
+                }
+            }
+
+            impl TryFrom<$name> for upstream::$name {
+                type Error = serde_json::Error;
+
+                fn try_from(value: $name) -> Result<Self, Self::Error> {
+                    convert(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_wire!(Location, Diagnostic, CompletionItem);
@@ -1,2 +1,8 @@
 pub mod lsp_types;
 pub use lsp_types::*;
+
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+
+#[cfg(feature = "lsp-types-compat")]
+pub mod compat;
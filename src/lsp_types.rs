@@ -2,16 +2,69 @@
     dead_code,
     non_snake_case,
     non_upper_case_globals,
-    non_camel_case_types
+    non_camel_case_types,
+    clippy::upper_case_acronyms
 )]
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 pub enum IntegerOrString {
     String(String),
     Integer(Integer),
 }
 
+impl From<Integer> for IntegerOrString {
+    fn from(integer: Integer) -> Self {
+        IntegerOrString::Integer(integer)
+    }
+}
+
+impl From<String> for IntegerOrString {
+    fn from(string: String) -> Self {
+        IntegerOrString::String(string)
+    }
+}
+
+impl From<&str> for IntegerOrString {
+    fn from(string: &str) -> Self {
+        IntegerOrString::String(string.to_owned())
+    }
+}
+
+/// the id of a request, `integer | string` on the wire. The integer is an
+/// `i64` rather than an [Integer] since some clients number their requests
+/// past `i32::MAX`, and a response has to echo the id unchanged.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum RequestId {
+    String(String),
+    Integer(i64),
+}
+
+impl From<i64> for RequestId {
+    fn from(integer: i64) -> Self {
+        RequestId::Integer(integer)
+    }
+}
+
+impl From<Integer> for RequestId {
+    fn from(integer: Integer) -> Self {
+        RequestId::Integer(integer.into())
+    }
+}
+
+impl From<String> for RequestId {
+    fn from(string: String) -> Self {
+        RequestId::String(string)
+    }
+}
+
+impl From<&str> for RequestId {
+    fn from(string: &str) -> Self {
+        RequestId::String(string.to_owned())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(untagged)]
 pub enum ArrayOrObject {
@@ -28,10 +81,121 @@ pub enum Value {
 }
 
 use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
+/// declares an enum sent as a number whose value set may grow, like the
+/// spec's `SymbolKind`. Each variant is listed with its wire value, and an
+/// `Unknown(u32)` variant is added so a value from a newer protocol version is
+/// kept and sent back unchanged instead of failing deserialization, since
+/// the spec asks clients to handle values outside the set gracefully.
+macro_rules! open_repr_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:literal),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant,)*
+
+            /// a value this crate doesn't know, kept as sent
+            Unknown(u32),
+        }
+
+        impl From<u32> for $name {
+            fn from(value: u32) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    value => $name::Unknown(value),
+                }
+            }
+        }
+
+        impl From<$name> for u32 {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_u32((*self).into())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                u32::deserialize(deserializer).map($name::from)
+            }
+        }
+    };
+}
+
+/// implements `Display` and `FromStr` for a fieldless enum, using either the
+/// variant names or the given strings as the textual form. For an
+/// [open_repr_enum!] name the `Unknown` variant after the `|`, which displays
+/// as its number and parses back from it.
+macro_rules! impl_display_from_str {
+    ($name:ident { $($variant:ident),* $(,)? } | $unknown:ident) => {
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $($name::$variant => f.write_str(stringify!($variant)),)*
+                    $name::$unknown(value) => write!(f, "{value}"),
+                }
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $(if s == stringify!($variant) {
+                    return Ok($name::$variant);
+                })*
+                match s.parse::<u32>() {
+                    Ok(value) if matches!($name::from(value), $name::$unknown(_)) => {
+                        Ok($name::$unknown(value))
+                    }
+                    _ => Err(format!("unknown {}: {s}", stringify!($name))),
+                }
+            }
+        }
+    };
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        impl_display_from_str!($name { $($variant => stringify!($variant)),* });
+    };
+    ($name:ident { $($variant:ident => $text:expr),* $(,)? }) => {
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(match self {
+                    $($name::$variant => $text,)*
+                })
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $(if s == $text {
+                    return Ok($name::$variant);
+                })*
+                Err(format!("unknown {}: {s}", stringify!($name)))
+            }
+        }
+    };
+}
+
 pub type Boolean = bool;
 
 /**
@@ -58,16 +222,237 @@ pub type Decimal = f64;
  *
  * @since 3.17.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
 pub enum LSPAny {
     LSPObject(LSPObject),
     LSPArray(LSPArray),
     String(String),
     Integer(Integer),
     UInteger(UInteger),
+    /// integers outside the `Integer` and `UInteger` ranges, which would
+    /// otherwise lose precision as a `Decimal`
+    Integer64(i64),
+    UInteger64(u64),
     Decimal(Decimal),
     Boolean(Boolean),
-    // Null
+    Null,
+}
+
+impl From<String> for LSPAny {
+    fn from(string: String) -> Self {
+        LSPAny::String(string)
+    }
+}
+
+impl From<&str> for LSPAny {
+    fn from(string: &str) -> Self {
+        LSPAny::String(string.to_owned())
+    }
+}
+
+impl From<Integer> for LSPAny {
+    fn from(integer: Integer) -> Self {
+        LSPAny::Integer(integer)
+    }
+}
+
+impl From<UInteger> for LSPAny {
+    fn from(integer: UInteger) -> Self {
+        LSPAny::UInteger(integer)
+    }
+}
+
+/// the narrowest variant holding `integer`, as it would be deserialized
+impl From<i64> for LSPAny {
+    fn from(integer: i64) -> Self {
+        if let Ok(integer) = Integer::try_from(integer) {
+            LSPAny::Integer(integer)
+        } else if let Ok(integer) = UInteger::try_from(integer) {
+            LSPAny::UInteger(integer)
+        } else {
+            LSPAny::Integer64(integer)
+        }
+    }
+}
+
+/// the narrowest variant holding `integer`, as it would be deserialized
+impl From<u64> for LSPAny {
+    fn from(integer: u64) -> Self {
+        match i64::try_from(integer) {
+            Ok(integer) => LSPAny::from(integer),
+            Err(_) => LSPAny::UInteger64(integer),
+        }
+    }
+}
+
+impl From<Decimal> for LSPAny {
+    fn from(decimal: Decimal) -> Self {
+        LSPAny::Decimal(decimal)
+    }
+}
+
+impl From<Boolean> for LSPAny {
+    fn from(boolean: Boolean) -> Self {
+        LSPAny::Boolean(boolean)
+    }
+}
+
+impl From<LSPObject> for LSPAny {
+    fn from(object: LSPObject) -> Self {
+        LSPAny::LSPObject(object)
+    }
+}
+
+impl From<LSPArray> for LSPAny {
+    fn from(array: LSPArray) -> Self {
+        LSPAny::LSPArray(array)
+    }
+}
+
+/// `None` is `null`
+impl<T: Into<LSPAny>> From<Option<T>> for LSPAny {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(LSPAny::Null, Into::into)
+    }
+}
+
+/// builds an [LSPAny::LSPObject] like `serde_json::json!` builds an object,
+/// e.g. `lsp_object! { "id" => 1, "tags" => lsp_array!["a", "b"] }`; every
+/// value is converted with `LSPAny::from`
+///
+/// ```
+/// use rust_lsp_types::{lsp_array, lsp_object};
+///
+/// let options = lsp_object! {
+///     "checkOnSave" => true,
+///     "target" => None::<String>,
+///     "features" => lsp_array!["serde", "rc"],
+/// };
+/// assert_eq!(
+///     serde_json::to_value(&options).unwrap(),
+///     serde_json::json!({ "checkOnSave": true, "target": null, "features": ["serde", "rc"] })
+/// );
+/// ```
+#[macro_export]
+macro_rules! lsp_object {
+    ($($key:expr => $value:expr),* $(,)?) => {
+        $crate::LSPAny::LSPObject({
+            #[allow(unused_mut)]
+            let mut object = $crate::LSPObject::new();
+            $(object.insert(::std::string::String::from($key), $crate::LSPAny::from($value));)*
+            object
+        })
+    };
+}
+
+/// builds an [LSPAny::LSPArray], converting every value with `LSPAny::from`,
+/// see [lsp_object!]
+///
+/// ```
+/// use rust_lsp_types::{lsp_array, LSPAny};
+///
+/// let array = lsp_array![1, "two", 3.5];
+/// assert_eq!(
+///     array,
+///     LSPAny::LSPArray(vec![
+///         LSPAny::Integer(1),
+///         LSPAny::String("two".into()),
+///         LSPAny::Decimal(3.5),
+///     ])
+/// );
+/// ```
+#[macro_export]
+macro_rules! lsp_array {
+    ($($value:expr),* $(,)?) => {
+        $crate::LSPAny::LSPArray(::std::vec![$($crate::LSPAny::from($value)),*])
+    };
+}
+
+/// a `T | null` value: [Nullable::Null] is the literal `null`.
+///
+/// Unlike an optional property the member is required: a missing member is a
+/// deserialization error rather than `Null`, and it must never be paired with
+/// `skip_serializing_if`, since e.g. a request result of `null` ("no hover")
+/// has to be sent as `"result":null` rather than left out. Where the member
+/// itself may also be absent use `Option<Nullable<T>>` with
+/// `deserialize_with = "deserialize_present"`, see [ResponseMessage::result].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Nullable<T> {
+    #[default]
+    Null,
+    Value(T),
+}
+
+impl<T> Nullable<T> {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Nullable::Null)
+    }
+
+    pub fn as_ref(&self) -> Nullable<&T> {
+        match self {
+            Nullable::Null => Nullable::Null,
+            Nullable::Value(value) => Nullable::Value(value),
+        }
+    }
+
+    /// the value, if not `null`
+    pub fn into_option(self) -> Option<T> {
+        self.into()
+    }
+}
+
+impl<T> From<Option<T>> for Nullable<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            None => Nullable::Null,
+            Some(value) => Nullable::Value(value),
+        }
+    }
+}
+
+impl<T> From<Nullable<T>> for Option<T> {
+    fn from(value: Nullable<T>) -> Self {
+        match value {
+            Nullable::Null => None,
+            Nullable::Value(value) => Some(value),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Nullable<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Nullable::Null => serializer.serialize_none(),
+            Nullable::Value(value) => serializer.serialize_some(value),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Nullable<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        // read through a `Value` rather than `deserialize_option`, which
+        // serde answers with `None` for a missing member
+        let value = serde_json::Value::deserialize(deserializer)?;
+        if value.is_null() {
+            return Ok(Nullable::Null);
+        }
+        T::deserialize(value)
+            .map(Nullable::Value)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// deserializes a member that is present (possibly `null`) as `Some`, leaving
+/// `None` for a missing member via `#[serde(default)]`
+fn deserialize_present<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
 }
 
 /**
@@ -97,7 +482,7 @@ pub struct RequestMessage {
     /**
      * The request id.
      */
-    pub id: IntegerOrString,
+    pub id: RequestId,
 
     /**
      * The method to be invoked.
@@ -108,9 +493,18 @@ pub struct RequestMessage {
      * The method's params.
      */
     // params: Option<array> | object,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub params: Option<ArrayOrObject>,
 }
 
+impl RequestMessage {
+    /// the params decoded as `T`, reading absent params as `null` so that
+    /// requests without any (e.g. `shutdown`) decode as [NoParams]
+    pub fn params_as<T: DeserializeOwned>(&self) -> Result<T, ParseError> {
+        decode_params(&self.method, self.params.as_ref())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ResponseMessage {
     /// extends Message
@@ -118,20 +512,50 @@ pub struct ResponseMessage {
     /**
      * The request id.
      */
-    pub id: Option<IntegerOrString>,
+    pub id: Nullable<RequestId>,
 
     /**
      * The result of a request. This member is REQUIRED on success.
      * This member MUST NOT exist if there was an error invoking the method.
      */
-    pub result: Option<LSPAny>,
+    /// `None` leaves the member out (error responses), `Some(Nullable::Null)`
+    /// is a successful `null` result
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_present"
+    )]
+    pub result: Option<Nullable<LSPAny>>,
 
     /**
      * The error object in case a request fails.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<ResponseError>,
 }
 
+impl ResponseMessage {
+    /// deserializes like `Deserialize`, but also rejects a message whose
+    /// `result` and `error` are both present or both missing, which the spec
+    /// forbids. Usable as `#[serde(deserialize_with = "...")]` or directly,
+    /// e.g. with a [serde_json::Deserializer].
+    pub fn deserialize_strict<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let message = ResponseMessage::deserialize(deserializer)?;
+        match (&message.result, &message.error) {
+            (Some(_), None) | (None, Some(_)) => Ok(message),
+            (Some(_), Some(_)) => Err(serde::de::Error::custom(
+                "response message has both a result and an error",
+            )),
+            (None, None) => Err(serde::de::Error::custom(
+                "response message has neither a result nor an error",
+            )),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ResponseError {
     /**
@@ -148,9 +572,18 @@ pub struct ResponseError {
      * A primitive or structured value that contains additional
      * information about the error. Can be omitted.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<LSPAny>,
 }
 
+impl ResponseError {
+    /// whether the request failed because the document changed while it was
+    /// being processed, which a client may answer by retrying it
+    pub fn is_content_modified(&self) -> bool {
+        self.code == ErrorCodes::ContentModified
+    }
+}
+
 pub mod ErrorCodes {
     use super::*;
     // Defined by JSON-RPC
@@ -255,23 +688,291 @@ pub struct NotificationMessage {
     /**
      * The notification's params.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub params: Option<ArrayOrObject>,
 }
 
+impl NotificationMessage {
+    /// the params decoded as `T`, reading absent params as `null` so that
+    /// notifications without any (e.g. `exit`) decode as [NoParams]
+    pub fn params_as<T: DeserializeOwned>(&self) -> Result<T, ParseError> {
+        decode_params(&self.method, self.params.as_ref())
+    }
+}
+
+fn decode_params<T: DeserializeOwned>(
+    method: &str,
+    params: Option<&ArrayOrObject>,
+) -> Result<T, ParseError> {
+    let params = match params {
+        Some(params) => serde_json::to_value(params),
+        None => Ok(serde_json::Value::Null),
+    };
+    params
+        .and_then(serde_json::from_value)
+        .map_err(|source| ParseError::InvalidParams {
+            method: method.to_owned(),
+            source,
+        })
+}
+
+/// any incoming JSON-RPC message, told apart by its members: one with a
+/// `method` and an `id` is a request, one with only a `method` a
+/// notification, and any other a response
+#[derive(Debug)]
+pub enum AnyMessage {
+    Request(RequestMessage),
+    Notification(NotificationMessage),
+    Response(ResponseMessage),
+}
+
+impl AnyMessage {
+    pub fn into_request(self) -> Result<RequestMessage, ParseError> {
+        match self {
+            AnyMessage::Request(request) => Ok(request),
+            _ => Err(ParseError::NotARequest),
+        }
+    }
+
+    pub fn into_notification(self) -> Result<NotificationMessage, ParseError> {
+        match self {
+            AnyMessage::Notification(notification) => Ok(notification),
+            _ => Err(ParseError::NotANotification),
+        }
+    }
+
+    pub fn into_response(self) -> Result<ResponseMessage, ParseError> {
+        match self {
+            AnyMessage::Response(response) => Ok(response),
+            _ => Err(ParseError::NotAResponse),
+        }
+    }
+
+    /// the request's or notification's method, `None` for a response
+    pub fn method(&self) -> Option<&str> {
+        match self {
+            AnyMessage::Request(request) => Some(&request.method),
+            AnyMessage::Notification(notification) => Some(&notification.method),
+            AnyMessage::Response(_) => None,
+        }
+    }
+
+    /// the message if it's a response or its method is one of
+    /// [PROTOCOL_METHODS], [ParseError::UnknownMethod] otherwise; for a
+    /// dispatcher without handlers for server or language specific extensions
+    pub fn require_known_method(self) -> Result<Self, ParseError> {
+        match self.method() {
+            Some(method) if !PROTOCOL_METHODS.contains(&method) => {
+                Err(ParseError::UnknownMethod(method.to_owned()))
+            }
+            _ => Ok(self),
+        }
+    }
+}
+
+/// the methods of every request and notification the protocol defines
+pub const PROTOCOL_METHODS: &[&str] = &[
+    "$/cancelRequest",
+    "$/logTrace",
+    "$/progress",
+    "$/setTrace",
+    "callHierarchy/incomingCalls",
+    "callHierarchy/outgoingCalls",
+    "client/registerCapability",
+    "client/unregisterCapability",
+    "codeAction/resolve",
+    "codeLens/resolve",
+    "completionItem/resolve",
+    "documentLink/resolve",
+    "exit",
+    "initialize",
+    "initialized",
+    "inlayHint/resolve",
+    "notebookDocument/didChange",
+    "notebookDocument/didClose",
+    "notebookDocument/didOpen",
+    "notebookDocument/didSave",
+    "shutdown",
+    "telemetry/event",
+    "textDocument/codeAction",
+    "textDocument/codeLens",
+    "textDocument/colorPresentation",
+    "textDocument/completion",
+    "textDocument/declaration",
+    "textDocument/definition",
+    "textDocument/diagnostic",
+    "textDocument/didChange",
+    "textDocument/didClose",
+    "textDocument/didOpen",
+    "textDocument/didSave",
+    "textDocument/documentColor",
+    "textDocument/documentHighlight",
+    "textDocument/documentLink",
+    "textDocument/documentSymbol",
+    "textDocument/foldingRange",
+    "textDocument/formatting",
+    "textDocument/hover",
+    "textDocument/implementation",
+    "textDocument/inlayHint",
+    "textDocument/inlineValue",
+    "textDocument/linkedEditingRange",
+    "textDocument/moniker",
+    "textDocument/onTypeFormatting",
+    "textDocument/prepareCallHierarchy",
+    "textDocument/prepareRename",
+    "textDocument/prepareTypeHierarchy",
+    "textDocument/publishDiagnostics",
+    "textDocument/rangeFormatting",
+    "textDocument/references",
+    "textDocument/rename",
+    "textDocument/selectionRange",
+    "textDocument/semanticTokens/full",
+    "textDocument/semanticTokens/full/delta",
+    "textDocument/semanticTokens/range",
+    "textDocument/signatureHelp",
+    "textDocument/typeDefinition",
+    "textDocument/willSave",
+    "textDocument/willSaveWaitUntil",
+    "typeHierarchy/subtypes",
+    "typeHierarchy/supertypes",
+    "window/logMessage",
+    "window/showDocument",
+    "window/showMessage",
+    "window/showMessageRequest",
+    "window/workDoneProgress/cancel",
+    "window/workDoneProgress/create",
+    "workspace/applyEdit",
+    "workspace/codeLens/refresh",
+    "workspace/configuration",
+    "workspace/diagnostic",
+    "workspace/diagnostic/refresh",
+    "workspace/didChangeConfiguration",
+    "workspace/didChangeWatchedFiles",
+    "workspace/didChangeWorkspaceFolders",
+    "workspace/didCreateFiles",
+    "workspace/didDeleteFiles",
+    "workspace/didRenameFiles",
+    "workspace/executeCommand",
+    "workspace/inlayHint/refresh",
+    "workspace/inlineValue/refresh",
+    "workspace/semanticTokens/refresh",
+    "workspace/symbol",
+    "workspace/willCreateFiles",
+    "workspace/willDeleteFiles",
+    "workspace/willRenameFiles",
+    "workspace/workspaceFolders",
+    "workspaceSymbol/resolve",
+];
+
+impl std::str::FromStr for AnyMessage {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let message: serde_json::Value =
+            serde_json::from_str(s).map_err(ParseError::InvalidMessage)?;
+        let Some(members) = message.as_object() else {
+            return Err(ParseError::InvalidMessage(serde::de::Error::custom(
+                "a message must be an object",
+            )));
+        };
+        let message = match (members.contains_key("method"), members.contains_key("id")) {
+            (true, true) => serde_json::from_value(message).map(AnyMessage::Request),
+            (true, false) => serde_json::from_value(message).map(AnyMessage::Notification),
+            (false, _) => ResponseMessage::deserialize_strict(message).map(AnyMessage::Response),
+        };
+        message.map_err(ParseError::InvalidMessage)
+    }
+}
+
+/// why an incoming message couldn't be read or dispatched
+#[derive(Debug)]
+pub enum ParseError {
+    /// the text isn't a well-formed JSON-RPC message
+    InvalidMessage(serde_json::Error),
+    /// the method isn't one the protocol defines, see
+    /// [AnyMessage::require_known_method]; also for a dispatcher's fallback arm
+    UnknownMethod(String),
+    /// the params don't match the type `method` takes
+    InvalidParams {
+        method: String,
+        source: serde_json::Error,
+    },
+    NotARequest,
+    NotANotification,
+    NotAResponse,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidMessage(source) => write!(f, "invalid message: {source}"),
+            ParseError::UnknownMethod(method) => write!(f, "unknown method {method}"),
+            ParseError::InvalidParams { method, source } => {
+                write!(f, "invalid params for {method}: {source}")
+            }
+            ParseError::NotARequest => write!(f, "message is not a request"),
+            ParseError::NotANotification => write!(f, "message is not a notification"),
+            ParseError::NotAResponse => write!(f, "message is not a response"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::InvalidMessage(source) | ParseError::InvalidParams { source, .. } => {
+                Some(source)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// the params of a request or notification that takes none, such as
+/// `shutdown`, `exit` or `workspace/semanticTokens/refresh`; it is sent as
+/// `null` and read from `null` or absent params
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoParams;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CancelParams {
     /**
      * The request id to cancel.
      */
-    pub id: IntegerOrString,
+    pub id: RequestId,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl CancelParams {
+    pub fn new(id: impl Into<RequestId>) -> Self {
+        CancelParams { id: id.into() }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
 pub enum ProgressToken {
     Integer(Integer),
     String(String),
 }
 
+impl From<Integer> for ProgressToken {
+    fn from(integer: Integer) -> Self {
+        ProgressToken::Integer(integer)
+    }
+}
+
+impl From<String> for ProgressToken {
+    fn from(string: String) -> Self {
+        ProgressToken::String(string)
+    }
+}
+
+impl From<&str> for ProgressToken {
+    fn from(string: &str) -> Self {
+        ProgressToken::String(string.to_owned())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ProgressParams<T> {
     /**
@@ -285,6 +986,40 @@ pub struct ProgressParams<T> {
     pub value: T,
 }
 
+impl<T> ProgressParams<T> {
+    pub fn new(token: impl Into<ProgressToken>, value: T) -> Self {
+        ProgressParams {
+            token: token.into(),
+            value,
+        }
+    }
+}
+
+impl<T: WorkDoneProgressValue> ProgressParams<T> {
+    /// a `$/progress` notification reporting work done progress
+    pub fn work_done(token: impl Into<ProgressToken>, value: T) -> Self {
+        ProgressParams::new(token, value)
+    }
+}
+
+impl ProgressParams<LSPAny> {
+    /// the value, received untyped, reinterpreted as the payload the token
+    /// was created for, e.g. a [WorkDoneProgressReport] or a request's
+    /// partial result
+    pub fn value_as<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::to_value(&self.value).and_then(serde_json::from_value)
+    }
+}
+
+/// the params of a `$/progress` notification beginning work done progress
+pub type WorkDoneProgressBeginParams = ProgressParams<WorkDoneProgressBegin>;
+
+/// the params of a `$/progress` notification reporting work done progress
+pub type WorkDoneProgressReportParams = ProgressParams<WorkDoneProgressReport>;
+
+/// the params of a `$/progress` notification ending work done progress
+pub type WorkDoneProgressEndParams = ProgressParams<WorkDoneProgressEnd>;
+
 /// extracted out for [HoverParams1::position]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct HoverParamsPosition {
@@ -305,7 +1040,7 @@ pub struct HoverResult {
     pub value: String,
 }
 
-/// ```
+/// ```text
 ///   foo://example.com:8042/over/there?name=ferret#nose
 ///   \_/   \______________/\_________/ \_________/ \__/
 ///    |           |            |            |        |
@@ -315,11 +1050,100 @@ pub struct HoverResult {
 ///   urn:example:animal:ferret:nose
 /// ```
 ///
-/// ```
+/// ```text
 /// file:///c:/project/readme.md
 /// file:///C%3A/project/readme.md
 /// ```
-type DocumentUri = String;
+///
+/// A uri dereferences to its `str` and converts from and into a `String`, with
+/// the same API whether or not the `shared-uri` feature is on. With the
+/// feature it's reference counted, so cloning one (e.g. into every diagnostic
+/// of a batch) doesn't allocate; either way it serializes as a plain string.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct DocumentUri(DocumentUriStorage);
+
+#[cfg(not(feature = "shared-uri"))]
+type DocumentUriStorage = String;
+
+#[cfg(feature = "shared-uri")]
+type DocumentUriStorage = std::sync::Arc<str>;
+
+impl DocumentUri {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for DocumentUri {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for DocumentUri {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::borrow::Borrow<str> for DocumentUri {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for DocumentUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for DocumentUri {
+    // a no-op without `shared-uri`
+    #[allow(clippy::useless_conversion)]
+    fn from(uri: String) -> Self {
+        DocumentUri(uri.into())
+    }
+}
+
+impl From<&str> for DocumentUri {
+    fn from(uri: &str) -> Self {
+        DocumentUri(uri.into())
+    }
+}
+
+impl From<&String> for DocumentUri {
+    fn from(uri: &String) -> Self {
+        DocumentUri(uri.as_str().into())
+    }
+}
+
+impl From<DocumentUri> for String {
+    fn from(uri: DocumentUri) -> Self {
+        uri.0.to_string()
+    }
+}
+
+impl PartialEq<str> for DocumentUri {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for DocumentUri {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for DocumentUri {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
 
 type URI = String;
 
@@ -336,6 +1160,7 @@ pub struct RegularExpressionsClientCapabilities {
     /**
      * The engine's version.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
 }
 
@@ -354,7 +1179,8 @@ pub enum EOL {
     CR,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Ordered by line, then character, i.e. in document order.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Position {
     /**
      * Line position in a document (zero-based).
@@ -371,6 +1197,23 @@ pub struct Position {
     pub character: UInteger,
 }
 
+impl Position {
+    /// the position moved into `text`: a line past the last one becomes the
+    /// last line, and a character past the end of its line becomes the line's
+    /// length in `encoding`, as the protocol reads such positions
+    pub fn clamp_to(&self, text: &str, encoding: PositionEncodingKind) -> Position {
+        let line = self.line.min(line_breaks(text));
+        let line_text = lines_with_breaks(text)
+            .nth(line as usize)
+            .unwrap_or_default();
+        let line_text = without_line_break(line_text);
+        Position {
+            line,
+            character: self.character.min(encoded_len(line_text, encoding)),
+        }
+    }
+}
+
 /**
  * A type indicating how positions are encoded,
  * specifically what column offsets mean.
@@ -383,7 +1226,7 @@ pub struct Position {
  *
  * @since 3.17.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PositionEncodingKind {
     /**
      * Character offsets count UTF-8 code units (e.g bytes).
@@ -415,7 +1258,7 @@ pub enum PositionEncodingKind {
 ///      pub start: { line: 5, character: 23 },
 ///      end : { line: 6, character: 0 }
 ///  }
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Range {
     /**
      * The range's start position.
@@ -428,6 +1271,85 @@ pub struct Range {
     pub end: Position,
 }
 
+impl Range {
+    /// whether the range doesn't end before it starts
+    pub fn is_valid(&self) -> bool {
+        self.start <= self.end
+    }
+
+    /// the same range with its endpoints swapped if it was inverted
+    pub fn normalized(self) -> Range {
+        if self.is_valid() {
+            self
+        } else {
+            Range {
+                start: self.end,
+                end: self.start,
+            }
+        }
+    }
+
+    /// the number of lines the range touches, counting both the start and the
+    /// end line; an empty range counts as one
+    pub fn line_count(&self) -> u32 {
+        self.end.line.abs_diff(self.start.line) + 1
+    }
+
+    /// whether the range starts and ends on the same line, as e.g. a
+    /// completion item's text edit has to
+    pub fn is_single_line(&self) -> bool {
+        self.start.line == self.end.line
+    }
+}
+
+/// `(line, character)`
+///
+/// ```
+/// use rust_lsp_types::Position;
+///
+/// assert_eq!(Position::from((3, 7)), Position { line: 3, character: 7 });
+/// ```
+impl From<(UInteger, UInteger)> for Position {
+    fn from((line, character): (UInteger, UInteger)) -> Self {
+        Position { line, character }
+    }
+}
+
+/// `(start, end)`
+impl From<(Position, Position)> for Range {
+    fn from((start, end): (Position, Position)) -> Self {
+        Range { start, end }
+    }
+}
+
+/// `((start line, start character), (end line, end character))`
+///
+/// ```
+/// use rust_lsp_types::{Position, Range};
+///
+/// let range = Range::from(((1, 0), (1, 4)));
+/// assert_eq!(range.start, Position { line: 1, character: 0 });
+/// assert_eq!(range.end, Position { line: 1, character: 4 });
+///
+/// let (start, end) = range.into();
+/// assert_eq!(Range::from((start, end)), range);
+/// ```
+impl From<((UInteger, UInteger), (UInteger, UInteger))> for Range {
+    fn from((start, end): ((UInteger, UInteger), (UInteger, UInteger))) -> Self {
+        Range {
+            start: start.into(),
+            end: end.into(),
+        }
+    }
+}
+
+/// `(start, end)`
+impl From<Range> for (Position, Position) {
+    fn from(range: Range) -> Self {
+        (range.start, range.end)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TextDocumentItem {
     /**
@@ -452,6 +1374,97 @@ pub struct TextDocumentItem {
     pub text: String,
 }
 
+impl TextDocumentItem {
+    /// ```
+    /// use rust_lsp_types::{LanguageId, TextDocumentItem};
+    ///
+    /// let item = TextDocumentItem::new("file:///src/lib.rs", LanguageId::Rust, 0, "");
+    /// assert_eq!(item.languageId, "rust");
+    /// assert_eq!(item.uri, "file:///src/lib.rs");
+    /// ```
+    pub fn new(
+        uri: impl Into<DocumentUri>,
+        language_id: impl Into<String>,
+        version: Integer,
+        text: impl Into<String>,
+    ) -> Self {
+        TextDocumentItem {
+            uri: uri.into(),
+            languageId: language_id.into(),
+            version,
+            text: text.into(),
+        }
+    }
+}
+
+/// the well-known language identifiers for [TextDocumentItem::languageId]
+///
+/// ```
+/// use rust_lsp_types::LanguageId;
+///
+/// assert_eq!(LanguageId::TypeScriptReact, "typescriptreact");
+/// assert_eq!(LanguageId::Pug, "jade");
+/// ```
+pub mod LanguageId {
+    pub const ABAP: &str = "abap";
+    pub const WindowsBat: &str = "bat";
+    pub const BibTeX: &str = "bibtex";
+    pub const Clojure: &str = "clojure";
+    pub const Coffeescript: &str = "coffeescript";
+    pub const C: &str = "c";
+    pub const Cpp: &str = "cpp";
+    pub const CSharp: &str = "csharp";
+    pub const CSS: &str = "css";
+    pub const Diff: &str = "diff";
+    pub const Dart: &str = "dart";
+    pub const Dockerfile: &str = "dockerfile";
+    pub const Elixir: &str = "elixir";
+    pub const Erlang: &str = "erlang";
+    pub const FSharp: &str = "fsharp";
+    pub const GitCommit: &str = "git-commit";
+    pub const GitRebase: &str = "git-rebase";
+    pub const Go: &str = "go";
+    pub const Groovy: &str = "groovy";
+    pub const Handlebars: &str = "handlebars";
+    pub const HTML: &str = "html";
+    pub const Ini: &str = "ini";
+    pub const Java: &str = "java";
+    pub const JavaScript: &str = "javascript";
+    pub const JavaScriptReact: &str = "javascriptreact";
+    pub const JSON: &str = "json";
+    pub const LaTeX: &str = "latex";
+    pub const Less: &str = "less";
+    pub const Lua: &str = "lua";
+    pub const Makefile: &str = "makefile";
+    pub const Markdown: &str = "markdown";
+    pub const ObjectiveC: &str = "objective-c";
+    pub const ObjectiveCpp: &str = "objective-cpp";
+    pub const Perl: &str = "perl";
+    pub const Perl6: &str = "perl6";
+    pub const PHP: &str = "php";
+    pub const Powershell: &str = "powershell";
+    pub const Pug: &str = "jade";
+    pub const Python: &str = "python";
+    pub const R: &str = "r";
+    pub const Razor: &str = "razor";
+    pub const Ruby: &str = "ruby";
+    pub const Rust: &str = "rust";
+    pub const SCSS: &str = "scss";
+    pub const Sass: &str = "sass";
+    pub const Scala: &str = "scala";
+    pub const ShaderLab: &str = "shaderlab";
+    pub const ShellScript: &str = "shellscript";
+    pub const SQL: &str = "sql";
+    pub const Swift: &str = "swift";
+    pub const TypeScript: &str = "typescript";
+    pub const TypeScriptReact: &str = "typescriptreact";
+    pub const TeX: &str = "tex";
+    pub const VisualBasic: &str = "vb";
+    pub const XML: &str = "xml";
+    pub const XSL: &str = "xsl";
+    pub const YAML: &str = "yaml";
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TextDocumentIdentifier {
     /**
@@ -460,7 +1473,13 @@ pub struct TextDocumentIdentifier {
     pub uri: DocumentUri,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl TextDocumentIdentifier {
+    pub fn new(uri: impl Into<DocumentUri>) -> Self {
+        TextDocumentIdentifier { uri: uri.into() }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct VersionedTextDocumentIdentifier {
     /// extends TextDocumentIdentifier
     /**
@@ -476,7 +1495,31 @@ pub struct VersionedTextDocumentIdentifier {
     pub version: Integer,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl VersionedTextDocumentIdentifier {
+    pub fn new(uri: impl Into<DocumentUri>, version: Integer) -> Self {
+        VersionedTextDocumentIdentifier {
+            uri: uri.into(),
+            version,
+        }
+    }
+
+    /// the identifier for the document after one more change, `None` once
+    /// the version would overflow `Integer`
+    pub fn next_version(&self) -> Option<Self> {
+        Some(VersionedTextDocumentIdentifier {
+            uri: self.uri.clone(),
+            version: self.version.checked_add(1)?,
+        })
+    }
+}
+
+impl From<(DocumentUri, Integer)> for VersionedTextDocumentIdentifier {
+    fn from((uri, version): (DocumentUri, Integer)) -> Self {
+        VersionedTextDocumentIdentifier { uri, version }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct OptionalVersionedTextDocumentIdentifier {
     /// extends TextDocumentIdentifier
     /**
@@ -494,7 +1537,7 @@ pub struct OptionalVersionedTextDocumentIdentifier {
      * The version number of a document will increase after each change,
      * including undo/redo. The number doesn't need to be consecutive.
      */
-    pub version: Option<Integer>,
+    pub version: Nullable<Integer>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -510,16 +1553,18 @@ pub struct TextDocumentPositionParams {
     pub position: Position,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DocumentFilter {
     /**
      * A language id, like `typescript`.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
 
     /**
      * A Uri scheme, like `file` or `untitled`.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub scheme: Option<String>,
 
     /**
@@ -536,12 +1581,289 @@ pub struct DocumentFilter {
      *   (e.g., `example.[!0-9]` to match on `example.a`, `example.b`, but
      *   not `example.0`)
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pattern: Option<String>,
 }
 
+impl DocumentFilter {
+    /// Whether a document with this `uri` and `language_id` is selected by this filter.
+    /// Every property that is set has to match; `pattern` is matched against the
+    /// path component of the URI (e.g. `/home/user/project/main.ts`), so relative
+    /// patterns like `*.ts` should normally be written as `**/*.ts`. The
+    /// pattern is compiled on every call, see [DocumentFilter::compile].
+    pub fn matches(&self, uri: &str, language_id: &str) -> bool {
+        let (scheme, path) = split_uri(uri);
+        let path = decode_uri_path(path);
+        self.language
+            .as_ref()
+            .is_none_or(|language| language == language_id)
+            && self.scheme.as_ref().is_none_or(|s| s == scheme)
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|pattern| Glob::new(pattern).matches(&path))
+    }
+
+    /// this filter with its pattern compiled, for matching many documents
+    pub fn compile(&self) -> CompiledFilter {
+        CompiledFilter {
+            kind: self.language.clone(),
+            scheme: self.scheme.clone(),
+            glob: self.pattern.as_deref().map(Glob::new),
+        }
+    }
+}
+
+/// splits a URI into its scheme and its path, dropping the authority, query and fragment
+fn split_uri(uri: &str) -> (&str, &str) {
+    let Some((scheme, rest)) = uri.split_once(':') else {
+        return ("", uri);
+    };
+    let rest = match rest.strip_prefix("//") {
+        Some(authority_and_path) => authority_and_path
+            .find('/')
+            .map_or("", |index| &authority_and_path[index..]),
+        None => rest,
+    };
+    let end = rest.find(['?', '#']).unwrap_or(rest.len());
+    (scheme, &rest[..end])
+}
+
+/// `path` with its `%XX` escapes decoded, so a pattern like `**/my file.ts`
+/// sees the characters it was written with; left as is if an escape is
+/// malformed
+fn decode_uri_path(path: &str) -> std::borrow::Cow<'_, str> {
+    match path.contains('%') {
+        true => decode_uri_component(path).map_or(path.into(), Into::into),
+        false => path.into(),
+    }
+}
+
+/// a glob using the syntax documented on [DocumentFilter::pattern], shared by
+/// [DocumentFilter] and [NotebookDocumentFilter]. Compile it once with
+/// [Glob::new] to match it against many paths.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    /// one pattern per combination of `{}` alternatives
+    alternatives: Vec<Vec<char>>,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Self {
+        Glob {
+            alternatives: expand_glob_braces(pattern)
+                .iter()
+                .map(|pattern| pattern.chars().collect())
+                .collect(),
+        }
+    }
+
+    /// A pattern with a wildcard allocates a table of
+    /// `(path.len() + 1) * (pattern.len() + 1)` flags per call, shared by its
+    /// `{}` alternatives; a pattern without one doesn't allocate.
+    pub fn matches(&self, path: &str) -> bool {
+        let mut failed = Vec::new();
+        self.alternatives.iter().any(|pattern| {
+            GlobMatcher {
+                pattern,
+                path,
+                failed: &mut failed,
+                backtracking: false,
+            }
+            .matches(0, 0)
+        })
+    }
+}
+
+/// matches one `{}`-free pattern against a path. `*` and `**` backtrack, so
+/// the (pattern index, path offset) pairs known not to match are remembered,
+/// keeping nested wildcards polynomial.
+struct GlobMatcher<'a> {
+    pattern: &'a [char],
+    path: &'a str,
+    /// indexed by `offset * (pattern.len() + 1) + index`; only filled in once
+    /// the pattern reaches a wildcard
+    failed: &'a mut Vec<bool>,
+    backtracking: bool,
+}
+
+impl GlobMatcher<'_> {
+    fn matches(&mut self, index: usize, offset: usize) -> bool {
+        let key = offset * (self.pattern.len() + 1) + index;
+        if self.backtracking && self.failed[key] {
+            return false;
+        }
+        let matched = self.matches_uncached(index, offset);
+        if !matched && self.backtracking {
+            self.failed[key] = true;
+        }
+        matched
+    }
+
+    /// sizes the table for this pattern, reusing the one left by a previous
+    /// alternative
+    fn backtracks(&mut self) {
+        if !self.backtracking {
+            self.failed.clear();
+            self.failed
+                .resize((self.path.len() + 1) * (self.pattern.len() + 1), false);
+            self.backtracking = true;
+        }
+    }
+
+    fn matches_uncached(&mut self, index: usize, offset: usize) -> bool {
+        let path = &self.path[offset..];
+        let boundaries = |end: usize| (0..=end).filter(move |end| path.is_char_boundary(*end));
+        match &self.pattern[index..] {
+            [] => path.is_empty(),
+            ['*', '*', '/', ..] => {
+                self.backtracks();
+                self.matches(index + 3, offset)
+                    || path
+                        .match_indices('/')
+                        .any(|(slash, _)| self.matches(index + 3, offset + slash + 1))
+            }
+            ['*', '*', ..] => {
+                self.backtracks();
+                boundaries(path.len()).any(|end| self.matches(index + 2, offset + end))
+            }
+            ['*', ..] => {
+                self.backtracks();
+                let segment = path.find('/').unwrap_or(path.len());
+                boundaries(segment).any(|end| self.matches(index + 1, offset + end))
+            }
+            ['?', ..] => match path.chars().next() {
+                Some(c) if c != '/' => self.matches(index + 1, offset + c.len_utf8()),
+                _ => false,
+            },
+            ['[', class @ ..] => match class.iter().skip(1).position(|c| *c == ']') {
+                Some(close) => match path.chars().next() {
+                    Some(c) if c != '/' => {
+                        glob_class_matches(&class[..close + 1], c)
+                            && self.matches(index + close + 3, offset + c.len_utf8())
+                    }
+                    _ => false,
+                },
+                None => path.starts_with('[') && self.matches(index + 1, offset + 1),
+            },
+            [c, ..] => path.starts_with(*c) && self.matches(index + 1, offset + c.len_utf8()),
+        }
+    }
+}
+
+/// expands `{a,b}` groups (which may nest) into one pattern per alternative
+fn expand_glob_braces(pattern: &str) -> Vec<String> {
+    let Some(open) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+    let mut depth = 0;
+    let mut alternatives = Vec::new();
+    let mut start = open + 1;
+    for (index, c) in pattern
+        .char_indices()
+        .skip_while(|(index, _)| *index <= open)
+    {
+        match c {
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            ',' if depth == 0 => {
+                alternatives.push(&pattern[start..index]);
+                start = index + 1;
+            }
+            '}' => {
+                alternatives.push(&pattern[start..index]);
+                let (prefix, suffix) = (&pattern[..open], &pattern[index + 1..]);
+                return alternatives
+                    .into_iter()
+                    .flat_map(|alternative| {
+                        expand_glob_braces(&format!("{prefix}{alternative}{suffix}"))
+                    })
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+    // an unterminated group is matched literally
+    vec![pattern.to_string()]
+}
+
+/// `class` is the content between `[` and `]`, e.g. `0-9` or `!a-z_`
+fn glob_class_matches(class: &[char], c: char) -> bool {
+    let (negated, mut class) = match class {
+        ['!', rest @ ..] if !rest.is_empty() => (true, rest),
+        _ => (false, class),
+    };
+    let mut found = false;
+    while let Some(&first) = class.first() {
+        match class {
+            [start, '-', end, rest @ ..] => {
+                found |= (*start..=*end).contains(&c);
+                class = rest;
+            }
+            [_, rest @ ..] => {
+                found |= first == c;
+                class = rest;
+            }
+            [] => break,
+        }
+    }
+    found != negated
+}
+
 pub type DocumentSelector = Vec<DocumentFilter>;
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Whether any filter of `selector` matches the document, see [DocumentFilter::matches].
+pub fn selector_matches(selector: &DocumentSelector, uri: &str, language_id: &str) -> bool {
+    selector
+        .iter()
+        .any(|filter| filter.matches(uri, language_id))
+}
+
+/// a [DocumentFilter] or [NotebookDocumentFilter] with its pattern compiled
+/// once, for matching it against many documents; `kind` is the language id
+/// or notebook type respectively
+#[derive(Debug, Clone)]
+pub struct CompiledFilter {
+    kind: Option<String>,
+    scheme: Option<String>,
+    glob: Option<Glob>,
+}
+
+impl CompiledFilter {
+    /// matches like [DocumentFilter::matches]
+    pub fn matches(&self, uri: &str, kind: &str) -> bool {
+        let (scheme, path) = split_uri(uri);
+        self.kind.as_ref().is_none_or(|k| k == kind)
+            && self.scheme.as_ref().is_none_or(|s| s == scheme)
+            && self
+                .glob
+                .as_ref()
+                .is_none_or(|glob| glob.matches(&decode_uri_path(path)))
+    }
+}
+
+/// a [DocumentSelector] with every pattern compiled once, see [CompiledFilter]
+#[derive(Debug, Clone)]
+pub struct CompiledDocumentSelector {
+    filters: Vec<CompiledFilter>,
+}
+
+impl CompiledDocumentSelector {
+    pub fn new(selector: &DocumentSelector) -> Self {
+        CompiledDocumentSelector {
+            filters: selector.iter().map(DocumentFilter::compile).collect(),
+        }
+    }
+
+    /// matches like [selector_matches]
+    pub fn matches(&self, uri: &str, language_id: &str) -> bool {
+        self.filters
+            .iter()
+            .any(|filter| filter.matches(uri, language_id))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct TextEdit {
     /**
      * The range of the text document to be manipulated. To insert
@@ -556,12 +1878,192 @@ pub struct TextEdit {
     pub newText: String,
 }
 
+/// edits turning `old` into `new`, computed from a line diff so unchanged
+/// lines are left alone. Character offsets are counted in `encoding`, which
+/// only matters for a last line without a line break.
+pub fn text_edits_from_diff(old: &str, new: &str, encoding: PositionEncodingKind) -> Vec<TextEdit> {
+    // without the empty line after a trailing line break
+    let old_lines: Vec<&str> = lines_with_breaks(old)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let new_lines: Vec<&str> = lines_with_breaks(new)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let position = |line: usize| match old_lines.last() {
+        Some(last) if line == old_lines.len() && without_line_break(last) == *last => Position {
+            line: (line - 1) as UInteger,
+            character: encoded_len(last, encoding),
+        },
+        _ => Position {
+            line: line as UInteger,
+            character: 0,
+        },
+    };
+
+    let mut edits = Vec::new();
+    let (mut old_line, mut new_line) = (0, 0);
+    // the old line a pending edit starts at, and its replacement text
+    let mut hunk: Option<(usize, String)> = None;
+    for op in diff_lines(&old_lines, &new_lines) {
+        match op {
+            DiffOp::Equal => {
+                if let Some((start, newText)) = hunk.take() {
+                    edits.push(TextEdit {
+                        range: Range {
+                            start: position(start),
+                            end: position(old_line),
+                        },
+                        newText,
+                    });
+                }
+                old_line += 1;
+                new_line += 1;
+            }
+            DiffOp::Delete => {
+                hunk.get_or_insert_with(|| (old_line, String::new()));
+                old_line += 1;
+            }
+            DiffOp::Insert => {
+                let (_, text) = hunk.get_or_insert_with(|| (old_line, String::new()));
+                text.push_str(new_lines[new_line]);
+                new_line += 1;
+            }
+        }
+    }
+    if let Some((start, newText)) = hunk {
+        edits.push(TextEdit {
+            range: Range {
+                start: position(start),
+                end: position(old_line),
+            },
+            newText,
+        });
+    }
+    edits
+}
+
+/// the smallest range covering the ranges of all `edits`, e.g. the region a
+/// client has to re-render after applying them
+pub fn edits_bounding_range(edits: &[TextEdit]) -> Option<Range> {
+    let (first, rest) = edits.split_first()?;
+    Some(rest.iter().fold(first.range, |bounds, edit| Range {
+        start: bounds.start.min(edit.range.start),
+        end: bounds.end.max(edit.range.end),
+    }))
+}
+
+/// the lines of `text`, each with its line break: `\r\n`, `\n` or a lone
+/// `\r`, as the protocol counts them. The last line has none, so it's empty
+/// after a trailing line break.
+fn lines_with_breaks(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = Some(text);
+    std::iter::from_fn(move || {
+        let text = rest?;
+        let Some(index) = text.find(['\n', '\r']) else {
+            rest = None;
+            return Some(text);
+        };
+        let end = if text[index..].starts_with("\r\n") {
+            index + 2
+        } else {
+            index + 1
+        };
+        rest = Some(&text[end..]);
+        Some(&text[..end])
+    })
+}
+
+/// a line from [lines_with_breaks] without its line break
+fn without_line_break(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// the length of `text` in the code units of `encoding`
+fn encoded_len(text: &str, encoding: PositionEncodingKind) -> UInteger {
+    let len = match encoding {
+        PositionEncodingKind::UTF8 => text.len(),
+        PositionEncodingKind::UTF16 => text.encode_utf16().count(),
+        PositionEncodingKind::UTF32 => text.chars().count(),
+    };
+    len as UInteger
+}
+
+enum DiffOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// the shortest edit script from `a` to `b` (Myers' algorithm)
+fn diff_lines(a: &[&str], b: &[&str]) -> Vec<DiffOp> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let offset = n + m;
+    // furthest x reached on each diagonal k = x - y, indexed by k + offset
+    let mut furthest = vec![0; 2 * offset as usize + 2];
+    let mut trace = Vec::new();
+    let goes_down = |furthest: &[isize], k: isize, d: isize| {
+        let index = (k + offset) as usize;
+        k == -d || (k != d && furthest[index - 1] < furthest[index + 1])
+    };
+
+    'search: for d in 0..=offset {
+        trace.push(furthest.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if goes_down(&furthest, k, d) {
+                furthest[(k + 1 + offset) as usize]
+            } else {
+                furthest[(k - 1 + offset) as usize] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            furthest[(k + offset) as usize] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, furthest) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let previous_k = if goes_down(furthest, k, d) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x = furthest[(previous_k + offset) as usize];
+        let previous_y = previous_x - previous_k;
+        while x > previous_x && y > previous_y {
+            ops.push(DiffOp::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            ops.push(if x == previous_x {
+                DiffOp::Insert
+            } else {
+                DiffOp::Delete
+            });
+        }
+        x = previous_x;
+        y = previous_y;
+    }
+    ops.reverse();
+    ops
+}
+
 /**
  * Additional information that describes document changes.
  *
  * @since 3.16.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ChangeAnnotation {
     /**
      * A human-readable String describing the actual change. The String
@@ -573,15 +2075,27 @@ pub struct ChangeAnnotation {
      * A flag which indicates that user confirmation is needed
      * before applying the change.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub needsConfirmation: Option<Boolean>,
 
     /**
      * A human-readable String which is rendered less prominent in
      * the user interface.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 }
 
+impl ChangeAnnotation {
+    pub fn new(label: impl Into<String>) -> Self {
+        ChangeAnnotation {
+            label: label.into(),
+            needsConfirmation: None,
+            description: None,
+        }
+    }
+}
+
 /**
  * An identifier referring to a change annotation managed by a workspace
  * edit.
@@ -595,7 +2109,7 @@ pub type ChangeAnnotationIdentifier = String;
  *
  * @since 3.16.0.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct AnnotatedTextEdit {
     /// extends TextEdit
     /**
@@ -618,13 +2132,17 @@ pub struct AnnotatedTextEdit {
 }
 
 /// extracted out for [TextDocumentEdit::edits]
-#[derive(Serialize, Deserialize, Debug)]
+///
+/// `AnnotatedTextEdit` comes first: a plain `TextEdit` would also accept an
+/// annotated edit, dropping its `annotationId`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
 pub enum TextEditOrAnnotatedTextEdit {
-    TextEdit(TextEdit),
     AnnotatedTextEdit(AnnotatedTextEdit),
+    TextEdit(TextEdit),
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct TextDocumentEdit {
     /**
      * The text document to change.
@@ -640,12 +2158,95 @@ pub struct TextDocumentEdit {
     pub edits: Vec<TextEditOrAnnotatedTextEdit>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Location {
     pub uri: DocumentUri,
     pub range: Range,
 }
 
+impl Location {
+    pub fn new(uri: impl Into<DocumentUri>, range: Range) -> Self {
+        Location {
+            uri: uri.into(),
+            range,
+        }
+    }
+
+    /// a location in the file at `path`, made absolute against the current
+    /// directory if needed and encoded by [document_uri_from_file_path].
+    ///
+    /// On Windows separators become `/` and the drive gets a leading slash,
+    /// so `C:\project\readme.md` is `file:///C%3A/project/readme.md`, and a
+    /// UNC path `\\server\share` becomes `file://server/share`.
+    pub fn from_file_path(path: &Path, range: Range) -> io::Result<Location> {
+        Ok(Location::new(document_uri_from_file_path(path)?, range))
+    }
+}
+
+/// the `file://` uri of `path`, made absolute against the current directory
+/// if needed. Each path segment goes through [encode_uri_component], so
+/// `C:\project\my file.md` is `file:///C%3A/project/my%20file.md`.
+pub fn document_uri_from_file_path(path: &Path) -> io::Result<DocumentUri> {
+    let path = std::path::absolute(path)?;
+    let path = path
+        .to_str()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path is not valid UTF-8"))?;
+    let path = if cfg!(windows) {
+        path.replace('\\', "/")
+    } else {
+        path.to_owned()
+    };
+    let mut uri = String::from("file:");
+    // a windows UNC path already starts with the `//` of its authority
+    if !(cfg!(windows) && path.starts_with("//")) {
+        uri.push_str("//");
+        if !path.starts_with('/') {
+            uri.push('/');
+        }
+    }
+    let segments: Vec<String> = path.split('/').map(encode_uri_component).collect();
+    uri.push_str(&segments.join("/"));
+    Ok(uri.into())
+}
+
+/// percent-encodes every byte of `component` outside the unreserved
+/// characters `A-Z a-z 0-9 - . _ ~`, so it can stand as one segment, query
+/// value or fragment of a uri
+pub fn encode_uri_component(component: &str) -> String {
+    let mut encoded = String::with_capacity(component.len());
+    for byte in component.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// reverses [encode_uri_component], decoding every `%XX` escape; `None` if an
+/// escape is malformed or the decoded bytes aren't UTF-8
+pub fn decode_uri_component(component: &str) -> Option<String> {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = component.get(index + 1..index + 3)?;
+            if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                return None;
+            }
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct LocationLink {
     /**
@@ -654,6 +2255,7 @@ pub struct LocationLink {
      * Used as the underlined span for mouse interaction. Defaults to the word
      * range at the mouse position.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub originSelectionRange: Option<Range>,
 
     /**
@@ -677,7 +2279,15 @@ pub struct LocationLink {
     pub targetSelectionRange: Range,
 }
 
+/// `Location[] | LocationLink[]`
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum LocationsOrLocationLinks {
+    Locations(Vec<Location>),
+    LocationLinks(Vec<LocationLink>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Diagnostic {
     /**
      * The range at which the message applies.
@@ -690,11 +2300,13 @@ pub struct Diagnostic {
      * servers always provide a severity value. If omitted, it’s recommended
      * for the client to interpret it as an Error severity.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub severity: Option<DiagnosticSeverity>,
 
     /**
      * The diagnostic's code, which might appear in the user interface.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub code: Option<IntegerOrString>,
 
     /**
@@ -702,12 +2314,14 @@ pub struct Diagnostic {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub codeDescription: Option<CodeDescription>,
 
     /**
      * A human-readable String describing the source of this
      * diagnostic, e.g. 'typescript' or 'super lint'.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
 
     /**
@@ -720,12 +2334,14 @@ pub struct Diagnostic {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<DiagnosticTag>>,
 
     /**
      * An array of related diagnostic information, e.g. when symbol-names within
      * a scope collide all definitions can be marked via this property.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub relatedInformation: Option<Vec<DiagnosticRelatedInformation>>,
 
     /**
@@ -735,28 +2351,79 @@ pub struct Diagnostic {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<LSPAny>,
 }
 
-#[derive(Serialize_repr, Deserialize_repr, Debug)]
-#[repr(u8)]
-pub enum DiagnosticSeverity {
-    /**
-     * Reports an error.
-     */
-    Error = 1,
-    /**
-     * Reports a warning.
-     */
-    Warning = 2,
-    /**
-     * Reports an information.
-     */
-    Information = 3,
-    /**
-     * Reports a hint.
-     */
-    Hint = 4,
+impl Diagnostic {
+    pub fn new(range: Range, message: impl Into<String>) -> Self {
+        Diagnostic {
+            range,
+            severity: None,
+            code: None,
+            codeDescription: None,
+            source: None,
+            message: message.into(),
+            tags: None,
+            relatedInformation: None,
+            data: None,
+        }
+    }
+
+    /// a numeric or string code, e.g. `with_code(42)` or `with_code("E0277")`
+    pub fn with_code(mut self, code: impl Into<IntegerOrString>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// links the diagnostic's code to its documentation
+    pub fn with_code_description(mut self, code_description: CodeDescription) -> Self {
+        self.codeDescription = Some(code_description);
+        self
+    }
+
+    pub fn add_related(&mut self, info: DiagnosticRelatedInformation) {
+        self.relatedInformation
+            .get_or_insert_with(Vec::new)
+            .push(info);
+    }
+}
+
+open_repr_enum! {
+    /// Ordered by the wire value, so `Error < Warning < Information < Hint` and
+    /// `severity <= DiagnosticSeverity::Warning` keeps errors and warnings;
+    /// an unknown severity sorts by its value too.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum DiagnosticSeverity {
+        /**
+         * Reports an error.
+         */
+        Error = 1,
+        /**
+         * Reports a warning.
+         */
+        Warning = 2,
+        /**
+         * Reports an information.
+         */
+        Information = 3,
+        /**
+         * Reports a hint.
+         */
+        Hint = 4,
+    }
+}
+
+impl PartialOrd for DiagnosticSeverity {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DiagnosticSeverity {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        u32::from(*self).cmp(&u32::from(*other))
+    }
 }
 
 /**
@@ -764,7 +2431,8 @@ pub enum DiagnosticSeverity {
  *
  * @since 3.15.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize_repr, Deserialize_repr, Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
 pub enum DiagnosticTag {
     /**
      * Unused or unnecessary code.
@@ -781,12 +2449,19 @@ pub enum DiagnosticTag {
     Deprecated = 2,
 }
 
+impl DiagnosticTag {
+    /// every tag, e.g. for [PublishDiagnosticsClientCapabilitiesTagSupport::valueSet]
+    pub fn all() -> &'static [DiagnosticTag] {
+        &[DiagnosticTag::Unnecessary, DiagnosticTag::Deprecated]
+    }
+}
+
 /**
  * Represents a related message and source code location for a diagnostic.
  * This should be used to point to code locations that cause or are related to
  * a diagnostics, e.g when duplicating a symbol in a scope.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct DiagnosticRelatedInformation {
     /**
      * The location of this related diagnostic information.
@@ -799,12 +2474,21 @@ pub struct DiagnosticRelatedInformation {
     pub message: String,
 }
 
+impl DiagnosticRelatedInformation {
+    pub fn new(location: Location, message: impl Into<String>) -> Self {
+        DiagnosticRelatedInformation {
+            location,
+            message: message.into(),
+        }
+    }
+}
+
 /**
  * Structure to capture a description for an error code.
  *
  * @since 3.16.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct CodeDescription {
     /**
      * An URI to open with more information about the diagnostic error.
@@ -812,7 +2496,13 @@ pub struct CodeDescription {
     pub href: URI,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl CodeDescription {
+    pub fn new(href: impl Into<URI>) -> Self {
+        CodeDescription { href: href.into() }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Command {
     /**
      * Title of the command, like `save`.
@@ -826,9 +2516,27 @@ pub struct Command {
      * Arguments that the command handler should be
      * invoked with.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub arguments: Option<Vec<LSPAny>>,
 }
 
+impl Command {
+    pub fn new(title: impl Into<String>, command: impl Into<String>) -> Self {
+        Command {
+            title: title.into(),
+            command: command.into(),
+            arguments: None,
+        }
+    }
+
+    /// appends `argument` to the arguments, converted through its json form
+    pub fn with_argument<T: Serialize>(mut self, argument: T) -> serde_json::Result<Self> {
+        let argument = serde_json::from_value(serde_json::to_value(argument)?)?;
+        self.arguments.get_or_insert_with(Vec::new).push(argument);
+        Ok(self)
+    }
+}
+
 /**
  * Describes the content type that a client supports in various
  * result literals like `Hover`, `ParameterInfo` or `CompletionItem`.
@@ -836,7 +2544,7 @@ pub struct Command {
  * Please note that `MarkupKinds` must not start with a `$`. This kinds
  * are reserved for internal usage.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MarkupKind {
     /**
      * Plain text is supported as a content format
@@ -877,7 +2585,7 @@ pub enum MarkupKind {
  * *Please Note* that clients might sanitize the return markdown. A client could
  * decide to remove HTML from the markdown to avoid script execution.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct MarkupContent {
     /**
      * The type of the Markup
@@ -905,6 +2613,7 @@ pub struct MarkdownClientCapabilities {
     /**
      * The version of the parser.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
 
     /**
@@ -913,21 +2622,25 @@ pub struct MarkdownClientCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub allowedTags: Option<Vec<String>>,
 }
 
 /**
  * Options to create a file.
  */
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct CreateFileOptions {
     /**
      * Overwrite existing file. Overwrite wins over `ignoreIfExists`
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub overwrite: Option<Boolean>,
 
     /**
      * Ignore if exists.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ignoreIfExists: Option<Boolean>,
 }
 
@@ -937,6 +2650,7 @@ pub enum FileKind {}
 /**
  * Create file operation
  */
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct CreateFile {
     /**
      * A create
@@ -952,6 +2666,7 @@ pub struct CreateFile {
     /**
      * Additional options
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<CreateFileOptions>,
 
     /**
@@ -959,27 +2674,43 @@ pub struct CreateFile {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub annotationId: Option<ChangeAnnotationIdentifier>,
 }
 
+impl CreateFile {
+    pub fn new(uri: impl Into<DocumentUri>) -> Self {
+        CreateFile {
+            kind: ResourceOperationKind::Create,
+            uri: uri.into(),
+            options: None,
+            annotationId: None,
+        }
+    }
+}
+
 /**
  * Rename file options
  */
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct RenameFileOptions {
     /**
      * Overwrite target if existing. Overwrite wins over `ignoreIfExists`
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub overwrite: Option<Boolean>,
 
     /**
      * Ignores if target exists.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ignoreIfExists: Option<Boolean>,
 }
 
 /**
  * Rename file operation
  */
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct RenameFile {
     /**
      * A rename
@@ -1000,6 +2731,7 @@ pub struct RenameFile {
     /**
      * Rename options.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<RenameFileOptions>,
 
     /**
@@ -1007,27 +2739,46 @@ pub struct RenameFile {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub annotationId: Option<ChangeAnnotationIdentifier>,
 }
 
+impl RenameFile {
+    pub fn new(old_uri: impl Into<DocumentUri>, new_uri: impl Into<DocumentUri>) -> Self {
+        RenameFile {
+            kind: ResourceOperationKind::Rename,
+            oldUri: old_uri.into(),
+            newUri: new_uri.into(),
+            options: None,
+            annotationId: None,
+        }
+    }
+}
+
 /**
  * Delete file options
  */
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DeleteFileOptions {
     /**
      * Delete the content recursively if a folder is denoted.
      */
+    /// unset means `false`: deleting a non-empty folder fails unless this is
+    /// `Some(true)`, see [DeleteFile::recursive]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub recursive: Option<Boolean>,
 
     /**
      * Ignore the operation if the file doesn't exist.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ignoreIfNotExists: Option<Boolean>,
 }
 
 /**
  * Delete file operation
  */
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DeleteFile {
     /**
      * A delete
@@ -1043,6 +2794,7 @@ pub struct DeleteFile {
     /**
      * Delete options.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<DeleteFileOptions>,
 
     /**
@@ -1050,22 +2802,83 @@ pub struct DeleteFile {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub annotationId: Option<ChangeAnnotationIdentifier>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl DeleteFile {
+    pub fn new(uri: impl Into<DocumentUri>) -> Self {
+        DeleteFile {
+            kind: ResourceOperationKind::Delete,
+            uri: uri.into(),
+            options: None,
+            annotationId: None,
+        }
+    }
+
+    /// deletes a folder along with its content
+    pub fn recursive(uri: impl Into<DocumentUri>) -> Self {
+        DeleteFile {
+            options: Some(DeleteFileOptions {
+                recursive: Some(true),
+                ignoreIfNotExists: None,
+            }),
+            ..DeleteFile::new(uri)
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum WorkspaceEditDocumentChanges {
     TextDocumentEdit(Vec<TextDocumentEdit>),
-    // (TextDocumentEdit | CreateFile | RenameFile | DeleteFile)[]
+    /// needs [WorkspaceEditClientCapabilities::resourceOperations]
+    DocumentChanges(Vec<DocumentChange>),
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// `TextDocumentEdit | CreateFile | RenameFile | DeleteFile`, told apart by
+/// the `kind` of the file operations, which a text document edit doesn't have
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum DocumentChange {
+    TextDocumentEdit(TextDocumentEdit),
+    CreateFile(CreateFile),
+    RenameFile(RenameFile),
+    DeleteFile(DeleteFile),
+}
+
+impl<'de> Deserialize<'de> for DocumentChange {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let kind = match value.get("kind") {
+            None => None,
+            Some(kind) => Some(ResourceOperationKind::deserialize(kind).map_err(D::Error::custom)?),
+        };
+        match kind {
+            None => TextDocumentEdit::deserialize(value).map(DocumentChange::TextDocumentEdit),
+            Some(ResourceOperationKind::Create) => {
+                CreateFile::deserialize(value).map(DocumentChange::CreateFile)
+            }
+            Some(ResourceOperationKind::Rename) => {
+                RenameFile::deserialize(value).map(DocumentChange::RenameFile)
+            }
+            Some(ResourceOperationKind::Delete) => {
+                DeleteFile::deserialize(value).map(DocumentChange::DeleteFile)
+            }
+        }
+        .map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
 pub struct WorkspaceEdit {
     /**
      * Holds changes to existing resources.
      */
     // changes?: { [uri: DocumentUri]: TextEdit[]; },
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub changes: Option<BTreeMap<DocumentUri, Vec<TextEdit>>>,
 
     /**
@@ -1087,6 +2900,7 @@ pub struct WorkspaceEdit {
     //     TextDocumentEdit[] |
     //     (TextDocumentEdit | CreateFile | RenameFile | DeleteFile)[]
     // ),
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentChanges: Option<WorkspaceEditDocumentChanges>,
 
     /**
@@ -1102,9 +2916,57 @@ pub struct WorkspaceEdit {
     // changeAnnotations?: {
     //     [id: String /* ChangeAnnotationIdentifier */]: ChangeAnnotation,
     // },
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub changeAnnotations: Option<BTreeMap<ChangeAnnotationIdentifier, ChangeAnnotation>>,
 }
 
+impl WorkspaceEdit {
+    /// stores `annotation` under a fresh id, to be referenced from
+    /// [AnnotatedTextEdit::annotationId] or a file operation
+    pub fn add_annotation(&mut self, annotation: ChangeAnnotation) -> ChangeAnnotationIdentifier {
+        let annotations = self.changeAnnotations.get_or_insert_with(BTreeMap::new);
+        let id = (annotations.len()..)
+            .map(|index| format!("annotation-{index}"))
+            .find(|id| !annotations.contains_key(id))
+            .expect("an unused annotation id");
+        annotations.insert(id.clone(), annotation);
+        id
+    }
+
+    /// whether the edit changes nothing, in which case a server would rather
+    /// answer `null`
+    pub fn is_empty(&self) -> bool {
+        self.edit_count() == 0
+            && self
+                .changeAnnotations
+                .as_ref()
+                .is_none_or(|annotations| annotations.is_empty())
+    }
+
+    /// the number of text edits across `changes` and `documentChanges`, with
+    /// each file operation counting as one
+    pub fn edit_count(&self) -> usize {
+        let changes = self
+            .changes
+            .as_ref()
+            .map_or(0, |changes| changes.values().map(Vec::len).sum());
+        let document_changes = match &self.documentChanges {
+            None => 0,
+            Some(WorkspaceEditDocumentChanges::TextDocumentEdit(edits)) => {
+                edits.iter().map(|edit| edit.edits.len()).sum()
+            }
+            Some(WorkspaceEditDocumentChanges::DocumentChanges(changes)) => changes
+                .iter()
+                .map(|change| match change {
+                    DocumentChange::TextDocumentEdit(edit) => edit.edits.len(),
+                    _ => 1,
+                })
+                .sum(),
+        };
+        changes + document_changes
+    }
+}
+
 /// extends from [WorkspaceEditClientCapabilities::changeAnnotationSupport]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct WorkspaceEditClientCapabilitiesChangeAnnotationSupport {
@@ -1113,6 +2975,7 @@ pub struct WorkspaceEditClientCapabilitiesChangeAnnotationSupport {
      * for instance all edits labelled with "Changes in Strings" would
      * be a tree node.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub groupsOnLabel: Option<Boolean>,
 }
 
@@ -1121,6 +2984,7 @@ pub struct WorkspaceEditClientCapabilities {
     /**
      * The client supports versioned document changes in `WorkspaceEdit`s
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentChanges: Option<Boolean>,
 
     /**
@@ -1129,6 +2993,7 @@ pub struct WorkspaceEditClientCapabilities {
      *
      * @since 3.13.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resourceOperations: Option<Vec<ResourceOperationKind>>,
 
     /**
@@ -1137,6 +3002,7 @@ pub struct WorkspaceEditClientCapabilities {
      *
      * @since 3.13.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub failureHandling: Option<FailureHandlingKind>,
 
     /**
@@ -1147,6 +3013,7 @@ pub struct WorkspaceEditClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub normalizesLineEndings: Option<Boolean>,
 
     /**
@@ -1155,13 +3022,14 @@ pub struct WorkspaceEditClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub changeAnnotationSupport: Option<WorkspaceEditClientCapabilitiesChangeAnnotationSupport>,
 }
 
 /**
  * The kind of resource operations supported by the client.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ResourceOperationKind {
     /**
      * Supports creating new files and folders.
@@ -1222,10 +3090,53 @@ pub enum WorkDoneProgress {
     End,
 }
 
+/// the `kind` tag of a work done progress payload, a zero sized stand-in
+/// for one [WorkDoneProgress] variant so that e.g. a [WorkDoneProgressBegin]
+/// always serializes as `"kind":"begin"` and only deserializes from it
+macro_rules! work_done_progress_kind {
+    ($($name:ident => $kind:ident = $text:literal),* $(,)?) => {
+        $(
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+            pub struct $name;
+
+            impl Serialize for $name {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    WorkDoneProgress::$kind.serialize(serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $name {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    match WorkDoneProgress::deserialize(deserializer)? {
+                        WorkDoneProgress::$kind => Ok($name),
+                        _ => Err(serde::de::Error::custom(concat!(
+                            "expected work done progress kind `",
+                            $text,
+                            "`"
+                        ))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+work_done_progress_kind! {
+    WorkDoneProgressBeginKind => Begin = "begin",
+    WorkDoneProgressReportKind => Report = "report",
+    WorkDoneProgressEndKind => End = "end",
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct WorkDoneProgressBegin {
     /// kind: 'begin',
-    pub kind: WorkDoneProgress,
+    pub kind: WorkDoneProgressBeginKind,
 
     /**
      * Mandatory title of the progress operation. Used to briefly inform about
@@ -1240,6 +3151,7 @@ pub struct WorkDoneProgressBegin {
      * long running operation. Clients that don't support cancellation are
      * allowed to ignore the setting.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cancellable: Option<Boolean>,
 
     /**
@@ -1249,6 +3161,7 @@ pub struct WorkDoneProgressBegin {
      * Examples: "3/25 files", "project/src/module2", "node_modules/some_dep".
      * If unset, the previous progress message (if any) is still valid.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
 
     /**
@@ -1259,13 +3172,14 @@ pub struct WorkDoneProgressBegin {
      * The value should be steadily rising. Clients are free to ignore values
      * that are not following this rule. The value range is [0, 100].
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub percentage: Option<UInteger>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct WorkDoneProgressReport {
     /// kind: 'report',
-    pub kind: WorkDoneProgress,
+    pub kind: WorkDoneProgressReportKind,
 
     /**
      * Controls enablement state of a cancel button. This property is only valid
@@ -1274,6 +3188,7 @@ pub struct WorkDoneProgressReport {
      * Clients that don't support cancellation or don't support control the
      * button's enablement state are allowed to ignore the setting.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cancellable: Option<Boolean>,
 
     /**
@@ -1283,6 +3198,7 @@ pub struct WorkDoneProgressReport {
      * Examples: "3/25 files", "project/src/module2", "node_modules/some_dep".
      * If unset, the previous progress message (if any) is still valid.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
 
     /**
@@ -1293,44 +3209,191 @@ pub struct WorkDoneProgressReport {
      * The value should be steadily rising. Clients are free to ignore values
      * that are not following this rule. The value range is [0, 100].
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub percentage: Option<UInteger>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct WorkDoneProgressEnd {
     /// kind: 'end',
-    pub kind: WorkDoneProgress,
+    pub kind: WorkDoneProgressEndKind,
 
     /**
      * Optional, a final message indicating to for example indicate the outcome
      * of the operation.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
 }
 
+/// the payloads of a work done progress notification, see
+/// [ProgressParams::work_done]
+pub trait WorkDoneProgressValue {}
+
+impl WorkDoneProgressValue for WorkDoneProgressBegin {}
+impl WorkDoneProgressValue for WorkDoneProgressReport {}
+impl WorkDoneProgressValue for WorkDoneProgressEnd {}
+
+impl WorkDoneProgressBegin {
+    pub fn new(title: impl Into<String>) -> Self {
+        WorkDoneProgressBegin {
+            kind: WorkDoneProgressBeginKind,
+            title: title.into(),
+            cancellable: None,
+            message: None,
+            percentage: None,
+        }
+    }
+
+    /// the initial percentage, clamped to 100
+    pub fn with_percentage(mut self, percentage: UInteger) -> Self {
+        self.percentage = Some(percentage.min(100));
+        self
+    }
+}
+
+impl WorkDoneProgressReport {
+    pub fn new() -> Self {
+        WorkDoneProgressReport {
+            kind: WorkDoneProgressReportKind,
+            cancellable: None,
+            message: None,
+            percentage: None,
+        }
+    }
+
+    /// the current percentage, clamped to 100
+    pub fn with_percentage(mut self, percentage: UInteger) -> Self {
+        self.percentage = Some(percentage.min(100));
+        self
+    }
+}
+
+impl Default for WorkDoneProgressReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkDoneProgressEnd {
+    pub fn new(message: Option<String>) -> Self {
+        WorkDoneProgressEnd {
+            kind: WorkDoneProgressEndKind,
+            message,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct WorkDoneProgressParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct WorkDoneProgressOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
+/// the `workDoneProgress` flag that every provider options struct repeats
+/// from [WorkDoneProgressOptions], to read or set it on any of them
+pub trait WithWorkDoneProgress {
+    fn work_done_progress(&self) -> Option<Boolean>;
+
+    fn set_work_done_progress(&mut self, work_done_progress: Option<Boolean>);
+}
+
+macro_rules! impl_with_work_done_progress {
+    ($($name:ident),* $(,)?) => {
+        $(impl WithWorkDoneProgress for $name {
+            fn work_done_progress(&self) -> Option<Boolean> {
+                self.workDoneProgress
+            }
+
+            fn set_work_done_progress(&mut self, work_done_progress: Option<Boolean>) {
+                self.workDoneProgress = work_done_progress;
+            }
+        })*
+    };
+}
+
+impl_with_work_done_progress!(
+    WorkDoneProgressOptions,
+    DeclarationOptions,
+    DeclarationRegistrationOptions,
+    DefinitionOptions,
+    DefinitionRegistrationOptions,
+    TypeDefinitionOptions,
+    TypeDefinitionRegistrationOptions,
+    ImplementationOptions,
+    ImplementationRegistrationOptions,
+    ReferenceOptions,
+    ReferenceRegistrationOptions,
+    CallHierarchyOptions,
+    CallHierarchyRegistrationOptions,
+    TypeHierarchyOptions,
+    TypeHierarchyRegistrationOptions,
+    DocumentHighlightOptions,
+    DocumentHighlightRegistrationOptions,
+    DocumentLinkOptions,
+    DocumentLinkRegistrationOptions,
+    HoverOptions,
+    HoverRegistrationOptions,
+    CodeLensOptions,
+    CodeLensRegistrationOptions,
+    FoldingRangeOptions,
+    FoldingRangeRegistrationOptions,
+    SelectionRangeOptions,
+    SelectionRangeRegistrationOptions,
+    DocumentSymbolOptions,
+    DocumentSymbolRegistrationOptions,
+    SemanticTokensOptions,
+    SemanticTokensRegistrationOptions,
+    InlayHintOptions,
+    InlayHintRegistrationOptions,
+    InlineValueOptions,
+    InlineValueRegistrationOptions,
+    MonikerOptions,
+    MonikerRegistrationOptions,
+    CompletionOptions,
+    CompletionRegistrationOptions,
+    DiagnosticOptions,
+    DiagnosticRegistrationOptions,
+    SignatureHelpOptions,
+    SignatureHelpRegistrationOptions,
+    CodeActionOptions,
+    CodeActionRegistrationOptions,
+    DocumentColorOptions,
+    DocumentColorRegistrationOptions,
+    DocumentFormattingOptions,
+    DocumentFormattingRegistrationOptions,
+    DocumentRangeFormattingOptions,
+    DocumentRangeFormattingRegistrationOptions,
+    RenameOptions,
+    RenameRegistrationOptions,
+    LinkedEditingRangeOptions,
+    LinkedEditingRangeRegistrationOptions,
+    WorkspaceSymbolOptions,
+    WorkspaceSymbolRegistrationOptions,
+    ExecuteCommandOptions,
+    ExecuteCommandRegistrationOptions,
+);
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PartialResultParams {
     /**
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TraceValue {
     #[serde(rename = "off")]
     Off,
@@ -1351,6 +3414,7 @@ pub struct InitializeParamsClientInfo {
     /**
      * The client's version as defined by the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
 }
 
@@ -1360,6 +3424,7 @@ pub struct InitializeParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /**
@@ -1368,13 +3433,14 @@ pub struct InitializeParams {
      * process is not alive then the server should exit (see exit notification)
      * its process.
      */
-    pub processId: Option<Integer>,
+    pub processId: Nullable<Integer>,
 
     /**
      * Information about the client
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub clientInfo: Option<InitializeParamsClientInfo>,
 
     /**
@@ -1387,6 +3453,7 @@ pub struct InitializeParams {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub locale: Option<String>,
 
     /**
@@ -1395,6 +3462,7 @@ pub struct InitializeParams {
      *
      * @deprecated in favour of `rootUri`.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rootPath: Option<String>,
 
     /**
@@ -1404,11 +3472,12 @@ pub struct InitializeParams {
      *
      * @deprecated in favour of `workspaceFolders`
      */
-    pub rootUri: Option<DocumentUri>,
+    pub rootUri: Nullable<DocumentUri>,
 
     /**
      * User provided initialization options.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub initializationOptions: Option<LSPAny>,
 
     /**
@@ -1419,6 +3488,7 @@ pub struct InitializeParams {
     /**
      * The initial trace setting. If omitted trace is disabled ('off').
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub trace: Option<TraceValue>,
 
     /**
@@ -1429,29 +3499,132 @@ pub struct InitializeParams {
      *
      * @since 3.6.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workspaceFolders: Option<Vec<WorkspaceFolder>>,
 }
 
+impl InitializeParams {
+    /// params with only the client capabilities set, everything else left
+    /// out or `null`
+    ///
+    /// ```
+    /// use rust_lsp_types::{ClientCapabilities, InitializeParams};
+    ///
+    /// let params = InitializeParams::new(ClientCapabilities::default())
+    ///     .with_process_id(42)
+    ///     .with_client_info("my-editor", Some("1.0".into()))
+    ///     .with_root_uri("file:///project");
+    /// let json = serde_json::to_value(&params).unwrap();
+    /// assert_eq!(json["processId"], 42);
+    /// assert_eq!(json["clientInfo"]["name"], "my-editor");
+    /// assert_eq!(json["rootUri"], "file:///project");
+    /// ```
+    pub fn new(capabilities: ClientCapabilities) -> Self {
+        InitializeParams {
+            workDoneToken: None,
+            processId: Nullable::Null,
+            clientInfo: None,
+            locale: None,
+            rootPath: None,
+            rootUri: Nullable::Null,
+            initializationOptions: None,
+            capabilities,
+            trace: None,
+            workspaceFolders: None,
+        }
+    }
+
+    pub fn with_process_id(mut self, process_id: Integer) -> Self {
+        self.processId = Nullable::Value(process_id);
+        self
+    }
+
+    pub fn with_client_info(mut self, name: impl Into<String>, version: Option<String>) -> Self {
+        self.clientInfo = Some(InitializeParamsClientInfo {
+            name: name.into(),
+            version,
+        });
+        self
+    }
+
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    pub fn with_root_uri(mut self, root_uri: impl Into<DocumentUri>) -> Self {
+        self.rootUri = Nullable::Value(root_uri.into());
+        self
+    }
+
+    pub fn with_initialization_options(mut self, options: LSPAny) -> Self {
+        self.initializationOptions = Some(options);
+        self
+    }
+
+    pub fn with_trace(mut self, trace: TraceValue) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
+    pub fn with_workspace_folders(mut self, folders: Vec<WorkspaceFolder>) -> Self {
+        self.workspaceFolders = Some(folders);
+        self
+    }
+
+    /// the workspace folders the client opened, taken from
+    /// `workspaceFolders` if present, otherwise from the deprecated `rootUri`
+    /// and then `rootPath`; each folder from a root is named after the last
+    /// segment of its path. A present but empty `workspaceFolders` means no
+    /// folder is open and gives an empty list even if a root is set.
+    pub fn workspace_folders_normalized(&self) -> Vec<WorkspaceFolder> {
+        if let Some(folders) = &self.workspaceFolders {
+            return folders.clone();
+        }
+        let uri = match (&self.rootUri, &self.rootPath) {
+            (Nullable::Value(uri), _) => uri.to_string(),
+            (Nullable::Null, Some(path)) => match document_uri_from_file_path(Path::new(path)) {
+                Ok(uri) => uri.into(),
+                Err(_) => return Vec::new(),
+            },
+            (Nullable::Null, None) => return Vec::new(),
+        };
+        let (_, path) = split_uri(&uri);
+        let name = path
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())
+            .and_then(decode_uri_component)
+            .unwrap_or_else(|| uri.clone());
+        vec![WorkspaceFolder { uri, name }]
+    }
+}
+
 /**
  * Text document specific client capabilities.
  */
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TextDocumentClientCapabilities {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub synchronization: Option<TextDocumentSyncClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/completion` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub completion: Option<CompletionClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/hover` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub hover: Option<HoverClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/signatureHelp` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub signatureHelp: Option<SignatureHelpClientCapabilities>,
 
     /**
@@ -1459,11 +3632,13 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.14.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub declaration: Option<DeclarationClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/definition` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub definition: Option<DefinitionClientCapabilities>,
 
     /**
@@ -1471,6 +3646,7 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.6.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub typeDefinition: Option<TypeDefinitionClientCapabilities>,
 
     /**
@@ -1478,36 +3654,43 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.6.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub implementation: Option<ImplementationClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/references` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub references: Option<ReferenceClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/documentHighlight` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentHighlight: Option<DocumentHighlightClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/documentSymbol` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentSymbol: Option<DocumentSymbolClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/codeAction` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub codeAction: Option<CodeActionClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/codeLens` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub codeLens: Option<CodeLensClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/documentLink` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentLink: Option<DocumentLinkClientCapabilities>,
 
     /**
@@ -1516,32 +3699,38 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.6.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub colorProvider: Option<DocumentColorClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/formatting` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub formatting: Option<DocumentFormattingClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/rangeFormatting` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rangeFormatting: Option<DocumentRangeFormattingClientCapabilities>,
 
     /** request.
      * Capabilities specific to the `textDocument/onTypeFormatting` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub onTypeFormatting: Option<DocumentOnTypeFormattingClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/rename` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rename: Option<RenameClientCapabilities>,
 
     /**
      * Capabilities specific to the `textDocument/publishDiagnostics`
      * notification.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub publishDiagnostics: Option<PublishDiagnosticsClientCapabilities>,
 
     /**
@@ -1549,6 +3738,7 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.10.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub foldingRange: Option<FoldingRangeClientCapabilities>,
 
     /**
@@ -1556,6 +3746,7 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub selectionRange: Option<SelectionRangeClientCapabilities>,
 
     /**
@@ -1563,6 +3754,7 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub linkedEditingRange: Option<LinkedEditingRangeClientCapabilities>,
 
     /**
@@ -1570,6 +3762,7 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub callHierarchy: Option<CallHierarchyClientCapabilities>,
 
     /**
@@ -1577,6 +3770,7 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub semanticTokens: Option<SemanticTokensClientCapabilities>,
 
     /**
@@ -1584,6 +3778,7 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub moniker: Option<MonikerClientCapabilities>,
 
     /**
@@ -1591,6 +3786,7 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub typeHierarchy: Option<TypeHierarchyClientCapabilities>,
 
     /**
@@ -1598,6 +3794,7 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub inlineValue: Option<InlineValueClientCapabilities>,
 
     /**
@@ -1605,6 +3802,7 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub inlayHint: Option<InlayHintClientCapabilities>,
 
     /**
@@ -1612,6 +3810,7 @@ pub struct TextDocumentClientCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub diagnostic: Option<DiagnosticClientCapabilities>,
 }
 
@@ -1637,36 +3836,43 @@ pub struct ClientCapabilitiesWorkspaceFileOperations {
      * Whether the client supports dynamic registration for file
      * requests/notifications.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
      * The client has support for sending didCreateFiles notifications.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub didCreate: Option<Boolean>,
 
     /**
      * The client has support for sending willCreateFiles requests.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub willCreate: Option<Boolean>,
 
     /**
      * The client has support for sending didRenameFiles notifications.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub didRename: Option<Boolean>,
 
     /**
      * The client has support for sending willRenameFiles requests.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub willRename: Option<Boolean>,
 
     /**
      * The client has support for sending didDeleteFiles notifications.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub didDelete: Option<Boolean>,
 
     /**
      * The client has support for sending willDeleteFiles requests.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub willDelete: Option<Boolean>,
 }
 
@@ -1678,33 +3884,39 @@ pub struct ClientCapabilitiesWorkspace {
      * to the workspace by supporting the request
      * 'workspace/applyEdit'
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub applyEdit: Option<Boolean>,
 
     /**
      * Capabilities specific to `WorkspaceEdit`s
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workspaceEdit: Option<WorkspaceEditClientCapabilities>,
 
     /**
      * Capabilities specific to the `workspace/didChangeConfiguration`
      * notification.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub didChangeConfiguration: Option<DidChangeConfigurationClientCapabilities>,
 
     /**
      * Capabilities specific to the `workspace/didChangeWatchedFiles`
      * notification.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub didChangeWatchedFiles: Option<DidChangeWatchedFilesClientCapabilities>,
 
     /**
      * Capabilities specific to the `workspace/symbol` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub symbol: Option<WorkspaceSymbolClientCapabilities>,
 
     /**
      * Capabilities specific to the `workspace/executeCommand` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub executeCommand: Option<ExecuteCommandClientCapabilities>,
 
     /**
@@ -1712,6 +3924,7 @@ pub struct ClientCapabilitiesWorkspace {
      *
      * @since 3.6.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workspaceFolders: Option<Boolean>,
 
     /**
@@ -1719,6 +3932,7 @@ pub struct ClientCapabilitiesWorkspace {
      *
      * @since 3.6.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub configuration: Option<Boolean>,
 
     /**
@@ -1727,6 +3941,7 @@ pub struct ClientCapabilitiesWorkspace {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub semanticTokens: Option<SemanticTokensWorkspaceClientCapabilities>,
 
     /**
@@ -1735,6 +3950,7 @@ pub struct ClientCapabilitiesWorkspace {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub codeLens: Option<CodeLensWorkspaceClientCapabilities>,
 
     /**
@@ -1742,6 +3958,7 @@ pub struct ClientCapabilitiesWorkspace {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub fileOperations: Option<ClientCapabilitiesWorkspaceFileOperations>,
 
     /**
@@ -1749,6 +3966,7 @@ pub struct ClientCapabilitiesWorkspace {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub inlineValue: Option<InlineValueWorkspaceClientCapabilities>,
 
     /**
@@ -1756,6 +3974,7 @@ pub struct ClientCapabilitiesWorkspace {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub inlayHint: Option<InlayHintWorkspaceClientCapabilities>,
 
     /**
@@ -1763,6 +3982,7 @@ pub struct ClientCapabilitiesWorkspace {
      *
      * @since 3.17.0.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub diagnostics: Option<DiagnosticWorkspaceClientCapabilities>,
 }
 
@@ -1780,6 +4000,7 @@ pub struct ClientCapabilitiesWindow {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
@@ -1787,6 +4008,7 @@ pub struct ClientCapabilitiesWindow {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub showMessage: Option<ShowMessageRequestClientCapabilities>,
 
     /**
@@ -1794,6 +4016,7 @@ pub struct ClientCapabilitiesWindow {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub showDocument: Option<ShowDocumentClientCapabilities>,
 }
 
@@ -1813,6 +4036,16 @@ pub struct StaleRequestSupport {
     pub retryOnContentModified: Vec<String>,
 }
 
+impl StaleRequestSupport {
+    /// whether the client retries `method` after a response failing with
+    /// [ErrorCodes::ContentModified], see [ResponseError::is_content_modified]
+    pub fn should_retry(&self, method: &str) -> bool {
+        self.retryOnContentModified
+            .iter()
+            .any(|retried| retried == method)
+    }
+}
+
 /// extends from [ClientCapabilities::general]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ClientCapabilitiesGeneral {
@@ -1824,6 +4057,7 @@ pub struct ClientCapabilitiesGeneral {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub staleRequestSupport: Option<StaleRequestSupport>,
 
     /**
@@ -1831,6 +4065,7 @@ pub struct ClientCapabilitiesGeneral {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub regularExpressions: Option<RegularExpressionsClientCapabilities>,
 
     /**
@@ -1838,6 +4073,7 @@ pub struct ClientCapabilitiesGeneral {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub markdown: Option<MarkdownClientCapabilities>,
 
     /**
@@ -1860,19 +4096,22 @@ pub struct ClientCapabilitiesGeneral {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub positionEncodings: Option<Vec<PositionEncodingKind>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct ClientCapabilities {
     /**
      * Workspace specific client capabilities.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workspace: Option<ClientCapabilitiesWorkspace>,
 
     /**
      * Text document specific client capabilities.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub textDocument: Option<TextDocumentClientCapabilities>,
 
     /**
@@ -1880,11 +4119,13 @@ pub struct ClientCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub notebookDocument: Option<NotebookDocumentClientCapabilities>,
 
     /**
      * Window specific client capabilities.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub window: Option<ClientCapabilitiesWindow>,
 
     /**
@@ -1892,14 +4133,644 @@ pub struct ClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub general: Option<ClientCapabilitiesGeneral>,
 
     /**
      * Experimental client capabilities.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub experimental: Option<LSPAny>,
 }
 
+impl ClientCapabilities {
+    /// `textDocument.documentSymbol.hierarchicalDocumentSymbolSupport`
+    pub fn supports_hierarchical_document_symbols(&self) -> bool {
+        self.textDocument
+            .as_ref()
+            .and_then(|text_document| text_document.documentSymbol.as_ref())
+            .and_then(|document_symbol| document_symbol.hierarchicalDocumentSymbolSupport)
+            .unwrap_or(false)
+    }
+
+    /// `textDocument.completion.completionItem.snippetSupport`
+    pub fn supports_snippet_completion(&self) -> bool {
+        self.textDocument
+            .as_ref()
+            .and_then(|text_document| text_document.completion.as_ref())
+            .and_then(|completion| completion.completionItem.as_ref())
+            .and_then(|completion_item| completion_item.snippetSupport)
+            .unwrap_or(false)
+    }
+
+    /// `window.workDoneProgress`
+    pub fn supports_work_done_progress(&self) -> bool {
+        self.window
+            .as_ref()
+            .and_then(|window| window.workDoneProgress)
+            .unwrap_or(false)
+    }
+
+    /// `workspace.workspaceFolders`
+    pub fn supports_workspace_folders(&self) -> bool {
+        self.workspace
+            .as_ref()
+            .and_then(|workspace| workspace.workspaceFolders)
+            .unwrap_or(false)
+    }
+
+    /// `workspace.configuration`
+    pub fn supports_configuration(&self) -> bool {
+        self.workspace
+            .as_ref()
+            .and_then(|workspace| workspace.configuration)
+            .unwrap_or(false)
+    }
+
+    /// capabilities of a client that supports every feature of the protocol
+    /// with all of its options, for testing a server as a mock client
+    pub fn full() -> Self {
+        let strings = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+        let markup = || Some(vec![MarkupKind::Markdown, MarkupKind::PlainText]);
+        let symbol_kinds = || SymbolKindStruct {
+            valueSet: Some(SymbolKind::all().to_vec()),
+        };
+        let workspace = ClientCapabilitiesWorkspace {
+            applyEdit: Some(true),
+            workspaceEdit: Some(WorkspaceEditClientCapabilities {
+                documentChanges: Some(true),
+                resourceOperations: Some(vec![
+                    ResourceOperationKind::Create,
+                    ResourceOperationKind::Rename,
+                    ResourceOperationKind::Delete,
+                ]),
+                failureHandling: Some(FailureHandlingKind::Transactional),
+                normalizesLineEndings: Some(true),
+                changeAnnotationSupport: Some(
+                    WorkspaceEditClientCapabilitiesChangeAnnotationSupport {
+                        groupsOnLabel: Some(true),
+                    },
+                ),
+            }),
+            didChangeConfiguration: Some(DidChangeConfigurationClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            didChangeWatchedFiles: Some(DidChangeWatchedFilesClientCapabilities {
+                dynamicRegistration: Some(true),
+                relativePatternSupport: Some(true),
+            }),
+            symbol: Some(WorkspaceSymbolClientCapabilities {
+                dynamicRegistration: Some(true),
+                symbolKind: Some(WorkspaceSymbolClientCapabilitiesSymbolKind {
+                    valueSet: Some(SymbolKind::all().to_vec()),
+                }),
+                tagSupport: Some(WorkspaceSymbolClientCapabilitiesTagSupport {
+                    valueSet: vec![SymbolTag::Deprecated],
+                }),
+                resolveSupport: Some(WorkspaceSymbolClientCapabilitiesResolveSupport {
+                    properties: strings(&["location.range"]),
+                }),
+            }),
+            executeCommand: Some(ExecuteCommandClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            workspaceFolders: Some(true),
+            configuration: Some(true),
+            semanticTokens: Some(SemanticTokensWorkspaceClientCapabilities {
+                refreshSupport: Some(true),
+            }),
+            codeLens: Some(CodeLensWorkspaceClientCapabilities {
+                refreshSupport: Some(true),
+            }),
+            fileOperations: Some(ClientCapabilitiesWorkspaceFileOperations {
+                dynamicRegistration: Some(true),
+                didCreate: Some(true),
+                willCreate: Some(true),
+                didRename: Some(true),
+                willRename: Some(true),
+                didDelete: Some(true),
+                willDelete: Some(true),
+            }),
+            inlineValue: Some(InlineValueWorkspaceClientCapabilities {
+                refreshSupport: Some(true),
+            }),
+            inlayHint: Some(InlayHintWorkspaceClientCapabilities {
+                refreshSupport: Some(true),
+            }),
+            diagnostics: Some(DiagnosticWorkspaceClientCapabilities {
+                refreshSupport: Some(true),
+            }),
+        };
+
+        let text_document = TextDocumentClientCapabilities {
+            synchronization: Some(TextDocumentSyncClientCapabilities {
+                dynamicRegistration: Some(true),
+                willSave: Some(true),
+                willSaveWaitUntil: Some(true),
+                didSave: Some(true),
+            }),
+            completion: Some(CompletionClientCapabilities {
+                dynamicRegistration: Some(true),
+                completionItem: Some(CompletionClientCapabilitiesCompletionItem {
+                    snippetSupport: Some(true),
+                    commitCharactersSupport: Some(true),
+                    documentationFormat: markup(),
+                    deprecatedSupport: Some(true),
+                    preselectSupport: Some(true),
+                    tagSupport: Some(CompletionItemTagValueSet {
+                        valueSet: vec![CompletionItemTag::Deprecated],
+                    }),
+                    insertReplaceSupport: Some(true),
+                    resolveSupport: Some(ResolveSupportProperties {
+                        properties: strings(&["documentation", "detail", "additionalTextEdits"]),
+                    }),
+                    insertTextModeSupport: Some(InsertTextModeValueSet {
+                        valueSet: vec![InsertTextMode::asIs, InsertTextMode::adjustIndentation],
+                    }),
+                    labelDetailsSupport: Some(true),
+                }),
+                completionItemKind: Some(CompletionItemKindValueSet {
+                    valueSet: Some(CompletionItemKind::all().to_vec()),
+                }),
+                contextSupport: Some(true),
+                insertTextMode: Some(InsertTextMode::adjustIndentation),
+                completionList: Some(CompletionClientCapabilitiesCompletionListItemDefaults {
+                    itemDefaults: Some(strings(&[
+                        "commitCharacters",
+                        "editRange",
+                        "insertTextFormat",
+                        "insertTextMode",
+                        "data",
+                    ])),
+                }),
+            }),
+            hover: Some(HoverClientCapabilities {
+                dynamicRegistration: Some(true),
+                contentFormat: markup(),
+            }),
+            signatureHelp: Some(SignatureHelpClientCapabilities {
+                dynamicRegistration: Some(true),
+                signatureInformation: Some(SignatureHelpClientCapabilitiesSignatureInformation {
+                    documentationFormat: markup(),
+                    parameterInformation: Some(
+                        SignatureHelpClientCapabilitiesSignatureInformationParameterInformation {
+                            labelOffsetSupport: Some(true),
+                        },
+                    ),
+                    activeParameterSupport: Some(true),
+                }),
+                contextSupport: Some(true),
+            }),
+            declaration: Some(DeclarationClientCapabilities {
+                dynamicRegistration: Some(true),
+                linkSupport: Some(true),
+            }),
+            definition: Some(DefinitionClientCapabilities {
+                dynamicRegistration: Some(true),
+                linkSupport: Some(true),
+            }),
+            typeDefinition: Some(TypeDefinitionClientCapabilities {
+                dynamicRegistration: Some(true),
+                linkSupport: Some(true),
+            }),
+            implementation: Some(ImplementationClientCapabilities {
+                dynamicRegistration: Some(true),
+                linkSupport: Some(true),
+            }),
+            references: Some(ReferenceClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            documentHighlight: Some(DocumentHighlightClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            documentSymbol: Some(DocumentSymbolClientCapabilities {
+                dynamicRegistration: Some(true),
+                symbolKind: Some(symbol_kinds()),
+                hierarchicalDocumentSymbolSupport: Some(true),
+                tagSupport: Some(TagSupportStruct {
+                    valueSet: vec![SymbolTag::Deprecated],
+                }),
+                labelSupport: Some(true),
+            }),
+            codeAction: Some(CodeActionClientCapabilities {
+                dynamicRegistration: Some(true),
+                codeActionLiteralSupport: Some(
+                    CodeActionClientCapabilitiesCodeActionLiteralSupport {
+                        codeActionKind: CodeActionClientCapabilitiesCodeActionKind {
+                            valueSet: CodeActionKind::all().to_vec(),
+                        },
+                    },
+                ),
+                isPreferredSupport: Some(true),
+                disabledSupport: Some(true),
+                dataSupport: Some(true),
+                resolveSupport: Some(CodeActionClientCapabilitiesResolveSupport {
+                    properties: strings(&["edit"]),
+                }),
+                honorsChangeAnnotations: Some(true),
+            }),
+            codeLens: Some(CodeLensClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            documentLink: Some(DocumentLinkClientCapabilities {
+                dynamicRegistration: Some(true),
+                tooltipSupport: Some(true),
+            }),
+            colorProvider: Some(DocumentColorClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            formatting: Some(DocumentFormattingClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            rangeFormatting: Some(DocumentRangeFormattingClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            onTypeFormatting: Some(DocumentOnTypeFormattingClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            rename: Some(RenameClientCapabilities {
+                dynamicRegistration: Some(true),
+                prepareSupport: Some(true),
+                prepareSupportDefaultBehavior: Some(PrepareSupportDefaultBehavior::Identifier),
+                honorsChangeAnnotations: Some(true),
+            }),
+            publishDiagnostics: Some(PublishDiagnosticsClientCapabilities {
+                relatedInformation: Some(true),
+                tagSupport: Some(PublishDiagnosticsClientCapabilitiesTagSupport {
+                    valueSet: DiagnosticTag::all().to_vec(),
+                }),
+                versionSupport: Some(true),
+                codeDescriptionSupport: Some(true),
+                dataSupport: Some(true),
+            }),
+            foldingRange: Some(FoldingRangeClientCapabilities {
+                dynamicRegistration: Some(true),
+                rangeLimit: None,
+                lineFoldingOnly: Some(false),
+                foldingRangeKind: Some(FoldingRangeKindStruct {
+                    valueSet: Some(vec![
+                        FoldingRangeKind::Comment,
+                        FoldingRangeKind::Imports,
+                        FoldingRangeKind::Region,
+                    ]),
+                }),
+                foldingRange: Some(FoldingRangeStruct {
+                    collapsedText: Some(true),
+                }),
+            }),
+            selectionRange: Some(SelectionRangeClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            linkedEditingRange: Some(LinkedEditingRangeClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            callHierarchy: Some(CallHierarchyClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            semanticTokens: Some(SemanticTokensClientCapabilities {
+                dynamicRegistration: Some(true),
+                requests: SemanticTokensClientCapabilitiesRequests {
+                    range: Some(true),
+                    full: SemanticTokensClientCapabilitiesRequestsFull::OptionDelta {
+                        delta: Some(true),
+                    },
+                },
+                tokenTypes: strings(&[
+                    "namespace",
+                    "type",
+                    "class",
+                    "enum",
+                    "interface",
+                    "struct",
+                    "typeParameter",
+                    "parameter",
+                    "variable",
+                    "property",
+                    "enumMember",
+                    "event",
+                    "function",
+                    "method",
+                    "macro",
+                    "keyword",
+                    "modifier",
+                    "comment",
+                    "string",
+                    "number",
+                    "regexp",
+                    "operator",
+                    "decorator",
+                ]),
+                tokenModifiers: strings(&[
+                    "declaration",
+                    "definition",
+                    "readonly",
+                    "static",
+                    "deprecated",
+                    "abstract",
+                    "async",
+                    "modification",
+                    "documentation",
+                    "defaultLibrary",
+                ]),
+                formats: vec![TokenFormat::Relative],
+                overlappingTokenSupport: Some(true),
+                multilineTokenSupport: Some(true),
+                serverCancelSupport: Some(true),
+                augmentsSyntaxTokens: Some(true),
+            }),
+            moniker: Some(MonikerClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            typeHierarchy: Some(TypeHierarchyClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            inlineValue: Some(InlineValueClientCapabilities {
+                dynamicRegistration: Some(true),
+            }),
+            inlayHint: Some(InlayHintClientCapabilities {
+                dynamicRegistration: Some(true),
+                resolveSupport: Some(InlayHintClientCapabilitiesResolveSupport {
+                    properties: strings(&[
+                        "tooltip",
+                        "textEdits",
+                        "label.tooltip",
+                        "label.location",
+                        "label.command",
+                    ]),
+                }),
+            }),
+            diagnostic: Some(DiagnosticClientCapabilities {
+                dynamicRegistration: Some(true),
+                relatedDocumentSupport: Some(true),
+            }),
+        };
+
+        ClientCapabilities {
+            workspace: Some(workspace),
+            textDocument: Some(text_document),
+            notebookDocument: Some(NotebookDocumentClientCapabilities {
+                synchronization: NotebookDocumentSyncClientCapabilities {
+                    dynamicRegistration: Some(true),
+                    executionSummarySupport: Some(true),
+                },
+            }),
+            window: Some(ClientCapabilitiesWindow {
+                workDoneProgress: Some(true),
+                showMessage: Some(ShowMessageRequestClientCapabilities {
+                    messageActionItem: Some(
+                        ShowMessageRequestClientCapabilitiesMessageActionItem {
+                            additionalPropertiesSupport: Some(true),
+                        },
+                    ),
+                }),
+                showDocument: Some(ShowDocumentClientCapabilities { support: true }),
+            }),
+            general: Some(ClientCapabilitiesGeneral {
+                staleRequestSupport: Some(StaleRequestSupport {
+                    cancel: true,
+                    retryOnContentModified: strings(&[
+                        "textDocument/semanticTokens/full",
+                        "textDocument/semanticTokens/range",
+                        "textDocument/semanticTokens/full/delta",
+                    ]),
+                }),
+                regularExpressions: Some(RegularExpressionsClientCapabilities {
+                    engine: "ECMAScript".to_string(),
+                    version: Some("ES2020".to_string()),
+                }),
+                markdown: Some(MarkdownClientCapabilities {
+                    parser: "marked".to_string(),
+                    version: Some("1.1.0".to_string()),
+                    allowedTags: None,
+                }),
+                positionEncodings: Some(vec![
+                    PositionEncodingKind::UTF8,
+                    PositionEncodingKind::UTF32,
+                    PositionEncodingKind::UTF16,
+                ]),
+            }),
+            experimental: None,
+        }
+    }
+
+    /// capabilities of a client that advertises nothing beyond the base
+    /// protocol, so every optional feature reads as unsupported
+    pub fn minimal() -> Self {
+        ClientCapabilities::default()
+    }
+}
+
+/// `$method(&self) -> bool` for each optional `Boolean` capability `$field`,
+/// reading an omitted capability as disabled
+macro_rules! impl_capabilities_enabled {
+    ($($name:ident { $($field:ident => $method:ident),* $(,)? })*) => {
+        $(impl $name {
+            $(
+                #[doc = concat!("whether `", stringify!($field), "` is set, `false` if omitted")]
+                pub fn $method(&self) -> bool {
+                    self.$field.unwrap_or(false)
+                }
+            )*
+        })*
+    };
+}
+
+impl_capabilities_enabled! {
+    WorkspaceEditClientCapabilitiesChangeAnnotationSupport {
+        groupsOnLabel => groups_on_label_enabled,
+    }
+    WorkspaceEditClientCapabilities {
+        documentChanges => document_changes_enabled,
+        normalizesLineEndings => normalizes_line_endings_enabled,
+    }
+    ClientCapabilitiesWorkspaceFileOperations {
+        dynamicRegistration => dynamic_registration_enabled,
+        didCreate => did_create_enabled,
+        willCreate => will_create_enabled,
+        didRename => did_rename_enabled,
+        willRename => will_rename_enabled,
+        didDelete => did_delete_enabled,
+        willDelete => will_delete_enabled,
+    }
+    ClientCapabilitiesWorkspace {
+        applyEdit => apply_edit_enabled,
+        workspaceFolders => workspace_folders_enabled,
+        configuration => configuration_enabled,
+    }
+    ClientCapabilitiesWindow {
+        workDoneProgress => work_done_progress_enabled,
+    }
+    TextDocumentSyncClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        willSave => will_save_enabled,
+        willSaveWaitUntil => will_save_wait_until_enabled,
+        didSave => did_save_enabled,
+    }
+    NotebookDocumentSyncClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        executionSummarySupport => execution_summary_support_enabled,
+    }
+    DeclarationClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        linkSupport => link_support_enabled,
+    }
+    DefinitionClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        linkSupport => link_support_enabled,
+    }
+    TypeDefinitionClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        linkSupport => link_support_enabled,
+    }
+    ImplementationClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        linkSupport => link_support_enabled,
+    }
+    ReferenceClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    CallHierarchyClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    TypeHierarchyClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    DocumentHighlightClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    DocumentLinkClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        tooltipSupport => tooltip_support_enabled,
+    }
+    HoverClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    CodeLensClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    CodeLensWorkspaceClientCapabilities {
+        refreshSupport => refresh_support_enabled,
+    }
+    FoldingRangeClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        lineFoldingOnly => line_folding_only_enabled,
+    }
+    SelectionRangeClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    DocumentSymbolClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        hierarchicalDocumentSymbolSupport => hierarchical_document_symbol_support_enabled,
+        labelSupport => label_support_enabled,
+    }
+    SemanticTokensClientCapabilitiesRequests {
+        range => range_enabled,
+    }
+    SemanticTokensClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        overlappingTokenSupport => overlapping_token_support_enabled,
+        multilineTokenSupport => multiline_token_support_enabled,
+        serverCancelSupport => server_cancel_support_enabled,
+        augmentsSyntaxTokens => augments_syntax_tokens_enabled,
+    }
+    SemanticTokensWorkspaceClientCapabilities {
+        refreshSupport => refresh_support_enabled,
+    }
+    InlayHintClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    InlayHintWorkspaceClientCapabilities {
+        refreshSupport => refresh_support_enabled,
+    }
+    InlineValueClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    InlineValueWorkspaceClientCapabilities {
+        refreshSupport => refresh_support_enabled,
+    }
+    MonikerClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    CompletionClientCapabilitiesCompletionItem {
+        snippetSupport => snippet_support_enabled,
+        commitCharactersSupport => commit_characters_support_enabled,
+        deprecatedSupport => deprecated_support_enabled,
+        preselectSupport => preselect_support_enabled,
+        insertReplaceSupport => insert_replace_support_enabled,
+        labelDetailsSupport => label_details_support_enabled,
+    }
+    CompletionClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        contextSupport => context_support_enabled,
+    }
+    PublishDiagnosticsClientCapabilities {
+        relatedInformation => related_information_enabled,
+        versionSupport => version_support_enabled,
+        codeDescriptionSupport => code_description_support_enabled,
+        dataSupport => data_support_enabled,
+    }
+    DiagnosticClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        relatedDocumentSupport => related_document_support_enabled,
+    }
+    DiagnosticWorkspaceClientCapabilities {
+        refreshSupport => refresh_support_enabled,
+    }
+    SignatureHelpClientCapabilitiesSignatureInformationParameterInformation {
+        labelOffsetSupport => label_offset_support_enabled,
+    }
+    SignatureHelpClientCapabilitiesSignatureInformation {
+        activeParameterSupport => active_parameter_support_enabled,
+    }
+    SignatureHelpClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        contextSupport => context_support_enabled,
+    }
+    CodeActionClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        isPreferredSupport => is_preferred_support_enabled,
+        disabledSupport => disabled_support_enabled,
+        dataSupport => data_support_enabled,
+        honorsChangeAnnotations => honors_change_annotations_enabled,
+    }
+    DocumentColorClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    DocumentFormattingClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    DocumentRangeFormattingClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    DocumentOnTypeFormattingClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    RenameClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        prepareSupport => prepare_support_enabled,
+        honorsChangeAnnotations => honors_change_annotations_enabled,
+    }
+    LinkedEditingRangeClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    WorkspaceSymbolClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    DidChangeConfigurationClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    DidChangeWatchedFilesClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+        relativePatternSupport => relative_pattern_support_enabled,
+    }
+    ExecuteCommandClientCapabilities {
+        dynamicRegistration => dynamic_registration_enabled,
+    }
+    ShowMessageRequestClientCapabilitiesMessageActionItem {
+        additionalPropertiesSupport => additional_properties_support_enabled,
+    }
+}
+
 /// extracted from [InitializeResult::ServerInfo]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ServerInfo {
@@ -1911,6 +4782,7 @@ pub struct ServerInfo {
     /**
      * The server's version as defined by the server.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
 }
 
@@ -1926,9 +4798,40 @@ pub struct InitializeResult {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub serverInfo: Option<ServerInfo>,
 }
 
+impl InitializeResult {
+    /// ```
+    /// use rust_lsp_types::{InitializeResult, ServerCapabilities};
+    ///
+    /// let result = InitializeResult::new(ServerCapabilities::default().hover(true))
+    ///     .with_server_info("my-server", None);
+    /// assert_eq!(
+    ///     serde_json::to_value(&result).unwrap(),
+    ///     serde_json::json!({
+    ///         "capabilities": { "hoverProvider": true },
+    ///         "serverInfo": { "name": "my-server" },
+    ///     })
+    /// );
+    /// ```
+    pub fn new(capabilities: ServerCapabilities) -> Self {
+        InitializeResult {
+            capabilities,
+            serverInfo: None,
+        }
+    }
+
+    pub fn with_server_info(mut self, name: impl Into<String>, version: Option<String>) -> Self {
+        self.serverInfo = Some(ServerInfo {
+            name: name.into(),
+            version,
+        });
+        self
+    }
+}
+
 /**
  * Known error codes for an `InitializeErrorCodes`,
  */
@@ -1956,11 +4859,27 @@ pub struct InitializeError {
     pub retry: Boolean,
 }
 
+/// `From` each variant's value for a provider union, so e.g. `true` or a
+/// `HoverOptions` converts into a [ServerCapabilitiesProviders::HoverProvider]
+macro_rules! impl_provider_from {
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        $(impl From<$variant> for $name {
+            fn from(value: $variant) -> Self {
+                $name::$variant(value)
+            }
+        })*
+    };
+}
+
 pub mod ServerCapabilitiesProviders {
     use super::*;
 
+    // a registration options variant comes before the plain options it
+    // extends, which would otherwise match it and drop `documentSelector`
+    // and `id`
+
     /// extracted from [ServerCapabilities::textDocumentSync]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum TextDocumentSync {
         TextDocumentSyncOptions(TextDocumentSyncOptions),
@@ -1968,15 +4887,15 @@ pub mod ServerCapabilitiesProviders {
     }
 
     /// extracted from [ServerCapabilities::notebookDocumentSync]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum NotebookDocumentSync {
-        NotebookDocumentSyncOptions(NotebookDocumentSyncOptions),
         NotebookDocumentSyncRegistrationOptions(NotebookDocumentSyncRegistrationOptions),
+        NotebookDocumentSyncOptions(NotebookDocumentSyncOptions),
     }
 
     /// extracted from [ServerCapabilities::hoverProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum HoverProvider {
         Boolean(Boolean),
@@ -1984,16 +4903,16 @@ pub mod ServerCapabilitiesProviders {
     }
 
     /// extracted from [ServerCapabilities::declarationProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum DeclarationProvider {
         Boolean(Boolean),
-        DeclarationOptions(DeclarationOptions),
         DeclarationRegistrationOptions(DeclarationRegistrationOptions),
+        DeclarationOptions(DeclarationOptions),
     }
 
     /// extracted from [ServerCapabilities::definitionProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum DefinitionProvider {
         Boolean(Boolean),
@@ -2001,25 +4920,25 @@ pub mod ServerCapabilitiesProviders {
     }
 
     /// extracted from [ServerCapabilities::typeDefinitionProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum TypeDefinitionProvider {
         Boolean(Boolean),
-        TypeDefinitionOptions(TypeDefinitionOptions),
         TypeDefinitionRegistrationOptions(TypeDefinitionRegistrationOptions),
+        TypeDefinitionOptions(TypeDefinitionOptions),
     }
 
     /// extracted from [ServerCapabilities::implementationProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum ImplementationProvider {
         Boolean(Boolean),
-        ImplementationOptions(ImplementationOptions),
         ImplementationRegistrationOptions(ImplementationRegistrationOptions),
+        ImplementationOptions(ImplementationOptions),
     }
 
     /// extracted from [ServerCapabilities::referencesProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum ReferencesProvider {
         Boolean(Boolean),
@@ -2027,7 +4946,7 @@ pub mod ServerCapabilitiesProviders {
     }
 
     /// extracted from [ServerCapabilities::documentHighlightProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum DocumentHighlightProvider {
         Boolean(Boolean),
@@ -2035,7 +4954,7 @@ pub mod ServerCapabilitiesProviders {
     }
 
     /// extracted from [ServerCapabilities::documentSymbolProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum DocumentSymbolProvider {
         Boolean(Boolean),
@@ -2043,7 +4962,7 @@ pub mod ServerCapabilitiesProviders {
     }
 
     /// extracted from [ServerCapabilities::codeActionProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum CodeActionProvider {
         Boolean(Boolean),
@@ -2051,24 +4970,32 @@ pub mod ServerCapabilitiesProviders {
     }
 
     /// extracted from [ServerCapabilities::colorProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum ColorProvider {
         Boolean(Boolean),
-        DocumentColorOptions(DocumentColorOptions),
         DocumentColorRegistrationOptions(DocumentColorRegistrationOptions),
+        DocumentColorOptions(DocumentColorOptions),
     }
 
     /// extracted from [ServerCapabilities::documentFormattingProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum DocumentFormattingProvider {
         Boolean(Boolean),
         DocumentFormattingOptions(DocumentFormattingOptions),
     }
 
+    /// extracted from [ServerCapabilities::documentRangeFormattingProvider]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(untagged)]
+    pub enum DocumentRangeFormattingProvider {
+        Boolean(Boolean),
+        DocumentRangeFormattingOptions(DocumentRangeFormattingOptions),
+    }
+
     /// extracted from [ServerCapabilities::renameProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum RenameProvider {
         Boolean(Boolean),
@@ -2076,150 +5003,275 @@ pub mod ServerCapabilitiesProviders {
     }
 
     /// extracted from [ServerCapabilities::foldingRangeProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum FoldingRangeProvider {
         Boolean(Boolean),
-        FoldingRangeOptions(FoldingRangeOptions),
         FoldingRangeRegistrationOptions(FoldingRangeRegistrationOptions),
+        FoldingRangeOptions(FoldingRangeOptions),
     }
 
     /// extracted from [ServerCapabilities::selectionRangeProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum SelectionRangeProvider {
         Boolean(Boolean),
-        SelectionRangeOptions(SelectionRangeOptions),
         SelectionRangeRegistrationOptions(SelectionRangeRegistrationOptions),
+        SelectionRangeOptions(SelectionRangeOptions),
     }
 
     /// extracted from [ServerCapabilities::linkedEditingRangeProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum LinkedEditingRangeProvider {
         Boolean(Boolean),
-        LinkedEditingRangeOptions(LinkedEditingRangeOptions),
         LinkedEditingRangeRegistrationOptions(LinkedEditingRangeRegistrationOptions),
+        LinkedEditingRangeOptions(LinkedEditingRangeOptions),
     }
 
     /// extracted from [ServerCapabilities::callHierarchyProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum CallHierarchyProvider {
         Boolean(Boolean),
-        CallHierarchyOptions(CallHierarchyOptions),
         CallHierarchyRegistrationOptions(CallHierarchyRegistrationOptions),
+        CallHierarchyOptions(CallHierarchyOptions),
     }
 
     /// extracted from [ServerCapabilities::semanticTokensProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum SemanticTokensProvider {
         Boolean(Boolean),
-        SemanticTokensOptions(SemanticTokensOptions),
         SemanticTokensRegistrationOptions(SemanticTokensRegistrationOptions),
+        SemanticTokensOptions(SemanticTokensOptions),
     }
 
     /// extracted from [ServerCapabilities::monikerProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum MonikerProvider {
         Boolean(Boolean),
-        MonikerOptions(MonikerOptions),
         MonikerRegistrationOptions(MonikerRegistrationOptions),
+        MonikerOptions(MonikerOptions),
     }
 
     /// extracted from [ServerCapabilities::typeHierarchyProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum TypeHierarchyProvider {
         Boolean(Boolean),
-        TypeHierarchyOptions(TypeHierarchyOptions),
         TypeHierarchyRegistrationOptions(TypeHierarchyRegistrationOptions),
+        TypeHierarchyOptions(TypeHierarchyOptions),
     }
 
     /// extracted from [ServerCapabilities::inlineValueProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum InlineValueProvider {
         Boolean(Boolean),
-        InlineValueOptions(InlineValueOptions),
         InlineValueRegistrationOptions(InlineValueRegistrationOptions),
+        InlineValueOptions(InlineValueOptions),
     }
 
     /// extracted from [ServerCapabilities::inlayHintProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum InlayHintProvider {
         Boolean(Boolean),
-        InlayHintOptions(InlayHintOptions),
         InlayHintRegistrationOptions(InlayHintRegistrationOptions),
+        InlayHintOptions(InlayHintOptions),
     }
 
     /// extracted from [ServerCapabilities::diagnosticProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum DiagnosticProvider {
-        DiagnosticOptions(DiagnosticOptions),
         DiagnosticRegistrationOptions(DiagnosticRegistrationOptions),
+        DiagnosticOptions(DiagnosticOptions),
     }
 
     /// extracted from [ServerCapabilities::workspaceSymbolProvider]
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
     #[serde(untagged)]
     pub enum WorkspaceSymbolProvider {
         Boolean(Boolean),
         WorkspaceSymbolOptions(WorkspaceSymbolOptions),
     }
+
+    impl_provider_from!(TextDocumentSync {
+        TextDocumentSyncOptions,
+        TextDocumentSyncKind
+    });
+    impl_provider_from!(NotebookDocumentSync {
+        NotebookDocumentSyncOptions,
+        NotebookDocumentSyncRegistrationOptions
+    });
+    impl_provider_from!(HoverProvider {
+        Boolean,
+        HoverOptions
+    });
+    impl_provider_from!(DeclarationProvider {
+        Boolean,
+        DeclarationOptions,
+        DeclarationRegistrationOptions
+    });
+    impl_provider_from!(DefinitionProvider {
+        Boolean,
+        DefinitionOptions
+    });
+    impl_provider_from!(TypeDefinitionProvider {
+        Boolean,
+        TypeDefinitionOptions,
+        TypeDefinitionRegistrationOptions
+    });
+    impl_provider_from!(ImplementationProvider {
+        Boolean,
+        ImplementationOptions,
+        ImplementationRegistrationOptions
+    });
+    impl_provider_from!(ReferencesProvider {
+        Boolean,
+        ReferenceOptions
+    });
+    impl_provider_from!(DocumentHighlightProvider {
+        Boolean,
+        DocumentHighlightOptions
+    });
+    impl_provider_from!(DocumentSymbolProvider {
+        Boolean,
+        DocumentSymbolOptions
+    });
+    impl_provider_from!(CodeActionProvider {
+        Boolean,
+        CodeActionOptions
+    });
+    impl_provider_from!(ColorProvider {
+        Boolean,
+        DocumentColorOptions,
+        DocumentColorRegistrationOptions
+    });
+    impl_provider_from!(DocumentFormattingProvider {
+        Boolean,
+        DocumentFormattingOptions
+    });
+    impl_provider_from!(DocumentRangeFormattingProvider {
+        Boolean,
+        DocumentRangeFormattingOptions
+    });
+    impl_provider_from!(RenameProvider {
+        Boolean,
+        RenameOptions
+    });
+    impl_provider_from!(FoldingRangeProvider {
+        Boolean,
+        FoldingRangeOptions,
+        FoldingRangeRegistrationOptions
+    });
+    impl_provider_from!(SelectionRangeProvider {
+        Boolean,
+        SelectionRangeOptions,
+        SelectionRangeRegistrationOptions
+    });
+    impl_provider_from!(LinkedEditingRangeProvider {
+        Boolean,
+        LinkedEditingRangeOptions,
+        LinkedEditingRangeRegistrationOptions
+    });
+    impl_provider_from!(CallHierarchyProvider {
+        Boolean,
+        CallHierarchyOptions,
+        CallHierarchyRegistrationOptions
+    });
+    impl_provider_from!(SemanticTokensProvider {
+        Boolean,
+        SemanticTokensOptions,
+        SemanticTokensRegistrationOptions
+    });
+    impl_provider_from!(MonikerProvider {
+        Boolean,
+        MonikerOptions,
+        MonikerRegistrationOptions
+    });
+    impl_provider_from!(TypeHierarchyProvider {
+        Boolean,
+        TypeHierarchyOptions,
+        TypeHierarchyRegistrationOptions
+    });
+    impl_provider_from!(InlineValueProvider {
+        Boolean,
+        InlineValueOptions,
+        InlineValueRegistrationOptions
+    });
+    impl_provider_from!(InlayHintProvider {
+        Boolean,
+        InlayHintOptions,
+        InlayHintRegistrationOptions
+    });
+    impl_provider_from!(DiagnosticProvider {
+        DiagnosticOptions,
+        DiagnosticRegistrationOptions
+    });
+    impl_provider_from!(WorkspaceSymbolProvider {
+        Boolean,
+        WorkspaceSymbolOptions
+    });
 }
 
 /// extracted from [ServerCapabilitiesWorkspace::fileOperations]
 /// extracted from [ServerCapabilities::workspace]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ServerCapabilitiesWorkspaceFileOperations {
     /**
      * The server is interested in receiving didCreateFiles
      * notifications.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub didCreate: Option<FileOperationRegistrationOptions>,
 
     /**
      * The server is interested in receiving willCreateFiles requests.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub willCreate: Option<FileOperationRegistrationOptions>,
 
     /**
      * The server is interested in receiving didRenameFiles
      * notifications.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub didRename: Option<FileOperationRegistrationOptions>,
 
     /**
      * The server is interested in receiving willRenameFiles requests.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub willRename: Option<FileOperationRegistrationOptions>,
 
     /**
      * The server is interested in receiving didDeleteFiles file
      * notifications.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub didDelete: Option<FileOperationRegistrationOptions>,
 
     /**
      * The server is interested in receiving willDeleteFiles file
      * requests.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub willDelete: Option<FileOperationRegistrationOptions>,
 }
 
 /// extracted from [ServerCapabilities::workspace]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ServerCapabilitiesWorkspace {
     /**
      * The server supports workspace folder.
      *
      * @since 3.6.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workspaceFolders: Option<WorkspaceFoldersServerCapabilities>,
 
     /**
@@ -2227,10 +5279,11 @@ pub struct ServerCapabilitiesWorkspace {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub fileOperations: Option<ServerCapabilitiesWorkspaceFileOperations>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
 pub struct ServerCapabilities {
     /**
      * The position encoding the server picked from the encodings offered
@@ -2243,6 +5296,7 @@ pub struct ServerCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub positionEncoding: Option<PositionEncodingKind>,
 
     /**
@@ -2251,6 +5305,7 @@ pub struct ServerCapabilities {
      * TextDocumentSyncKind number. If omitted it defaults to
      * `TextDocumentSyncKind.None`.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub textDocumentSync: Option<ServerCapabilitiesProviders::TextDocumentSync>,
 
     /**
@@ -2258,21 +5313,25 @@ pub struct ServerCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub notebookDocumentSync: Option<ServerCapabilitiesProviders::NotebookDocumentSync>,
 
     /**
      * The server provides completion support.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub completionProvider: Option<CompletionOptions>,
 
     /**
      * The server provides hover support.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub hoverProvider: Option<ServerCapabilitiesProviders::HoverProvider>,
 
     /**
      * The server provides signature help support.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub signatureHelpProvider: Option<SignatureHelpOptions>,
 
     /**
@@ -2280,11 +5339,13 @@ pub struct ServerCapabilities {
      *
      * @since 3.14.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub declarationProvider: Option<ServerCapabilitiesProviders::DeclarationProvider>,
 
     /**
      * The server provides goto definition support.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub definitionProvider: Option<ServerCapabilitiesProviders::DefinitionProvider>,
 
     /**
@@ -2292,6 +5353,7 @@ pub struct ServerCapabilities {
      *
      * @since 3.6.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub typeDefinitionProvider: Option<ServerCapabilitiesProviders::TypeDefinitionProvider>,
 
     /**
@@ -2299,21 +5361,25 @@ pub struct ServerCapabilities {
      *
      * @since 3.6.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub implementationProvider: Option<ServerCapabilitiesProviders::ImplementationProvider>,
 
     /**
      * The server provides find references support.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub referencesProvider: Option<ServerCapabilitiesProviders::ReferencesProvider>,
 
     /**
      * The server provides document highlight support.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentHighlightProvider: Option<ServerCapabilitiesProviders::DocumentHighlightProvider>,
 
     /**
      * The server provides document symbol support.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentSymbolProvider: Option<ServerCapabilitiesProviders::DocumentSymbolProvider>,
 
     /**
@@ -2321,16 +5387,19 @@ pub struct ServerCapabilities {
      * only valid if the client signals code action literal support via the
      * property `textDocument.codeAction.codeActionLiteralSupport`.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub codeActionProvider: Option<ServerCapabilitiesProviders::CodeActionProvider>,
 
     /**
      * The server provides code lens.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub codeLensProvider: Option<CodeLensOptions>,
 
     /**
      * The server provides document link support.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentLinkProvider: Option<DocumentLinkOptions>,
 
     /**
@@ -2338,22 +5407,26 @@ pub struct ServerCapabilities {
      *
      * @since 3.6.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub colorProvider: Option<ServerCapabilitiesProviders::ColorProvider>,
 
     /**
      * The server provides document formatting.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentFormattingProvider: Option<ServerCapabilitiesProviders::DocumentFormattingProvider>,
 
     /**
      * The server provides document range formatting.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentRangeFormattingProvider:
-        Option<ServerCapabilitiesProviders::DocumentFormattingProvider>,
+        Option<ServerCapabilitiesProviders::DocumentRangeFormattingProvider>,
 
     /**
      * The server provides document formatting on typing.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentOnTypeFormattingProvider: Option<DocumentOnTypeFormattingOptions>,
 
     /**
@@ -2361,6 +5434,7 @@ pub struct ServerCapabilities {
      * specified if the client states that it supports
      * `prepareSupport` in its initial `initialize` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub renameProvider: Option<ServerCapabilitiesProviders::RenameProvider>,
 
     /**
@@ -2368,11 +5442,13 @@ pub struct ServerCapabilities {
      *
      * @since 3.10.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub foldingRangeProvider: Option<ServerCapabilitiesProviders::FoldingRangeProvider>,
 
     /**
      * The server provides execute command support.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub executeCommandProvider: Option<ExecuteCommandOptions>,
 
     /**
@@ -2380,6 +5456,7 @@ pub struct ServerCapabilities {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub selectionRangeProvider: Option<ServerCapabilitiesProviders::SelectionRangeProvider>,
 
     /**
@@ -2387,6 +5464,7 @@ pub struct ServerCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub linkedEditingRangeProvider: Option<ServerCapabilitiesProviders::LinkedEditingRangeProvider>,
 
     /**
@@ -2394,6 +5472,7 @@ pub struct ServerCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub callHierarchyProvider: Option<ServerCapabilitiesProviders::CallHierarchyProvider>,
 
     /**
@@ -2401,6 +5480,7 @@ pub struct ServerCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub semanticTokensProvider: Option<ServerCapabilitiesProviders::SemanticTokensProvider>,
 
     /**
@@ -2408,6 +5488,7 @@ pub struct ServerCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub monikerProvider: Option<ServerCapabilitiesProviders::MonikerProvider>,
 
     /**
@@ -2415,6 +5496,7 @@ pub struct ServerCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub typeHierarchyProvider: Option<ServerCapabilitiesProviders::TypeHierarchyProvider>,
 
     /**
@@ -2422,6 +5504,7 @@ pub struct ServerCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub inlineValueProvider: Option<ServerCapabilitiesProviders::InlineValueProvider>,
 
     /**
@@ -2429,6 +5512,7 @@ pub struct ServerCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub inlayHintProvider: Option<ServerCapabilitiesProviders::InlayHintProvider>,
 
     /**
@@ -2436,24 +5520,152 @@ pub struct ServerCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub diagnosticProvider: Option<ServerCapabilitiesProviders::DiagnosticProvider>,
 
     /**
      * The server provides workspace symbol support.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workspaceSymbolProvider: Option<ServerCapabilitiesProviders::WorkspaceSymbolProvider>,
 
     /**
      * Workspace specific server capabilities
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workspace: Option<ServerCapabilitiesWorkspace>,
 
     /**
      * Experimental server capabilities.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub experimental: Option<LSPAny>,
 }
 
+/// setters for the capabilities of [ServerCapabilities], each taking the
+/// capability itself or anything converting into it, e.g. `true` or the
+/// options for a provider union
+macro_rules! capability_setters {
+    ($($method:ident => $field:ident: $ty:ty),* $(,)?) => {
+        $(#[doc = concat!("sets [ServerCapabilities::", stringify!($field), "]")]
+        pub fn $method(mut self, capability: impl Into<$ty>) -> Self {
+            self.$field = Some(capability.into());
+            self
+        })*
+    };
+}
+
+/// ```
+/// use rust_lsp_types::{ServerCapabilities, TextDocumentSyncKind};
+///
+/// let capabilities = ServerCapabilities::default()
+///     .text_document_sync(TextDocumentSyncKind::Incremental)
+///     .hover(true)
+///     .definition(true);
+/// assert_eq!(
+///     serde_json::to_value(&capabilities).unwrap(),
+///     serde_json::json!({
+///         "textDocumentSync": 2,
+///         "hoverProvider": true,
+///         "definitionProvider": true,
+///     })
+/// );
+/// ```
+impl ServerCapabilities {
+    capability_setters! {
+        position_encoding => positionEncoding: PositionEncodingKind,
+        text_document_sync => textDocumentSync: ServerCapabilitiesProviders::TextDocumentSync,
+        notebook_document_sync => notebookDocumentSync: ServerCapabilitiesProviders::NotebookDocumentSync,
+        completion => completionProvider: CompletionOptions,
+        hover => hoverProvider: ServerCapabilitiesProviders::HoverProvider,
+        signature_help => signatureHelpProvider: SignatureHelpOptions,
+        declaration => declarationProvider: ServerCapabilitiesProviders::DeclarationProvider,
+        definition => definitionProvider: ServerCapabilitiesProviders::DefinitionProvider,
+        type_definition => typeDefinitionProvider: ServerCapabilitiesProviders::TypeDefinitionProvider,
+        implementation => implementationProvider: ServerCapabilitiesProviders::ImplementationProvider,
+        references => referencesProvider: ServerCapabilitiesProviders::ReferencesProvider,
+        document_highlight => documentHighlightProvider: ServerCapabilitiesProviders::DocumentHighlightProvider,
+        document_symbol => documentSymbolProvider: ServerCapabilitiesProviders::DocumentSymbolProvider,
+        code_action => codeActionProvider: ServerCapabilitiesProviders::CodeActionProvider,
+        code_lens => codeLensProvider: CodeLensOptions,
+        document_link => documentLinkProvider: DocumentLinkOptions,
+        color => colorProvider: ServerCapabilitiesProviders::ColorProvider,
+        document_formatting => documentFormattingProvider: ServerCapabilitiesProviders::DocumentFormattingProvider,
+        document_range_formatting => documentRangeFormattingProvider: ServerCapabilitiesProviders::DocumentRangeFormattingProvider,
+        document_on_type_formatting => documentOnTypeFormattingProvider: DocumentOnTypeFormattingOptions,
+        rename => renameProvider: ServerCapabilitiesProviders::RenameProvider,
+        folding_range => foldingRangeProvider: ServerCapabilitiesProviders::FoldingRangeProvider,
+        execute_command => executeCommandProvider: ExecuteCommandOptions,
+        selection_range => selectionRangeProvider: ServerCapabilitiesProviders::SelectionRangeProvider,
+        linked_editing_range => linkedEditingRangeProvider: ServerCapabilitiesProviders::LinkedEditingRangeProvider,
+        call_hierarchy => callHierarchyProvider: ServerCapabilitiesProviders::CallHierarchyProvider,
+        semantic_tokens => semanticTokensProvider: ServerCapabilitiesProviders::SemanticTokensProvider,
+        moniker => monikerProvider: ServerCapabilitiesProviders::MonikerProvider,
+        type_hierarchy => typeHierarchyProvider: ServerCapabilitiesProviders::TypeHierarchyProvider,
+        inline_value => inlineValueProvider: ServerCapabilitiesProviders::InlineValueProvider,
+        inlay_hint => inlayHintProvider: ServerCapabilitiesProviders::InlayHintProvider,
+        diagnostic => diagnosticProvider: ServerCapabilitiesProviders::DiagnosticProvider,
+        workspace_symbol => workspaceSymbolProvider: ServerCapabilitiesProviders::WorkspaceSymbolProvider,
+    }
+}
+
+impl ServerCapabilities {
+    /// combines the capabilities of two providers. A capability set on only
+    /// one side is taken from that side; if both set it, `self` wins, since
+    /// provider options can't be combined generically
+    pub fn merge(self, other: Self) -> Self {
+        ServerCapabilities {
+            positionEncoding: self.positionEncoding.or(other.positionEncoding),
+            textDocumentSync: self.textDocumentSync.or(other.textDocumentSync),
+            notebookDocumentSync: self.notebookDocumentSync.or(other.notebookDocumentSync),
+            completionProvider: self.completionProvider.or(other.completionProvider),
+            hoverProvider: self.hoverProvider.or(other.hoverProvider),
+            signatureHelpProvider: self.signatureHelpProvider.or(other.signatureHelpProvider),
+            declarationProvider: self.declarationProvider.or(other.declarationProvider),
+            definitionProvider: self.definitionProvider.or(other.definitionProvider),
+            typeDefinitionProvider: self.typeDefinitionProvider.or(other.typeDefinitionProvider),
+            implementationProvider: self.implementationProvider.or(other.implementationProvider),
+            referencesProvider: self.referencesProvider.or(other.referencesProvider),
+            documentHighlightProvider: self
+                .documentHighlightProvider
+                .or(other.documentHighlightProvider),
+            documentSymbolProvider: self.documentSymbolProvider.or(other.documentSymbolProvider),
+            codeActionProvider: self.codeActionProvider.or(other.codeActionProvider),
+            codeLensProvider: self.codeLensProvider.or(other.codeLensProvider),
+            documentLinkProvider: self.documentLinkProvider.or(other.documentLinkProvider),
+            colorProvider: self.colorProvider.or(other.colorProvider),
+            documentFormattingProvider: self
+                .documentFormattingProvider
+                .or(other.documentFormattingProvider),
+            documentRangeFormattingProvider: self
+                .documentRangeFormattingProvider
+                .or(other.documentRangeFormattingProvider),
+            documentOnTypeFormattingProvider: self
+                .documentOnTypeFormattingProvider
+                .or(other.documentOnTypeFormattingProvider),
+            renameProvider: self.renameProvider.or(other.renameProvider),
+            foldingRangeProvider: self.foldingRangeProvider.or(other.foldingRangeProvider),
+            executeCommandProvider: self.executeCommandProvider.or(other.executeCommandProvider),
+            selectionRangeProvider: self.selectionRangeProvider.or(other.selectionRangeProvider),
+            linkedEditingRangeProvider: self
+                .linkedEditingRangeProvider
+                .or(other.linkedEditingRangeProvider),
+            callHierarchyProvider: self.callHierarchyProvider.or(other.callHierarchyProvider),
+            semanticTokensProvider: self.semanticTokensProvider.or(other.semanticTokensProvider),
+            monikerProvider: self.monikerProvider.or(other.monikerProvider),
+            typeHierarchyProvider: self.typeHierarchyProvider.or(other.typeHierarchyProvider),
+            inlineValueProvider: self.inlineValueProvider.or(other.inlineValueProvider),
+            inlayHintProvider: self.inlayHintProvider.or(other.inlayHintProvider),
+            diagnosticProvider: self.diagnosticProvider.or(other.diagnosticProvider),
+            workspaceSymbolProvider: self
+                .workspaceSymbolProvider
+                .or(other.workspaceSymbolProvider),
+            workspace: self.workspace.or(other.workspace),
+            experimental: self.experimental.or(other.experimental),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct InitializedParams {}
 
@@ -2476,6 +5688,7 @@ pub struct Registration {
     /**
      * Options necessary for the registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub registerOptions: Option<LSPAny>,
 }
 
@@ -2484,6 +5697,12 @@ pub struct RegistrationParams {
     pub registrations: Vec<Registration>,
 }
 
+impl RegistrationParams {
+    pub fn new(registrations: Vec<Registration>) -> Self {
+        RegistrationParams { registrations }
+    }
+}
+
 /**
  * Static registration options to be returned in the initialize request.
  */
@@ -2493,6 +5712,7 @@ pub struct StaticRegistrationOptions {
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -2505,7 +5725,7 @@ pub struct TextDocumentRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 }
 
 /**
@@ -2525,6 +5745,15 @@ pub struct Unregistration {
     pub method: String,
 }
 
+impl Unregistration {
+    pub fn new(id: impl Into<String>, method: impl Into<String>) -> Self {
+        Unregistration {
+            id: id.into(),
+            method: method.into(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct UnregistrationParams {
     /// This should correctly be named `unregistrations`. However changing this
@@ -2533,6 +5762,15 @@ pub struct UnregistrationParams {
     pub unregisterations: Vec<Unregistration>,
 }
 
+impl UnregistrationParams {
+    /// params sent under the spec's misspelled `unregisterations` key
+    pub fn new(unregistrations: Vec<Unregistration>) -> Self {
+        UnregistrationParams {
+            unregisterations: unregistrations,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SetTraceParams {
     /**
@@ -2551,14 +5789,43 @@ pub struct LogTraceParams {
      * Additional information that can be computed if the `trace` configuration
      * is set to `'verbose'`
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub verbose: Option<String>,
 }
 
+impl SetTraceParams {
+    pub fn new(value: TraceValue) -> Self {
+        SetTraceParams { value }
+    }
+}
+
+impl LogTraceParams {
+    pub fn new(message: impl Into<String>) -> Self {
+        LogTraceParams {
+            message: message.into(),
+            verbose: None,
+        }
+    }
+
+    /// sets the extra detail, which should only be sent when
+    /// [should_log_verbose] holds for the negotiated trace
+    pub fn verbose(mut self, verbose: impl Into<String>) -> Self {
+        self.verbose = Some(verbose.into());
+        self
+    }
+}
+
+/// whether `$/logTrace` notifications may carry [LogTraceParams::verbose],
+/// which the spec reserves for a `verbose` trace
+pub fn should_log_verbose(trace: TraceValue) -> bool {
+    trace == TraceValue::Verbose
+}
+
 /**
  * Defines how the host (editor) should sync document changes to the language
  * server.
  */
-#[derive(Serialize_repr, Deserialize_repr, Debug)]
+#[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq)]
 #[repr(u8)]
 pub enum TextDocumentSyncKind {
     /**
@@ -2580,6 +5847,28 @@ pub enum TextDocumentSyncKind {
     Incremental = 2,
 }
 
+impl TextDocumentSyncKind {
+    pub fn is_incremental(&self) -> bool {
+        matches!(self, TextDocumentSyncKind::Incremental)
+    }
+
+    /// whether a client syncing with this kind has to send the whole document
+    /// text for an edit it has as `change`: always for
+    /// [TextDocumentSyncKind::Full], only for a change without a range for
+    /// [TextDocumentSyncKind::Incremental], and never for
+    /// [TextDocumentSyncKind::None], which sends nothing at all
+    pub fn should_send_full(&self, change: &TextDocumentContentChangeEvent) -> bool {
+        match self {
+            TextDocumentSyncKind::None => false,
+            TextDocumentSyncKind::Full => true,
+            TextDocumentSyncKind::Incremental => matches!(
+                change,
+                TextDocumentContentChangeEvent::TextDocumentContentChangeEventWithoutRange(_)
+            ),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DidOpenTextDocumentParams {
     /**
@@ -2588,6 +5877,27 @@ pub struct DidOpenTextDocumentParams {
     pub textDocument: TextDocumentItem,
 }
 
+impl DidOpenTextDocumentParams {
+    /// ```
+    /// use rust_lsp_types::{DidOpenTextDocumentParams, LanguageId, TextDocumentItem};
+    ///
+    /// let params = DidOpenTextDocumentParams::new(TextDocumentItem::new(
+    ///     "file:///src/main.rs",
+    ///     LanguageId::Rust,
+    ///     1,
+    ///     "fn main() {}",
+    /// ));
+    /// let json = serde_json::to_value(&params).unwrap();
+    /// assert_eq!(json["textDocument"]["languageId"], "rust");
+    /// assert_eq!(json["textDocument"]["version"], 1);
+    /// ```
+    pub fn new(text_document: TextDocumentItem) -> Self {
+        DidOpenTextDocumentParams {
+            textDocument: text_document,
+        }
+    }
+}
+
 /**
  * Describe options to be used when registering for text document change events.
  */
@@ -2598,7 +5908,7 @@ pub struct TextDocumentChangeRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
     /**
      * How documents are synced to the server. See TextDocumentSyncKind.Full
      * and TextDocumentSyncKind.Incremental.
@@ -2646,6 +5956,7 @@ pub struct TextDocumentContentChangeEventWithRange {
      *
      * @deprecated use range instead.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rangeLength: Option<UInteger>,
 
     /**
@@ -2673,6 +5984,133 @@ pub enum TextDocumentContentChangeEvent {
     TextDocumentContentChangeEventWithoutRange(TextDocumentContentChangeEventWithoutRange),
 }
 
+/// applies `changes` to `text` in order, each on the result of the previous
+/// one as the spec asks; character offsets are counted in `encoding`, and a
+/// position past the end of its line or of the text is clamped to it
+pub fn apply_content_changes(
+    text: &mut String,
+    changes: &[TextDocumentContentChangeEvent],
+    encoding: PositionEncodingKind,
+) {
+    for change in changes {
+        match change {
+            TextDocumentContentChangeEvent::TextDocumentContentChangeEventWithRange(change) => {
+                let start = byte_offset(text, change.range.start, encoding);
+                let end = byte_offset(text, change.range.end, encoding).max(start);
+                text.replace_range(start..end, &change.text);
+            }
+            TextDocumentContentChangeEvent::TextDocumentContentChangeEventWithoutRange(change) => {
+                text.clone_from(&change.text);
+            }
+        }
+    }
+}
+
+/// checks, as far as possible without the document text, that `changes` are
+/// consistent with being applied one after another: no range ends before it
+/// starts, and once a change without a range has set the whole text, no
+/// range refers to a line past the end of the text as left by the changes
+/// before it. A sequence that passes may still be wrong for the real text.
+pub fn validate_change_sequence(
+    changes: &[TextDocumentContentChangeEvent],
+) -> Result<(), ChangeOrderError> {
+    // the exact line count, known from the last full text change on
+    let mut line_count = None;
+    for (index, change) in changes.iter().enumerate() {
+        match change {
+            TextDocumentContentChangeEvent::TextDocumentContentChangeEventWithRange(change) => {
+                let range = change.range;
+                if range.end < range.start {
+                    return Err(ChangeOrderError::InvertedRange { change: index });
+                }
+                if let Some(lines) = line_count {
+                    if range.end.line >= lines {
+                        return Err(ChangeOrderError::LineOutOfBounds {
+                            change: index,
+                            line: range.end.line,
+                            line_count: lines,
+                        });
+                    }
+                    line_count = Some(lines - range.line_count() + 1 + line_breaks(&change.text));
+                }
+            }
+            TextDocumentContentChangeEvent::TextDocumentContentChangeEventWithoutRange(change) => {
+                line_count = Some(line_breaks(&change.text) + 1);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// the number of `\n`, `\r\n` and `\r` line breaks in `text`
+fn line_breaks(text: &str) -> UInteger {
+    (lines_with_breaks(text).count() - 1) as UInteger
+}
+
+/// why [validate_change_sequence] rejected a sequence of changes, with the
+/// index of the offending change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeOrderError {
+    /// the change's range ends before it starts
+    InvertedRange { change: usize },
+    /// the change's range ends on a line past the end of the text the
+    /// earlier changes left
+    LineOutOfBounds {
+        change: usize,
+        line: UInteger,
+        line_count: UInteger,
+    },
+}
+
+impl std::fmt::Display for ChangeOrderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeOrderError::InvertedRange { change } => {
+                write!(
+                    f,
+                    "content change {change} has a range ending before its start"
+                )
+            }
+            ChangeOrderError::LineOutOfBounds {
+                change,
+                line,
+                line_count,
+            } => write!(
+                f,
+                "content change {change} refers to line {line} of a text with {line_count} lines"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ChangeOrderError {}
+
+/// the byte offset of `position` in `text`, clamped to the end of its line
+/// (before the line break) or of the text
+fn byte_offset(text: &str, position: Position, encoding: PositionEncodingKind) -> usize {
+    let mut lines = lines_with_breaks(text);
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match lines.next() {
+            Some(line) => line_start += line.len(),
+            None => return text.len(),
+        }
+    }
+    let line = without_line_break(lines.next().unwrap_or_default());
+    let mut units = 0;
+    for (index, c) in line.char_indices() {
+        if units >= position.character {
+            return line_start + index;
+        }
+        units += match encoding {
+            PositionEncodingKind::UTF8 => c.len_utf8(),
+            PositionEncodingKind::UTF16 => c.len_utf16(),
+            PositionEncodingKind::UTF32 => 1,
+        } as UInteger;
+    }
+    line_start + line.len()
+}
+
 /**
  * The parameters send in a will save text document notification.
  */
@@ -2712,11 +6150,18 @@ pub enum TextDocumentSaveReason {
     FocusOut = 3,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl_display_from_str!(TextDocumentSaveReason {
+    Manual,
+    AfterDelay,
+    FocusOut
+});
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct SaveOptions {
     /**
      * The client is supposed to include the content on save.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub includeText: Option<Boolean>,
 }
 
@@ -2727,11 +6172,12 @@ pub struct TextDocumentSaveRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /**
      * The client is supposed to include the content on save.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub includeText: Option<Boolean>,
 }
 
@@ -2746,9 +6192,38 @@ pub struct DidSaveTextDocumentParams {
      * Optional the content when saved. Depends on the includeText value
      * when the save notification was requested.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
 }
 
+impl DidSaveTextDocumentParams {
+    /// ```
+    /// use rust_lsp_types::DidSaveTextDocumentParams;
+    ///
+    /// let params = DidSaveTextDocumentParams::new("file:///src/main.rs");
+    /// assert_eq!(
+    ///     serde_json::to_value(&params).unwrap(),
+    ///     serde_json::json!({ "textDocument": { "uri": "file:///src/main.rs" } })
+    /// );
+    ///
+    /// let params = params.with_text("fn main() {}");
+    /// assert_eq!(params.text.as_deref(), Some("fn main() {}"));
+    /// ```
+    pub fn new(uri: impl Into<DocumentUri>) -> Self {
+        DidSaveTextDocumentParams {
+            textDocument: TextDocumentIdentifier::new(uri),
+            text: None,
+        }
+    }
+
+    /// includes the saved content, for clients asked to via
+    /// [SaveOptions::includeText]
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DidCloseTextDocumentParams {
     /**
@@ -2757,16 +6232,35 @@ pub struct DidCloseTextDocumentParams {
     pub textDocument: TextDocumentIdentifier,
 }
 
+impl DidCloseTextDocumentParams {
+    /// ```
+    /// use rust_lsp_types::DidCloseTextDocumentParams;
+    ///
+    /// let params = DidCloseTextDocumentParams::new("file:///src/main.rs");
+    /// assert_eq!(
+    ///     serde_json::to_value(&params).unwrap(),
+    ///     serde_json::json!({ "textDocument": { "uri": "file:///src/main.rs" } })
+    /// );
+    /// ```
+    pub fn new(uri: impl Into<DocumentUri>) -> Self {
+        DidCloseTextDocumentParams {
+            textDocument: TextDocumentIdentifier::new(uri),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TextDocumentSyncClientCapabilities {
     /**
      * Whether text document synchronization supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
      * The client supports sending will save notifications.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub willSave: Option<Boolean>,
 
     /**
@@ -2774,26 +6268,42 @@ pub struct TextDocumentSyncClientCapabilities {
      * waits for a response providing text edits which will
      * be applied to the document before it is saved.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub willSaveWaitUntil: Option<Boolean>,
 
     /**
      * The client supports did save notifications.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub didSave: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// extracted from [TextDocumentSyncOptions::save]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
 pub enum BooleanOrSaveOptions {
     Boolean(Boolean),
     SaveOptions(SaveOptions),
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl BooleanOrSaveOptions {
+    /// Whether the client should include the content on save.
+    /// A bare `true`/`false` only toggles the notification, so it never includes the text.
+    pub fn include_text(&self) -> bool {
+        match self {
+            BooleanOrSaveOptions::Boolean(_) => false,
+            BooleanOrSaveOptions::SaveOptions(options) => options.includeText.unwrap_or(false),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct TextDocumentSyncOptions {
     /**
      * Open and close notifications are sent to the server. If omitted open
      * close notification should not be sent.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub openClose: Option<Boolean>,
     /**
      * Change notifications are sent to the server. See
@@ -2801,21 +6311,25 @@ pub struct TextDocumentSyncOptions {
      * TextDocumentSyncKind.Incremental. If omitted it defaults to
      * TextDocumentSyncKind.None.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub change: Option<TextDocumentSyncKind>,
     /**
      * If present will save notifications are sent to the server. If omitted
      * the notification should not be sent.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub willSave: Option<Boolean>,
     /**
      * If present will save wait until requests are sent to the server. If
      * omitted the request should not be sent.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub willSaveWaitUntil: Option<Boolean>,
     /**
      * If present save notifications are sent to the server. If omitted the
      * notification should not be sent.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub save: Option<BooleanOrSaveOptions>,
 }
 
@@ -2846,6 +6360,7 @@ pub struct NotebookDocument {
      * Additional metadata stored with the notebook
      * document.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<LSPObject>,
 
     /**
@@ -2854,6 +6369,66 @@ pub struct NotebookDocument {
     pub cells: Vec<NotebookCell>,
 }
 
+impl NotebookDocument {
+    /// the cell whose text document is `uri`; cell document URIs are unique
+    /// across all notebooks, so this maps a change to a cell's text document
+    /// back to the cell
+    pub fn cell_by_uri(&self, uri: &str) -> Option<&NotebookCell> {
+        self.cells.iter().find(|cell| &*cell.document == uri)
+    }
+
+    /// mirrors a `notebookDocument/didChange`: replaces the metadata, splices
+    /// the cell array, updates cell data and applies the cell text changes
+    /// to `cell_documents`, the open cell text documents by URI, with
+    /// character offsets counted in `encoding`. Cells opened or closed by the
+    /// change are added to or removed from `cell_documents`. The notebook's
+    /// `version` comes with [DidChangeNotebookDocumentParams::notebookDocument]
+    /// rather than the event and is left to the caller.
+    pub fn apply_change(
+        &mut self,
+        change: NotebookDocumentChangeEvent,
+        cell_documents: &mut BTreeMap<DocumentUri, TextDocumentItem>,
+        encoding: PositionEncodingKind,
+    ) {
+        if let Some(metadata) = change.metadata {
+            self.metadata = Some(metadata);
+        }
+        let Some(cells) = change.cells else {
+            return;
+        };
+        if let Some(structure) = cells.structure {
+            let array = structure.array;
+            let start = (array.start as usize).min(self.cells.len());
+            let end = start
+                .saturating_add(array.deleteCount as usize)
+                .min(self.cells.len());
+            self.cells
+                .splice(start..end, array.cells.unwrap_or_default());
+            for document in structure.didClose.into_iter().flatten() {
+                cell_documents.remove(&document.uri);
+            }
+            for document in structure.didOpen.into_iter().flatten() {
+                cell_documents.insert(document.uri.clone(), document);
+            }
+        }
+        for data in cells.data.into_iter().flatten() {
+            if let Some(cell) = self
+                .cells
+                .iter_mut()
+                .find(|cell| cell.document == data.document)
+            {
+                *cell = data;
+            }
+        }
+        for content in cells.textContent.into_iter().flatten() {
+            if let Some(document) = cell_documents.get_mut(&content.document.uri) {
+                apply_content_changes(&mut document.text, &content.changes, encoding);
+                document.version = content.document.version;
+            }
+        }
+    }
+}
+
 /**
  * A notebook cell.
  *
@@ -2879,12 +6454,14 @@ pub struct NotebookCell {
     /**
      * Additional metadata stored with the cell.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<LSPObject>,
 
     /**
      * Additional execution summary information
      * if supported by the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub executionSummary: Option<ExecutionSummary>,
 }
 
@@ -2920,11 +6497,13 @@ pub struct ExecutionSummary {
      * Whether the execution was successful or
      * not if known by the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub success: Option<Boolean>,
 }
 
 /// String | NotebookDocumentFilter
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
 pub enum StringOrNotebookDocumentFilter {
     String(String),
     NotebookDocumentFilter(NotebookDocumentFilter),
@@ -2952,6 +6531,7 @@ pub struct NotebookCellTextDocumentFilter {
      * Will be matched against the language id of the
      * notebook cell document. '*' matches every language.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
 }
 
@@ -2962,18 +6542,83 @@ pub struct NotebookCellTextDocumentFilter {
  * @since 3.17.0
  */
 /// the TypeScript signatures indicate that at least 1 will be a string, the rest can undefined
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(try_from = "NotebookDocumentFilterFields")]
 pub struct NotebookDocumentFilter {
     /** The type of the enclosing notebook. */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub notebookType: Option<String>,
 
     /** A Uri scheme, like `file` or `untitled`. */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub scheme: Option<String>,
 
     /** A glob pattern. */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pattern: Option<String>,
 }
 
+/// the unvalidated wire form of [NotebookDocumentFilter]
+#[derive(Deserialize)]
+struct NotebookDocumentFilterFields {
+    notebookType: Option<String>,
+    scheme: Option<String>,
+    pattern: Option<String>,
+}
+
+impl TryFrom<NotebookDocumentFilterFields> for NotebookDocumentFilter {
+    type Error = String;
+
+    fn try_from(fields: NotebookDocumentFilterFields) -> Result<Self, Self::Error> {
+        NotebookDocumentFilter::new(fields.notebookType, fields.scheme, fields.pattern)
+    }
+}
+
+impl NotebookDocumentFilter {
+    /// Fails if none of `notebookType`, `scheme` or `pattern` is set.
+    pub fn new(
+        notebook_type: Option<String>,
+        scheme: Option<String>,
+        pattern: Option<String>,
+    ) -> Result<Self, String> {
+        if notebook_type.is_none() && scheme.is_none() && pattern.is_none() {
+            return Err(
+                "NotebookDocumentFilter requires at least one of notebookType, scheme or pattern"
+                    .to_string(),
+            );
+        }
+        Ok(Self {
+            notebookType: notebook_type,
+            scheme,
+            pattern,
+        })
+    }
+
+    /// Whether a notebook with this `uri` and `notebook_type` is selected by
+    /// this filter, matching like [DocumentFilter::matches].
+    pub fn matches(&self, uri: &str, notebook_type: &str) -> bool {
+        let (scheme, path) = split_uri(uri);
+        let path = decode_uri_path(path);
+        self.notebookType
+            .as_ref()
+            .is_none_or(|t| t == notebook_type)
+            && self.scheme.as_ref().is_none_or(|s| s == scheme)
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|pattern| Glob::new(pattern).matches(&path))
+    }
+
+    /// this filter with its pattern compiled, for matching many notebooks
+    pub fn compile(&self) -> CompiledFilter {
+        CompiledFilter {
+            kind: self.notebookType.clone(),
+            scheme: self.scheme.clone(),
+            glob: self.pattern.as_deref().map(Glob::new),
+        }
+    }
+}
+
 /**
  * Notebook specific client capabilities.
  *
@@ -2987,22 +6632,24 @@ pub struct NotebookDocumentSyncClientCapabilities {
      * `(NotebookDocumentSyncRegistrationOptions & NotebookDocumentSyncOptions)`
      * return value for the corresponding server capability as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
      * The client supports sending execution summary data per cell.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub executionSummarySupport: Option<Boolean>,
 }
 
 /// extracted from [NotebookDocumentSyncOptions::notebookSelector]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct NotebookDocumentSyncOptionsNotebookSelectorNotebookCell {
     pub language: String,
 }
 
 /// extracted from [NotebookDocumentSyncOptions::notebookSelector]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct NotebookDocumentSyncOptionsNotebookSelectorNotebook {
     /**
      * The notebook to be synced. If a String
@@ -3014,17 +6661,19 @@ pub struct NotebookDocumentSyncOptionsNotebookSelectorNotebook {
     /**
      * The cells of the matching notebook to be synced.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cells: Option<Vec<NotebookDocumentSyncOptionsNotebookSelectorNotebookCell>>,
 }
 
 /// extracted from [NotebookDocumentSyncOptions::notebookSelector]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct NotebookDocumentSyncOptionsNotebookSelectorCells {
     /**
      * The notebook to be synced. If a String
      * value is provided it matches against the
      * notebook type. '*' matches every notebook.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub notebook: Option<StringOrNotebookDocumentFilter>,
 
     /**
@@ -3034,7 +6683,8 @@ pub struct NotebookDocumentSyncOptionsNotebookSelectorCells {
 }
 
 /// extracted from [NotebookDocumentSyncOptions::notebookSelector]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
 pub enum NotebookDocumentSyncOptionsNotebookSelector {
     NotebookDocumentSyncOptionsNotebookSelectorNotebook(
         NotebookDocumentSyncOptionsNotebookSelectorNotebook,
@@ -3059,7 +6709,7 @@ pub enum NotebookDocumentSyncOptionsNotebookSelector {
  *
  * @since 3.17.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct NotebookDocumentSyncOptions {
     /**
      * The notebooks to be synced
@@ -3070,6 +6720,7 @@ pub struct NotebookDocumentSyncOptions {
      * Whether save notification should be forwarded to
      * the server. Will only be honored if mode === `notebook`.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub save: Option<Boolean>,
 }
 
@@ -3078,7 +6729,7 @@ pub struct NotebookDocumentSyncOptions {
  *
  * @since 3.17.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct NotebookDocumentSyncRegistrationOptions {
     /// extends NotebookDocumentSyncOptions
     /**
@@ -3091,6 +6742,7 @@ pub struct NotebookDocumentSyncRegistrationOptions {
      * Whether save notification should be forwarded to
      * the server. Will only be honored if mode === `notebook`.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub save: Option<Boolean>,
 
     /// extends StaticRegistrationOptions
@@ -3098,6 +6750,7 @@ pub struct NotebookDocumentSyncRegistrationOptions {
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -3178,11 +6831,13 @@ pub struct NotebookDocumentChangeEventCellsStructure {
     /**
      * Additional opened cell text documents.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub didOpen: Option<Vec<TextDocumentItem>>,
 
     /**
      * Additional closed cell text documents.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub didClose: Option<Vec<TextDocumentIdentifier>>,
 }
 
@@ -3200,17 +6855,20 @@ pub struct NotebookDocumentChangeEventCells {
      * Changes to the cell structure to add or
      * remove cells.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub structure: Option<NotebookDocumentChangeEventCellsStructure>,
 
     /**
      * Changes to notebook cells properties like its
      * kind, execution summary or metadata.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<Vec<NotebookCell>>,
 
     /**
      * Changes to the text content of notebook cells.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub textContent: Option<Vec<NotebookDocumentChangeEventCellsTextContent>>,
 }
 
@@ -3224,11 +6882,13 @@ pub struct NotebookDocumentChangeEvent {
     /**
      * The changed meta data if any.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<LSPObject>,
 
     /**
      * Changes to cells
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cells: Option<NotebookDocumentChangeEventCells>,
 }
 
@@ -3253,6 +6913,7 @@ pub struct NotebookCellArrayChange {
     /**
      * The new cells, if any
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cells: Option<Vec<NotebookCell>>,
 }
 
@@ -3308,24 +6969,28 @@ pub struct DeclarationClientCapabilities {
      * `true` the client supports the new `DeclarationRegistrationOptions`
      * return value for the corresponding server capability as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
      * The client supports additional metadata in the form of declaration links.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub linkSupport: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DeclarationOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DeclarationRegistrationOptions {
     /// extends DeclarationOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends TextDocumentRegistrationOptions
@@ -3333,13 +6998,14 @@ pub struct DeclarationRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends StaticRegistrationOptions
     /**
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -3361,6 +7027,7 @@ pub struct DeclarationParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -3368,14 +7035,19 @@ pub struct DeclarationParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 }
 
+/// partial result of `textDocument/declaration`
+pub type DeclarationPartialResult = LocationsOrLocationLinks;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DefinitionClientCapabilities {
     /**
      * Whether definition supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
@@ -3383,12 +7055,14 @@ pub struct DefinitionClientCapabilities {
      *
      * @since 3.14.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub linkSupport: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DefinitionOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -3399,10 +7073,11 @@ pub struct DefinitionRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends DefinitionOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -3424,6 +7099,7 @@ pub struct DefinitionParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -3431,9 +7107,13 @@ pub struct DefinitionParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 }
 
+/// partial result of `textDocument/definition`
+pub type DefinitionPartialResult = LocationsOrLocationLinks;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TypeDefinitionClientCapabilities {
     /**
@@ -3441,6 +7121,7 @@ pub struct TypeDefinitionClientCapabilities {
      * `true` the client supports the new `TypeDefinitionRegistrationOptions`
      * return value for the corresponding server capability as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
@@ -3448,26 +7129,29 @@ pub struct TypeDefinitionClientCapabilities {
      *
      * @since 3.14.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub linkSupport: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct TypeDefinitionOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct TypeDefinitionRegistrationOptions {
     /// extends TextDocumentRegistrationOptions
     /**
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends TypeDefinitionOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends StaticRegistrationOptions
@@ -3475,6 +7159,7 @@ pub struct TypeDefinitionRegistrationOptions {
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -3496,6 +7181,7 @@ pub struct TypeDefinitionParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -3503,9 +7189,13 @@ pub struct TypeDefinitionParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 }
 
+/// partial result of `textDocument/typeDefinition`
+pub type TypeDefinitionPartialResult = LocationsOrLocationLinks;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ImplementationClientCapabilities {
     /**
@@ -3513,6 +7203,7 @@ pub struct ImplementationClientCapabilities {
      * `true` the client supports the new `ImplementationRegistrationOptions`
      * return value for the corresponding server capability as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
@@ -3520,26 +7211,29 @@ pub struct ImplementationClientCapabilities {
      *
      * @since 3.14.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub linkSupport: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ImplementationOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ImplementationRegistrationOptions {
     /// extends TextDocumentRegistrationOptions
     /**
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends ImplementationOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends StaticRegistrationOptions
@@ -3547,6 +7241,7 @@ pub struct ImplementationRegistrationOptions {
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -3568,6 +7263,7 @@ pub struct ImplementationParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -3575,20 +7271,26 @@ pub struct ImplementationParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 }
 
+/// partial result of `textDocument/implementation`
+pub type ImplementationPartialResult = LocationsOrLocationLinks;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ReferenceClientCapabilities {
     /**
      * Whether references supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ReferenceOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -3599,10 +7301,11 @@ pub struct ReferenceRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends ReferenceOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -3624,6 +7327,7 @@ pub struct ReferenceParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -3631,11 +7335,31 @@ pub struct ReferenceParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     pub context: ReferenceContext,
 }
 
+impl ReferenceParams {
+    pub fn new(
+        uri: impl Into<DocumentUri>,
+        position: Position,
+        include_declaration: Boolean,
+    ) -> Self {
+        ReferenceParams {
+            textDocument: TextDocumentIdentifier::new(uri),
+            position,
+            workDoneToken: None,
+            partialResultToken: None,
+            context: ReferenceContext::new(include_declaration),
+        }
+    }
+}
+
+/// partial result of `textDocument/references`
+pub type ReferencesPartialResult = Vec<Location>;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ReferenceContext {
     /**
@@ -3644,6 +7368,14 @@ pub struct ReferenceContext {
     pub includeDeclaration: Boolean,
 }
 
+impl ReferenceContext {
+    pub fn new(include_declaration: Boolean) -> Self {
+        ReferenceContext {
+            includeDeclaration: include_declaration,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CallHierarchyClientCapabilities {
     /**
@@ -3652,26 +7384,29 @@ pub struct CallHierarchyClientCapabilities {
      * StaticRegistrationOptions)` return value for the corresponding server
      * capability as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct CallHierarchyOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct CallHierarchyRegistrationOptions {
     /// extends TextDocumentRegistrationOptions
     /**
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends CallHierarchyOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends StaticRegistrationOptions
@@ -3679,6 +7414,7 @@ pub struct CallHierarchyRegistrationOptions {
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -3699,6 +7435,7 @@ pub struct CallHierarchyPrepareParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 }
 
@@ -3717,11 +7454,13 @@ pub struct CallHierarchyItem {
     /**
      * Tags for this item.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<SymbolTag>>,
 
     /**
      * More detail for this item, e.g. the signature of a function.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<String>,
 
     /**
@@ -3746,6 +7485,7 @@ pub struct CallHierarchyItem {
      * A data entry field that is preserved between a call hierarchy prepare and
      * incoming calls or outgoing calls requests.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<LSPAny>,
 }
 
@@ -3755,6 +7495,7 @@ pub struct CallHierarchyIncomingCallsParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -3762,11 +7503,15 @@ pub struct CallHierarchyIncomingCallsParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     pub item: CallHierarchyItem,
 }
 
+/// partial result of `callHierarchy/incomingCalls`
+pub type CallHierarchyIncomingCallsPartialResult = Vec<CallHierarchyIncomingCall>;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CallHierarchyIncomingCall {
     /**
@@ -3787,6 +7532,7 @@ pub struct CallHierarchyOutgoingCallsParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -3794,11 +7540,15 @@ pub struct CallHierarchyOutgoingCallsParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     pub item: CallHierarchyItem,
 }
 
+/// partial result of `callHierarchy/outgoingCalls`
+pub type CallHierarchyOutgoingCallsPartialResult = Vec<CallHierarchyOutgoingCall>;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CallHierarchyOutgoingCall {
     /**
@@ -3821,26 +7571,29 @@ pub struct TypeHierarchyClientCapabilities {
      * StaticRegistrationOptions)` return value for the corresponding server
      * capability as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct TypeHierarchyOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct TypeHierarchyRegistrationOptions {
     /// extends TextDocumentRegistrationOptions
     /**
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends TypeHierarchyOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends StaticRegistrationOptions
@@ -3848,6 +7601,7 @@ pub struct TypeHierarchyRegistrationOptions {
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -3869,6 +7623,7 @@ pub struct TypeHierarchyPrepareParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 }
 
@@ -3887,11 +7642,13 @@ pub struct TypeHierarchyItem {
     /**
      * Tags for this item.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<SymbolTag>>,
 
     /**
      * More detail for this item, e.g. the signature of a function.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<String>,
 
     /**
@@ -3918,6 +7675,7 @@ pub struct TypeHierarchyItem {
      * type hierarchy in the server, helping improve the performance on
      * resolving supertypes and subtypes.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<LSPAny>,
 }
 
@@ -3927,6 +7685,7 @@ pub struct TypeHierarchySupertypesParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -3934,17 +7693,22 @@ pub struct TypeHierarchySupertypesParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     pub item: TypeHierarchyItem,
 }
 
+/// partial result of `typeHierarchy/supertypes`
+pub type TypeHierarchySupertypesPartialResult = Vec<TypeHierarchyItem>;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TypeHierarchySubtypesParams {
     /// extends WorkDoneProgressParams
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -3952,22 +7716,28 @@ pub struct TypeHierarchySubtypesParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     pub item: TypeHierarchyItem,
 }
 
+/// partial result of `typeHierarchy/subtypes`
+pub type TypeHierarchySubtypesPartialResult = Vec<TypeHierarchyItem>;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DocumentHighlightClientCapabilities {
     /**
      * Whether document highlight supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DocumentHighlightOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -3978,10 +7748,11 @@ pub struct DocumentHighlightRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends DocumentHighlightOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -4003,6 +7774,7 @@ pub struct DocumentHighlightParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -4010,9 +7782,13 @@ pub struct DocumentHighlightParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 }
 
+/// partial result of `textDocument/documentHighlight`
+pub type DocumentHighlightPartialResult = Vec<DocumentHighlight>;
+
 /**
  * A document highlight is a range inside a text document which deserves
  * special attention. Usually a document highlight is visualized by changing
@@ -4029,13 +7805,14 @@ pub struct DocumentHighlight {
     /**
      * The highlight kind, default is DocumentHighlightKind.Text.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<DocumentHighlightKind>,
 }
 
 /**
  * A document highlight kind.
  */
-#[derive(Serialize_repr, Deserialize_repr, Debug)]
+#[derive(Serialize_repr, Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum DocumentHighlightKind {
     /**
@@ -4054,11 +7831,32 @@ pub enum DocumentHighlightKind {
     Write = 3,
 }
 
+impl DocumentHighlight {
+    /// a highlight of the default kind, [DocumentHighlightKind::Text]
+    pub fn new(range: Range) -> Self {
+        DocumentHighlight {
+            range,
+            kind: Some(DocumentHighlightKind::Text),
+        }
+    }
+
+    pub fn read(mut self) -> Self {
+        self.kind = Some(DocumentHighlightKind::Read);
+        self
+    }
+
+    pub fn write(mut self) -> Self {
+        self.kind = Some(DocumentHighlightKind::Write);
+        self
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DocumentLinkClientCapabilities {
     /**
      * Whether document link supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
@@ -4066,17 +7864,20 @@ pub struct DocumentLinkClientCapabilities {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tooltipSupport: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DocumentLinkOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
      * Document links have a resolve provider as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveProvider: Option<Boolean>,
 }
 
@@ -4087,15 +7888,17 @@ pub struct DocumentLinkRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends DocumentLinkOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
      * Document links have a resolve provider as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveProvider: Option<Boolean>,
 }
 
@@ -4105,6 +7908,7 @@ pub struct DocumentLinkParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -4112,6 +7916,7 @@ pub struct DocumentLinkParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -4120,6 +7925,9 @@ pub struct DocumentLinkParams {
     pub textDocument: TextDocumentIdentifier,
 }
 
+/// partial result of `textDocument/documentLink`
+pub type DocumentLinkPartialResult = Vec<DocumentLink>;
+
 /**
  * A document link is a range in a text document that links to an internal or
  * external resource, like another text document or a web site.
@@ -4134,6 +7942,7 @@ pub struct DocumentLink {
     /**
      * The uri this link points to. If missing a resolve request is sent later.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub target: Option<URI>,
 
     /**
@@ -4146,20 +7955,50 @@ pub struct DocumentLink {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tooltip: Option<String>,
 
     /**
      * A data entry field that is preserved on a document link between a
      * DocumentLinkRequest and a DocumentLinkResolveRequest.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<LSPAny>,
 }
 
+impl DocumentLink {
+    /// an unresolved link, to be given its target by `documentLink/resolve`
+    pub fn new(range: Range) -> Self {
+        DocumentLink {
+            range,
+            target: None,
+            tooltip: None,
+            data: None,
+        }
+    }
+
+    pub fn with_target(mut self, target: impl Into<URI>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// whether the target is still to be filled in by a resolve request
+    pub fn needs_resolve(&self) -> bool {
+        self.target.is_none()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct HoverClientCapabilities {
     /**
      * Whether hover supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
@@ -4167,12 +8006,14 @@ pub struct HoverClientCapabilities {
      * property refers to a `literal of type MarkupContent`.
      * The order describes the preferred format of the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub contentFormat: Option<Vec<MarkupKind>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct HoverOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -4183,10 +8024,11 @@ pub struct HoverRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends HoverOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -4209,21 +8051,43 @@ pub struct HoverParams2 {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 }
 
 /// extracted from [Hover::contents]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum HoverContents {
     MarkedString(MarkedString),
     MarkedStringArray(Vec<MarkedString>),
     MarkupContent(MarkupContent),
 }
+
+impl HoverContents {
+    /// the contents in the non-deprecated form: marked strings become one
+    /// markdown value, with language strings as fenced code blocks and array
+    /// entries separated by blank lines
+    pub fn into_markup(self) -> MarkupContent {
+        let value = match self {
+            HoverContents::MarkupContent(markup) => return markup,
+            HoverContents::MarkedString(marked) => marked.into_markdown(),
+            HoverContents::MarkedStringArray(marked) => marked
+                .into_iter()
+                .map(MarkedString::into_markdown)
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        };
+        MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        }
+    }
+}
 /**
  * The result of a hover request.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Hover {
     /**
      * The hover's content
@@ -4234,6 +8098,7 @@ pub struct Hover {
      * An optional range is a range inside a text document
      * that is used to visualize a hover, e.g. by changing the background color.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub range: Option<Range>,
 }
 
@@ -4254,29 +8119,46 @@ pub struct Hover {
  * @deprecated use MarkupContent instead.
  */
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum MarkedString {
     String(String),
     LanguageString { language: String, value: String },
 }
 
+impl MarkedString {
+    fn into_markdown(self) -> String {
+        match self {
+            MarkedString::String(markdown) => markdown,
+            MarkedString::LanguageString { language, value } => {
+                // the fence has to be longer than any backtick run in the code
+                let longest_run = value.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+                let fence = "`".repeat(longest_run.max(2) + 1);
+                format!("{fence}{language}\n{value}\n{fence}")
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CodeLensClientCapabilities {
     /**
      * Whether code lens supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct CodeLensOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
      * Code lens has a resolve provider as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveProvider: Option<Boolean>,
 }
 
@@ -4287,16 +8169,18 @@ pub struct CodeLensRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends CodeLensOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends CodeLensOptions
     /**
      * Code lens has a resolve provider as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveProvider: Option<Boolean>,
 }
 
@@ -4306,6 +8190,7 @@ pub struct CodeLensParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -4313,6 +8198,7 @@ pub struct CodeLensParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -4321,6 +8207,9 @@ pub struct CodeLensParams {
     pub textDocument: TextDocumentIdentifier,
 }
 
+/// partial result of `textDocument/codeLens`
+pub type CodeLensPartialResult = Vec<CodeLens>;
+
 /**
  * A code lens represents a command that should be shown along with
  * source text, like the number of references, a way to run tests, etc.
@@ -4329,7 +8218,7 @@ pub struct CodeLensParams {
  * performance reasons the creation of a code lens and resolving should be done
  * in two stages.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct CodeLens {
     /**
      * The range in which this code lens is valid. Should only span a single
@@ -4340,15 +8229,41 @@ pub struct CodeLens {
     /**
      * The command this code lens represents.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub command: Option<Command>,
 
     /**
      * A data entry field that is preserved on a code lens item between
      * a code lens and a code lens resolve request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<LSPAny>,
 }
 
+impl CodeLens {
+    /// an unresolved lens, to be given its command by `codeLens/resolve`
+    pub fn new(range: Range) -> Self {
+        CodeLens {
+            range,
+            command: None,
+            data: None,
+        }
+    }
+
+    pub fn with_data(mut self, data: LSPAny) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// the lens with its command filled in, keeping `range` and `data`
+    pub fn resolve(&self, command: Command) -> Self {
+        CodeLens {
+            command: Some(command),
+            ..self.clone()
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CodeLensWorkspaceClientCapabilities {
     /**
@@ -4360,6 +8275,7 @@ pub struct CodeLensWorkspaceClientCapabilities {
      * useful for situation where a server for example detect a project wide
      * change that requires such a calculation.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub refreshSupport: Option<Boolean>,
 }
 
@@ -4372,6 +8288,7 @@ pub struct FoldingRangeKindStruct {
      * handle values outside its set gracefully and falls back
      * to a default value when unknown.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub valueSet: Option<Vec<FoldingRangeKind>>,
 }
 
@@ -4384,6 +8301,7 @@ pub struct FoldingRangeStruct {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub collapsedText: Option<Boolean>,
 }
 
@@ -4395,6 +8313,7 @@ pub struct FoldingRangeClientCapabilities {
      * `FoldingRangeRegistrationOptions` return value for the corresponding
      * server capability as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
@@ -4402,6 +8321,7 @@ pub struct FoldingRangeClientCapabilities {
      * per document. The value serves as a hint, servers are free to follow the
      * limit.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rangeLimit: Option<UInteger>,
 
     /**
@@ -4409,6 +8329,7 @@ pub struct FoldingRangeClientCapabilities {
      * If set, client will ignore specified `startCharacter` and `endCharacter`
      * properties in a FoldingRange.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub lineFoldingOnly: Option<Boolean>,
 
     /**
@@ -4416,32 +8337,36 @@ pub struct FoldingRangeClientCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub foldingRangeKind: Option<FoldingRangeKindStruct>,
 
     /**
      * Specific options for the folding range.
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub foldingRange: Option<FoldingRangeStruct>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct FoldingRangeOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct FoldingRangeRegistrationOptions {
     /// extends TextDocumentRegistrationOptions
     /**
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends FoldingRangeOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends StaticRegistrationOptions
@@ -4449,6 +8374,7 @@ pub struct FoldingRangeRegistrationOptions {
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -4458,6 +8384,7 @@ pub struct FoldingRangeParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -4465,6 +8392,7 @@ pub struct FoldingRangeParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -4473,13 +8401,16 @@ pub struct FoldingRangeParams {
     pub textDocument: TextDocumentIdentifier,
 }
 
+/// partial result of `textDocument/foldingRange`
+pub type FoldingRangePartialResult = Vec<FoldingRange>;
+
 /**
  * A set of predefined range kinds.
  */
 /**
  * The type is a String since the value set is extensible
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum FoldingRangeKind {
     /**
      * Folding range for a comment
@@ -4498,6 +8429,10 @@ pub enum FoldingRangeKind {
      */
     #[serde(rename = "region")]
     Region,
+
+    /// the set of kinds is open, so anything not predefined above ends up here
+    #[serde(untagged)]
+    Other(String),
 }
 
 /**
@@ -4518,6 +8453,7 @@ pub struct FoldingRange {
      * The zero-based character offset from where the folded range starts. If
      * not defined, defaults to the length of the start line.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub startCharacter: Option<UInteger>,
 
     /**
@@ -4531,6 +8467,7 @@ pub struct FoldingRange {
      * The zero-based character offset before the folded range ends. If not
      * defined, defaults to the length of the end line.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub endCharacter: Option<UInteger>,
 
     /**
@@ -4539,6 +8476,7 @@ pub struct FoldingRange {
      * 'Fold all comments'. See [FoldingRangeKind](#FoldingRangeKind) for an
      * enumeration of standardized kinds.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<FoldingRangeKind>,
 
     /**
@@ -4548,9 +8486,41 @@ pub struct FoldingRange {
      *
      * @since 3.17.0 - proposed
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub collapsedText: Option<String>,
 }
 
+impl FoldingRange {
+    /// a fold over whole lines, leaving the character offsets to the client
+    pub fn lines(start_line: UInteger, end_line: UInteger) -> Self {
+        FoldingRange {
+            startLine: start_line,
+            startCharacter: None,
+            endLine: end_line,
+            endCharacter: None,
+            kind: None,
+            collapsedText: None,
+        }
+    }
+
+    pub fn with_kind(mut self, kind: FoldingRangeKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// whether the range is of `kind`, e.g. to pick the ranges for a
+    /// "fold all comments" command
+    pub fn is_kind(&self, kind: FoldingRangeKind) -> bool {
+        self.kind.as_ref() == Some(&kind)
+    }
+
+    /// whether both lines lie inside a document of `num_lines` lines and the
+    /// range doesn't end before it starts
+    pub fn validate(&self, num_lines: UInteger) -> bool {
+        self.startLine <= self.endLine && self.endLine < num_lines
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SelectionRangeClientCapabilities {
     /**
@@ -4559,19 +8529,22 @@ pub struct SelectionRangeClientCapabilities {
      * `SelectionRangeRegistrationOptions` return value for the corresponding
      * server capability as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct SelectionRangeOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct SelectionRangeRegistrationOptions {
     /// extends SelectionRangeOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends TextDocumentRegistrationOptions
@@ -4579,13 +8552,14 @@ pub struct SelectionRangeRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends StaticRegistrationOptions
     /**
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -4595,6 +8569,7 @@ pub struct SelectionRangeParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -4602,6 +8577,7 @@ pub struct SelectionRangeParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -4615,6 +8591,9 @@ pub struct SelectionRangeParams {
     pub positions: Vec<Position>,
 }
 
+/// partial result of `textDocument/selectionRange`
+pub type SelectionRangePartialResult = Vec<SelectionRange>;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SelectionRange {
     /**
@@ -4626,6 +8605,7 @@ pub struct SelectionRange {
      * `parent.range` must contain `this.range`.
      */
     // parent: Option<SelectionRange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub parent: Option<Box<SelectionRange>>,
 }
 
@@ -4642,6 +8622,7 @@ pub struct SymbolKindStruct {
      * the symbol kinds from `File` to `Array` as defined in
      * the initial version of the protocol.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub valueSet: Option<Vec<SymbolKind>>,
 }
 
@@ -4659,17 +8640,20 @@ pub struct DocumentSymbolClientCapabilities {
     /**
      * Whether document symbol supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
      * Specific capabilities for the `SymbolKind` in the
      * `textDocument/documentSymbol` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub symbolKind: Option<SymbolKindStruct>,
 
     /**
      * The client supports hierarchical document symbols.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub hierarchicalDocumentSymbolSupport: Option<Boolean>,
 
     /**
@@ -4679,6 +8663,7 @@ pub struct DocumentSymbolClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tagSupport: Option<TagSupportStruct>,
 
     /**
@@ -4687,12 +8672,14 @@ pub struct DocumentSymbolClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub labelSupport: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DocumentSymbolOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
@@ -4701,6 +8688,7 @@ pub struct DocumentSymbolOptions {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
 }
 
@@ -4711,10 +8699,11 @@ pub struct DocumentSymbolRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends DocumentSymbolOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends DocumentSymbolOptions
@@ -4724,6 +8713,7 @@ pub struct DocumentSymbolRegistrationOptions {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
 }
 
@@ -4733,6 +8723,7 @@ pub struct DocumentSymbolParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -4740,6 +8731,7 @@ pub struct DocumentSymbolParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -4748,46 +8740,151 @@ pub struct DocumentSymbolParams {
     pub textDocument: TextDocumentIdentifier,
 }
 
-/**
- * A symbol kind.
- */
-#[derive(Serialize_repr, Deserialize_repr, Debug)]
-#[repr(u8)]
-pub enum SymbolKind {
-    File = 1,
-    Module = 2,
-    Namespace = 3,
-    Package = 4,
-    Class = 5,
-    Method = 6,
-    Property = 7,
-    Field = 8,
-    Constructor = 9,
-    Enum = 10,
-    Interface = 11,
-    Function = 12,
-    Variable = 13,
-    Constant = 14,
-    String = 15,
-    Number = 16,
-    Boolean = 17,
-    Array = 18,
-    Object = 19,
-    Key = 20,
-    Null = 21,
-    EnumMember = 22,
-    Struct = 23,
-    Event = 24,
-    Operator = 25,
-    TypeParameter = 26,
+/// partial result of `textDocument/documentSymbol`
+pub type DocumentSymbolPartialResult = DocumentSymbolsOrSymbolInformations;
+
+open_repr_enum! {
+    /**
+     * A symbol kind.
+     */
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SymbolKind {
+        File = 1,
+        Module = 2,
+        Namespace = 3,
+        Package = 4,
+        Class = 5,
+        Method = 6,
+        Property = 7,
+        Field = 8,
+        Constructor = 9,
+        Enum = 10,
+        Interface = 11,
+        Function = 12,
+        Variable = 13,
+        Constant = 14,
+        String = 15,
+        Number = 16,
+        Boolean = 17,
+        Array = 18,
+        Object = 19,
+        Key = 20,
+        Null = 21,
+        EnumMember = 22,
+        Struct = 23,
+        Event = 24,
+        Operator = 25,
+        TypeParameter = 26,
+    }
+}
+
+impl SymbolKind {
+    /// every kind, e.g. for [SymbolKindStruct::valueSet]
+    pub fn all() -> &'static [SymbolKind] {
+        &[
+            SymbolKind::File,
+            SymbolKind::Module,
+            SymbolKind::Namespace,
+            SymbolKind::Package,
+            SymbolKind::Class,
+            SymbolKind::Method,
+            SymbolKind::Property,
+            SymbolKind::Field,
+            SymbolKind::Constructor,
+            SymbolKind::Enum,
+            SymbolKind::Interface,
+            SymbolKind::Function,
+            SymbolKind::Variable,
+            SymbolKind::Constant,
+            SymbolKind::String,
+            SymbolKind::Number,
+            SymbolKind::Boolean,
+            SymbolKind::Array,
+            SymbolKind::Object,
+            SymbolKind::Key,
+            SymbolKind::Null,
+            SymbolKind::EnumMember,
+            SymbolKind::Struct,
+            SymbolKind::Event,
+            SymbolKind::Operator,
+            SymbolKind::TypeParameter,
+        ]
+    }
+
+    /// the completion kind for completing this symbol. Kinds without a
+    /// counterpart map to the closest one: namespaces and packages to
+    /// modules, keys to properties, and the literal kinds (strings, numbers,
+    /// booleans, arrays, objects and null) to values. An unknown kind
+    /// completes as text.
+    pub fn to_completion_kind(&self) -> CompletionItemKind {
+        match self {
+            SymbolKind::File => CompletionItemKind::File,
+            SymbolKind::Module | SymbolKind::Namespace | SymbolKind::Package => {
+                CompletionItemKind::Module
+            }
+            SymbolKind::Class => CompletionItemKind::Class,
+            SymbolKind::Method => CompletionItemKind::Method,
+            SymbolKind::Property | SymbolKind::Key => CompletionItemKind::Property,
+            SymbolKind::Field => CompletionItemKind::Field,
+            SymbolKind::Constructor => CompletionItemKind::Constructor,
+            SymbolKind::Enum => CompletionItemKind::Enum,
+            SymbolKind::Interface => CompletionItemKind::Interface,
+            SymbolKind::Function => CompletionItemKind::Function,
+            SymbolKind::Variable => CompletionItemKind::Variable,
+            SymbolKind::Constant => CompletionItemKind::Constant,
+            SymbolKind::String
+            | SymbolKind::Number
+            | SymbolKind::Boolean
+            | SymbolKind::Array
+            | SymbolKind::Object
+            | SymbolKind::Null => CompletionItemKind::Value,
+            SymbolKind::EnumMember => CompletionItemKind::EnumMember,
+            SymbolKind::Struct => CompletionItemKind::Struct,
+            SymbolKind::Event => CompletionItemKind::Event,
+            SymbolKind::Operator => CompletionItemKind::Operator,
+            SymbolKind::TypeParameter => CompletionItemKind::TypeParameter,
+            SymbolKind::Unknown(_) => CompletionItemKind::Text,
+        }
+    }
 }
 
+impl_display_from_str!(
+    SymbolKind {
+        File,
+        Module,
+        Namespace,
+        Package,
+        Class,
+        Method,
+        Property,
+        Field,
+        Constructor,
+        Enum,
+        Interface,
+        Function,
+        Variable,
+        Constant,
+        String,
+        Number,
+        Boolean,
+        Array,
+        Object,
+        Key,
+        Null,
+        EnumMember,
+        Struct,
+        Event,
+        Operator,
+        TypeParameter,
+    } | Unknown
+);
+
 /**
  * Symbol tags are extra annotations that tweak the rendering of a symbol.
  *
  * @since 3.16
  */
-#[derive(Serialize_repr, Deserialize_repr, Debug)]
+#[derive(Serialize_repr, Deserialize_repr, Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum SymbolTag {
     /**
@@ -4802,7 +8899,7 @@ pub enum SymbolTag {
  * have two ranges: one that encloses its definition and one that points to its
  * most interesting range, e.g. the range of an identifier.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DocumentSymbol {
     /**
      * The name of this symbol. Will be displayed in the user struct and
@@ -4814,6 +8911,7 @@ pub struct DocumentSymbol {
     /**
      * More detail for this symbol, e.g the signature of a function.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<String>,
 
     /**
@@ -4826,6 +8924,7 @@ pub struct DocumentSymbol {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<SymbolTag>>,
 
     /**
@@ -4833,6 +8932,7 @@ pub struct DocumentSymbol {
      *
      * @deprecated Use tags instead
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<Boolean>,
 
     /**
@@ -4852,9 +8952,56 @@ pub struct DocumentSymbol {
     /**
      * Children of this symbol, e.g. properties of a class.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub children: Option<Vec<DocumentSymbol>>,
 }
 
+impl DocumentSymbol {
+    /// a best-effort hierarchy rebuilt from flat symbols of one document:
+    /// symbols whose range lies within another's become its children. The
+    /// selection range is unknown and taken to be the whole range.
+    pub fn from_symbol_informations(mut symbols: Vec<SymbolInformation>) -> Vec<DocumentSymbol> {
+        // parents first: by start, and the longer of two ranges starting together
+        symbols.sort_by(|a, b| {
+            let (a, b) = (&a.location.range, &b.location.range);
+            a.start.cmp(&b.start).then(b.end.cmp(&a.end))
+        });
+        let mut roots = Vec::new();
+        // the chain of symbols enclosing the current one, outermost first
+        let mut open: Vec<DocumentSymbol> = Vec::new();
+        let close = |open: &mut Vec<DocumentSymbol>, roots: &mut Vec<DocumentSymbol>| {
+            if let Some(symbol) = open.pop() {
+                match open.last_mut() {
+                    Some(parent) => parent.children.get_or_insert_with(Vec::new).push(symbol),
+                    None => roots.push(symbol),
+                }
+            }
+        };
+        for symbol in symbols {
+            let range = symbol.location.range;
+            while open.last().is_some_and(|parent| {
+                range.start < parent.range.start || parent.range.end < range.end
+            }) {
+                close(&mut open, &mut roots);
+            }
+            open.push(DocumentSymbol {
+                name: symbol.name,
+                detail: None,
+                kind: symbol.kind,
+                tags: symbol.tags,
+                deprecated: symbol.deprecated,
+                range,
+                selectionRange: range,
+                children: None,
+            });
+        }
+        while !open.is_empty() {
+            close(&mut open, &mut roots);
+        }
+        roots
+    }
+}
+
 /**
  * Represents information about programming constructs like variables, classes;
  * interfaces etc.
@@ -4878,6 +9025,7 @@ pub struct SymbolInformation {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<SymbolTag>>,
 
     /**
@@ -4885,6 +9033,7 @@ pub struct SymbolInformation {
      *
      * @deprecated Use tags instead
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<Boolean>,
 
     /**
@@ -4906,10 +9055,51 @@ pub struct SymbolInformation {
      * if necessary). It can't be used to re-infer a hierarchy for the document
      * symbols.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub containerName: Option<String>,
 }
 
+impl SymbolInformation {
+    /// `symbol` and all its descendants as flat symbols in `uri`, parents
+    /// before their children, each naming its parent as `containerName`
+    pub fn from_document_symbol(
+        symbol: &DocumentSymbol,
+        uri: impl Into<DocumentUri>,
+    ) -> Vec<SymbolInformation> {
+        fn flatten(
+            symbol: &DocumentSymbol,
+            uri: &DocumentUri,
+            container: Option<&str>,
+            symbols: &mut Vec<SymbolInformation>,
+        ) {
+            symbols.push(SymbolInformation {
+                name: symbol.name.clone(),
+                kind: symbol.kind,
+                tags: symbol.tags.clone(),
+                deprecated: symbol.deprecated,
+                location: Location::new(uri.clone(), symbol.range),
+                containerName: container.map(str::to_owned),
+            });
+            for child in symbol.children.iter().flatten() {
+                flatten(child, uri, Some(&symbol.name), symbols);
+            }
+        }
+
+        let mut symbols = Vec::new();
+        flatten(symbol, &uri.into(), None, &mut symbols);
+        symbols
+    }
+}
+
+/// `DocumentSymbol[] | SymbolInformation[]`
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum DocumentSymbolsOrSymbolInformations {
+    DocumentSymbols(Vec<DocumentSymbol>),
+    SymbolInformations(Vec<SymbolInformation>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SemanticTokenTypes {
     #[serde(rename = "namespace")]
     Namespace,
@@ -4951,7 +9141,7 @@ pub enum SemanticTokenTypes {
     Modifier,
     #[serde(rename = "comment")]
     Comment,
-    #[serde(rename = "String")]
+    #[serde(rename = "string")]
     String,
     #[serde(rename = "number")]
     Number,
@@ -4966,7 +9156,33 @@ pub enum SemanticTokenTypes {
     Decorator,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl_display_from_str!(SemanticTokenTypes {
+    Namespace => "namespace",
+    Type => "type",
+    Class => "class",
+    Enum => "enum",
+    Interface => "interface",
+    Struct => "struct",
+    TypeParameter => "typeParameter",
+    Parameter => "parameter",
+    Variable => "variable",
+    Property => "property",
+    EnumMember => "enumMember",
+    Event => "event",
+    Function => "function",
+    Method => "method",
+    Macro => "macro",
+    Keyword => "keyword",
+    Modifier => "modifier",
+    Comment => "comment",
+    String => "string",
+    Number => "number",
+    Regexp => "regexp",
+    Operator => "operator",
+    Decorator => "decorator",
+});
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SemanticTokenModifiers {
     #[serde(rename = "declaration")]
     Declaration,
@@ -4990,13 +9206,26 @@ pub enum SemanticTokenModifiers {
     DefaultLibrary,
 }
 
+impl_display_from_str!(SemanticTokenModifiers {
+    Declaration => "declaration",
+    Definition => "definition",
+    Readonly => "readonly",
+    Static => "static",
+    Deprecated => "deprecated",
+    Abstract => "abstract",
+    Async => "async",
+    Modification => "modification",
+    Documentation => "documentation",
+    DefaultLibrary => "defaultLibrary",
+});
+
 #[derive(Serialize, Deserialize, Debug)]
 pub enum TokenFormat {
     #[serde(rename = "relative")]
     Relative,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct SemanticTokensLegend {
     /**
      * The token types a server uses.
@@ -5009,6 +9238,32 @@ pub struct SemanticTokensLegend {
     pub tokenModifiers: Vec<String>,
 }
 
+impl SemanticTokensLegend {
+    pub fn new(
+        token_types: Vec<SemanticTokenTypes>,
+        token_modifiers: Vec<SemanticTokenModifiers>,
+    ) -> Self {
+        SemanticTokensLegend {
+            tokenTypes: token_types.iter().map(ToString::to_string).collect(),
+            tokenModifiers: token_modifiers.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    /// the index to encode `token_type` with, if the legend has it
+    pub fn type_index(&self, token_type: SemanticTokenTypes) -> Option<UInteger> {
+        let name = token_type.to_string();
+        let index = self.tokenTypes.iter().position(|t| *t == name)?;
+        Some(index as UInteger)
+    }
+
+    /// the bit for `modifier` in a token's modifier set, if the legend has it
+    pub fn modifier_bit(&self, modifier: SemanticTokenModifiers) -> Option<UInteger> {
+        let name = modifier.to_string();
+        let index = self.tokenModifiers.iter().position(|m| *m == name)?;
+        UInteger::checked_shl(1, index as u32)
+    }
+}
+
 /// extracted from [SemanticTokensClientCapabilitiesRequests::full]
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(untagged)]
@@ -5019,7 +9274,7 @@ pub enum SemanticTokensClientCapabilitiesRequestsFull {
          * The client will send the `textDocument/semanticTokens/full/delta`
          * request if the server provides a corresponding handler.
          */
-        pub delta: Option<Boolean>,
+        delta: Option<Boolean>,
     },
 }
 
@@ -5030,6 +9285,7 @@ pub struct SemanticTokensClientCapabilitiesRequests {
      * The client will send the `textDocument/semanticTokens/range` request
      * if the server provides a corresponding handler.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub range: Option<Boolean>,
 
     /**
@@ -5047,6 +9303,7 @@ pub struct SemanticTokensClientCapabilities {
      * StaticRegistrationOptions)` return value for the corresponding server
      * capability as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
@@ -5079,11 +9336,13 @@ pub struct SemanticTokensClientCapabilities {
     /**
      * Whether the client supports tokens that can overlap each other.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub overlappingTokenSupport: Option<Boolean>,
 
     /**
      * Whether the client supports tokens that can span multiple lines.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub multilineTokenSupport: Option<Boolean>,
 
     /**
@@ -5094,6 +9353,7 @@ pub struct SemanticTokensClientCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub serverCancelSupport: Option<Boolean>,
 
     /**
@@ -5108,25 +9368,29 @@ pub struct SemanticTokensClientCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub augmentsSyntaxTokens: Option<Boolean>,
 }
 
 /// extended from [SemanticTokensOptions::full]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct SemanticTokensOptionsFullDelta {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub delta: Option<Boolean>,
 }
 
 /// extended from [SemanticTokensOptions::full]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
 pub enum SemanticTokensOptionsFull {
     Boolean(Boolean),
     SemanticTokensOptionsFullDelta(SemanticTokensOptionsFullDelta),
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct SemanticTokensOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
@@ -5139,25 +9403,28 @@ pub struct SemanticTokensOptions {
      * of a document.
      */
     /// idk why the docs say `range?: boolean | { };`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub range: Option<Boolean>,
 
     /**
      * Server supports providing semantic tokens for a full document.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub full: Option<SemanticTokensOptionsFull>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct SemanticTokensRegistrationOptions {
     /// extends TextDocumentRegistrationOptions
     /**
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends SemanticTokensOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends SemanticTokensOptions
@@ -5173,12 +9440,14 @@ pub struct SemanticTokensRegistrationOptions {
      * of a document.
      */
     /// idk why the docs say `range?: boolean | { };`
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub range: Option<Boolean>,
 
     /// extends SemanticTokensOptions
     /**
      * Server supports providing semantic tokens for a full document.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub full: Option<SemanticTokensOptionsFull>,
 
     /// extends StaticRegistrationOptions
@@ -5186,6 +9455,7 @@ pub struct SemanticTokensRegistrationOptions {
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -5195,6 +9465,7 @@ pub struct SemanticTokensParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -5202,6 +9473,7 @@ pub struct SemanticTokensParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -5218,6 +9490,7 @@ pub struct SemanticTokens {
      * A server can then instead of computing all semantic tokens again simply
      * send a delta.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resultId: Option<String>,
 
     /**
@@ -5226,6 +9499,128 @@ pub struct SemanticTokens {
     pub data: Vec<UInteger>,
 }
 
+impl SemanticTokens {
+    /// checks the encoded tokens against `text`, the document they were
+    /// computed for, and the client's
+    /// [SemanticTokensClientCapabilities::multilineTokenSupport] and
+    /// [SemanticTokensClientCapabilities::overlappingTokenSupport]. Token
+    /// lengths are counted in `encoding`; whether a token spills onto the
+    /// next line can't be told from `data` alone, hence the text.
+    pub fn validate(
+        &self,
+        text: &str,
+        encoding: PositionEncodingKind,
+        multiline_allowed: bool,
+        overlap_allowed: bool,
+    ) -> Result<(), TokenError> {
+        if self.data.len() % 5 != 0 {
+            return Err(TokenError::IncompleteToken);
+        }
+        let lines: Vec<&str> = lines_with_breaks(text).collect();
+        // a line's length without and with its line break
+        let line_len = |line: usize| {
+            lines.get(line).map(|text| {
+                let content = without_line_break(text);
+                (encoded_len(content, encoding), encoded_len(text, encoding))
+            })
+        };
+
+        let mut start = Position {
+            line: 0,
+            character: 0,
+        };
+        let mut furthest_end = start;
+        for (token, encoded) in self.data.chunks(5).enumerate() {
+            let (delta_line, delta_start, length) = (encoded[0], encoded[1], encoded[2]);
+            start = match delta_line {
+                0 => start
+                    .character
+                    .checked_add(delta_start)
+                    .map(|character| Position {
+                        line: start.line,
+                        character,
+                    }),
+                _ => start.line.checked_add(delta_line).map(|line| Position {
+                    line,
+                    character: delta_start,
+                }),
+            }
+            .ok_or(TokenError::OutOfBounds { token })?;
+
+            let out_of_bounds = TokenError::OutOfBounds { token };
+            let (content_len, full_len) = line_len(start.line as usize).ok_or(out_of_bounds)?;
+            let token_end = start.character.checked_add(length).ok_or(out_of_bounds)?;
+            if start.character > content_len {
+                return Err(out_of_bounds);
+            }
+            let end = if token_end <= content_len {
+                Position {
+                    line: start.line,
+                    character: token_end,
+                }
+            } else if !multiline_allowed {
+                return Err(TokenError::Multiline { token });
+            } else {
+                // carry what's left of the token over the following lines
+                let mut line = start.line;
+                let mut remaining = token_end.saturating_sub(full_len);
+                loop {
+                    line += 1;
+                    let (content_len, full_len) = line_len(line as usize).ok_or(out_of_bounds)?;
+                    if remaining <= content_len {
+                        break Position {
+                            line,
+                            character: remaining,
+                        };
+                    }
+                    remaining = remaining.saturating_sub(full_len);
+                }
+            };
+
+            if !overlap_allowed && start < furthest_end {
+                return Err(TokenError::Overlapping { token });
+            }
+            furthest_end = furthest_end.max(end);
+        }
+        Ok(())
+    }
+}
+
+/// why [SemanticTokens::validate] rejected a set of tokens; `token` is the
+/// index of the offending token, i.e. its offset in `data` divided by 5
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenError {
+    /// `data` doesn't hold a whole number of 5-integer tokens
+    IncompleteToken,
+    /// the token starts or ends outside the document
+    OutOfBounds { token: usize },
+    /// the token runs past the end of its line but the client doesn't
+    /// support multiline tokens
+    Multiline { token: usize },
+    /// the token starts inside an earlier one but the client doesn't support
+    /// overlapping tokens
+    Overlapping { token: usize },
+}
+
+impl std::fmt::Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenError::IncompleteToken => write!(f, "semantic token data is not a multiple of 5"),
+            TokenError::OutOfBounds { token } => {
+                write!(f, "semantic token {token} is outside the document")
+            }
+            TokenError::Multiline { token } => {
+                write!(f, "semantic token {token} spans multiple lines")
+            }
+            TokenError::Overlapping { token } => {
+                write!(f, "semantic token {token} overlaps an earlier token")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SemanticTokensPartialResult {
     pub data: Vec<UInteger>,
@@ -5237,6 +9632,7 @@ pub struct SemanticTokensDeltaParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -5244,6 +9640,7 @@ pub struct SemanticTokensDeltaParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -5261,6 +9658,7 @@ pub struct SemanticTokensDeltaParams {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SemanticTokensDelta {
     /// readonly
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resultId: Option<String>,
     /**
      * The semantic token edits to transform a previous result into a new
@@ -5284,9 +9682,46 @@ pub struct SemanticTokensEdit {
     /**
      * The elements to insert.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<Vec<UInteger>>,
 }
 
+/// the delta turning the flat token array `prev` into `next`: a single edit
+/// replacing whatever lies between their common prefix and common suffix,
+/// or no edits if the arrays are equal
+pub fn compute_semantic_tokens_delta(
+    prev: &[UInteger],
+    next: &[UInteger],
+    result_id: String,
+) -> SemanticTokensDelta {
+    let prefix = prev
+        .iter()
+        .zip(next)
+        .take_while(|(prev, next)| prev == next)
+        .count();
+    let suffix = prev[prefix..]
+        .iter()
+        .rev()
+        .zip(next[prefix..].iter().rev())
+        .take_while(|(prev, next)| prev == next)
+        .count();
+    let deleted = prev.len() - prefix - suffix;
+    let inserted = &next[prefix..next.len() - suffix];
+    let edits = if deleted == 0 && inserted.is_empty() {
+        Vec::new()
+    } else {
+        vec![SemanticTokensEdit {
+            start: prefix as UInteger,
+            deleteCount: deleted as UInteger,
+            data: (!inserted.is_empty()).then(|| inserted.to_vec()),
+        }]
+    };
+    SemanticTokensDelta {
+        resultId: Some(result_id),
+        edits,
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SemanticTokensDeltaPartialResult {
     pub edits: Vec<SemanticTokensEdit>,
@@ -5298,6 +9733,7 @@ pub struct SemanticTokensRangeParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -5305,6 +9741,7 @@ pub struct SemanticTokensRangeParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -5329,6 +9766,7 @@ pub struct SemanticTokensWorkspaceClientCapabilities {
      * and is useful for situation where a server for example detect a project
      * wide change that requires such a calculation.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub refreshSupport: Option<Boolean>,
 }
 
@@ -5351,12 +9789,14 @@ pub struct InlayHintClientCapabilities {
     /**
      * Whether inlay hints support dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
      * Indicates which properties a client can resolve lazily on an inlay
      * hint.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveSupport: Option<InlayHintClientCapabilitiesResolveSupport>,
 }
 
@@ -5365,15 +9805,17 @@ pub struct InlayHintClientCapabilities {
  *
  * @since 3.17.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct InlayHintOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
      * The server provides support to resolve additional
      * information for an inlay hint item.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveProvider: Option<Boolean>,
 }
 
@@ -5382,10 +9824,11 @@ pub struct InlayHintOptions {
  *
  * @since 3.17.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct InlayHintRegistrationOptions {
     /// extends InlayHintOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends InlayHintOptions
@@ -5393,6 +9836,7 @@ pub struct InlayHintRegistrationOptions {
      * The server provides support to resolve additional
      * information for an inlay hint item.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveProvider: Option<Boolean>,
 
     /// extends TextDocumentRegistrationOptions
@@ -5400,13 +9844,14 @@ pub struct InlayHintRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends StaticRegistrationOptions
     /**
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -5421,6 +9866,7 @@ pub struct InlayHintParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /**
@@ -5442,6 +9888,24 @@ pub enum InlayHintLabel {
     InlayHintLabelPartArray(Vec<InlayHintLabelPart>),
 }
 
+impl From<String> for InlayHintLabel {
+    fn from(string: String) -> Self {
+        InlayHintLabel::String(string)
+    }
+}
+
+impl From<&str> for InlayHintLabel {
+    fn from(string: &str) -> Self {
+        InlayHintLabel::String(string.to_owned())
+    }
+}
+
+impl From<Vec<InlayHintLabelPart>> for InlayHintLabel {
+    fn from(parts: Vec<InlayHintLabelPart>) -> Self {
+        InlayHintLabel::InlayHintLabelPartArray(parts)
+    }
+}
+
 /**
  * Inlay hint information.
  *
@@ -5469,6 +9933,7 @@ pub struct InlayHint {
      * The kind of this hint. Can be omitted in which case the client
      * should fall back to a reasonable default.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<InlayHintKind>,
 
     /**
@@ -5481,6 +9946,7 @@ pub struct InlayHint {
      * Depending on the client capability `inlayHint.resolveSupport` clients
      * might resolve this property late using the resolve request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub textEdits: Option<Vec<TextEdit>>,
 
     /**
@@ -5489,6 +9955,7 @@ pub struct InlayHint {
      * Depending on the client capability `inlayHint.resolveSupport` clients
      * might resolve this property late using the resolve request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tooltip: Option<MarkupContentOrString>,
 
     /**
@@ -5498,6 +9965,7 @@ pub struct InlayHint {
      * background color of the hint itself. That means padding can be used
      * to visually align/separate an inlay hint.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub paddingLeft: Option<Boolean>,
 
     /**
@@ -5507,14 +9975,64 @@ pub struct InlayHint {
      * background color of the hint itself. That means padding can be used
      * to visually align/separate an inlay hint.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub paddingRight: Option<Boolean>,
     /**
      * A data entry field that is preserved on an inlay hint between
      * a `textDocument/inlayHint` and a `inlayHint/resolve` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<LSPAny>,
 }
 
+impl InlayHint {
+    /// whether any property of this hint can be resolved later, see
+    /// [InlayHint::resolvable_properties]
+    pub fn can_resolve(&self, caps: &InlayHintClientCapabilities) -> bool {
+        !self.resolvable_properties(caps).is_empty()
+    }
+
+    /// the properties (e.g. `"tooltip"` or `"label.location"`) the client
+    /// listed in [InlayHintClientCapabilities::resolveSupport] that this hint
+    /// leaves unset, i.e. whose computing can be put off until the client
+    /// sends `inlayHint/resolve` for this hint. The resolve request carries
+    /// the hint back, including `data`, and its result is the same hint with
+    /// the properties filled in.
+    ///
+    /// A listed name that isn't a property of [InlayHint] is left out, as
+    /// there's nothing on the hint it could fill in.
+    pub fn resolvable_properties<'a>(&self, caps: &'a InlayHintClientCapabilities) -> Vec<&'a str> {
+        let Some(support) = &caps.resolveSupport else {
+            return Vec::new();
+        };
+        support
+            .properties
+            .iter()
+            .map(String::as_str)
+            .filter(|property| self.is_unset(property) == Some(true))
+            .collect()
+    }
+
+    /// whether `property` is unset, `None` if the hint has no such property
+    fn is_unset(&self, property: &str) -> Option<bool> {
+        let part_unset = |unset: fn(&InlayHintLabelPart) -> bool| match &self.label {
+            InlayHintLabel::String(_) => false,
+            InlayHintLabel::InlayHintLabelPartArray(parts) => parts.iter().any(unset),
+        };
+        Some(match property {
+            "kind" => self.kind.is_none(),
+            "textEdits" => self.textEdits.is_none(),
+            "tooltip" => self.tooltip.is_none(),
+            "paddingLeft" => self.paddingLeft.is_none(),
+            "paddingRight" => self.paddingRight.is_none(),
+            "label.tooltip" => part_unset(|part| part.tooltip.is_none()),
+            "label.location" => part_unset(|part| part.location.is_none()),
+            "label.command" => part_unset(|part| part.command.is_none()),
+            _ => return None,
+        })
+    }
+}
+
 /**
  * An inlay hint label part allows for interactive and composite labels
  * of inlay hints.
@@ -5533,6 +10051,7 @@ pub struct InlayHintLabelPart {
      * the client capability `inlayHint.resolveSupport` clients might resolve
      * this property late using the resolve request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tooltip: Option<MarkupContentOrString>,
 
     /**
@@ -5548,6 +10067,7 @@ pub struct InlayHintLabelPart {
      * Depending on the client capability `inlayHint.resolveSupport` clients
      * might resolve this property late using the resolve request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<Location>,
 
     /**
@@ -5556,9 +10076,38 @@ pub struct InlayHintLabelPart {
      * Depending on the client capability `inlayHint.resolveSupport` clients
      * might resolve this property late using the resolve request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub command: Option<Command>,
 }
 
+impl InlayHintLabelPart {
+    pub fn new(value: impl Into<String>) -> Self {
+        InlayHintLabelPart {
+            value: value.into(),
+            tooltip: None,
+            location: None,
+            command: None,
+        }
+    }
+
+    pub fn with_tooltip(mut self, tooltip: MarkupContentOrString) -> Self {
+        self.tooltip = Some(tooltip);
+        self
+    }
+
+    /// makes the part clickable: the client jumps to `location` and may
+    /// show its hover or a context menu for it
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    pub fn with_command(mut self, command: Command) -> Self {
+        self.command = Some(command);
+        self
+    }
+}
+
 /**
  * Inlay hint kinds.
  *
@@ -5594,6 +10143,7 @@ pub struct InlayHintWorkspaceClientCapabilities {
      * is useful for situation where a server for example detects a project wide
      * change that requires such a calculation.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub refreshSupport: Option<Boolean>,
 }
 
@@ -5608,6 +10158,7 @@ pub struct InlineValueClientCapabilities {
      * Whether implementation supports dynamic registration for inline
      * value providers.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
@@ -5616,9 +10167,10 @@ pub struct InlineValueClientCapabilities {
  *
  * @since 3.17.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct InlineValueOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -5627,10 +10179,11 @@ pub struct InlineValueOptions {
  *
  * @since 3.17.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct InlineValueRegistrationOptions {
     /// extends InlineValueOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends TextDocumentRegistrationOptions
@@ -5638,13 +10191,14 @@ pub struct InlineValueRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends StaticRegistrationOptions
     /**
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -5659,6 +10213,7 @@ pub struct InlineValueParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /**
@@ -5678,6 +10233,9 @@ pub struct InlineValueParams {
     pub context: InlineValueContext,
 }
 
+/// partial result of `textDocument/inlineValue`
+pub type InlineValuePartialResult = Vec<InlineValue>;
+
 /**
  * @since 3.17.0
  */
@@ -5696,6 +10254,15 @@ pub struct InlineValueContext {
     pub stoppedLocation: Range,
 }
 
+impl InlineValueContext {
+    pub fn new(frame_id: Integer, stopped_location: Range) -> Self {
+        InlineValueContext {
+            frameId: frame_id,
+            stoppedLocation: stopped_location,
+        }
+    }
+}
+
 /**
  * Provide inline value as text.
  *
@@ -5714,6 +10281,15 @@ pub struct InlineValueText {
     pub text: String,
 }
 
+impl InlineValueText {
+    pub fn new(range: Range, text: impl Into<String>) -> Self {
+        InlineValueText {
+            range,
+            text: text.into(),
+        }
+    }
+}
+
 /**
  * Provide inline value through a variable lookup.
  *
@@ -5736,6 +10312,7 @@ pub struct InlineValueVariableLookup {
     /**
      * If specified the name of the variable to look up.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub variableName: Option<String>,
 
     /**
@@ -5744,6 +10321,22 @@ pub struct InlineValueVariableLookup {
     pub caseSensitiveLookup: Boolean,
 }
 
+impl InlineValueVariableLookup {
+    /// a lookup of the variable named by the text at `range`
+    pub fn new(range: Range, case_sensitive: Boolean) -> Self {
+        InlineValueVariableLookup {
+            range,
+            variableName: None,
+            caseSensitiveLookup: case_sensitive,
+        }
+    }
+
+    pub fn with_variable_name(mut self, variable_name: impl Into<String>) -> Self {
+        self.variableName = Some(variable_name.into());
+        self
+    }
+}
+
 /**
  * Provide an inline value through an expression evaluation.
  *
@@ -5766,25 +10359,51 @@ pub struct InlineValueEvaluatableExpression {
     /**
      * If specified the expression overrides the extracted expression.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub expression: Option<String>,
 }
 
+impl InlineValueEvaluatableExpression {
+    /// an evaluation of the expression at `range`
+    pub fn new(range: Range) -> Self {
+        InlineValueEvaluatableExpression {
+            range,
+            expression: None,
+        }
+    }
+
+    pub fn with_expression(mut self, expression: impl Into<String>) -> Self {
+        self.expression = Some(expression.into());
+        self
+    }
+}
+
 /**
  * Inline value information can be provided by different means:
  * - directly as a text value (class InlineValueText).
  * - as a name to use for a variable lookup (class InlineValueVariableLookup)
  * - as an evaluatable expression (class InlineValueEvaluatableExpression)
+ *
  * The InlineValue types combines all inline value types into one type.
  *
  * @since 3.17.0
  */
+/// untagged, so [InlineValueEvaluatableExpression], whose only required
+/// member is `range`, has to stay last
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
 pub enum InlineValue {
     InlineValueText(InlineValueText),
     InlineValueVariableLookup(InlineValueVariableLookup),
     InlineValueEvaluatableExpression(InlineValueEvaluatableExpression),
 }
 
+impl_provider_from!(InlineValue {
+    InlineValueText,
+    InlineValueVariableLookup,
+    InlineValueEvaluatableExpression
+});
+
 /**
  * Client workspace capabilities specific to inline values.
  *
@@ -5801,6 +10420,7 @@ pub struct InlineValueWorkspaceClientCapabilities {
      * is useful for situation where a server for example detect a project wide
      * change that requires such a calculation.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub refreshSupport: Option<Boolean>,
 }
 
@@ -5812,26 +10432,29 @@ pub struct MonikerClientCapabilities {
      * StaticRegistrationOptions)` return value for the corresponding server
      * capability as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct MonikerOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct MonikerRegistrationOptions {
     /// extends TextDocumentRegistrationOptions
     /**
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends MonikerOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -5853,6 +10476,7 @@ pub struct MonikerParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -5860,13 +10484,17 @@ pub struct MonikerParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 }
 
+/// partial result of `textDocument/moniker`
+pub type MonikerPartialResult = Vec<Moniker>;
+
 /**
  * Moniker uniqueness level to define scope of the moniker.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UniquenessLevel {
     /**
      * The moniker is only unique inside a document
@@ -5899,10 +10527,18 @@ pub enum UniquenessLevel {
     Global,
 }
 
+impl_display_from_str!(UniquenessLevel {
+    Document => "document",
+    Project => "project",
+    Group => "group",
+    Scheme => "scheme",
+    Global => "global",
+});
+
 /**
  * The moniker kind.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MonikerKind {
     /**
      * The moniker represent a symbol that is imported into a project
@@ -5924,6 +10560,12 @@ pub enum MonikerKind {
     Local,
 }
 
+impl_display_from_str!(MonikerKind {
+    Import => "import",
+    Export => "export",
+    Local => "local",
+});
+
 /**
  * Moniker definition to match LSIF 0.5 moniker definition.
  */
@@ -5948,6 +10590,7 @@ pub struct Moniker {
     /**
      * The moniker kind if known.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<MonikerKind>,
 }
 
@@ -5986,27 +10629,32 @@ pub struct CompletionClientCapabilitiesCompletionItem {
      * the end of the snippet. Placeholders with equal identifiers are
      * linked, that is typing in one will update others too.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub snippetSupport: Option<Boolean>,
 
     /**
      * Client supports commit characters on a completion item.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub commitCharactersSupport: Option<Boolean>,
 
     /**
      * Client supports the follow content formats for the documentation
      * property. The order describes the preferred format of the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentationFormat: Option<Vec<MarkupKind>>,
 
     /**
      * Client supports the deprecated property on a completion item.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecatedSupport: Option<Boolean>,
 
     /**
      * Client supports the preselect property on a completion item.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub preselectSupport: Option<Boolean>,
 
     /**
@@ -6017,6 +10665,7 @@ pub struct CompletionClientCapabilitiesCompletionItem {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tagSupport: Option<CompletionItemTagValueSet>,
 
     /**
@@ -6025,6 +10674,7 @@ pub struct CompletionClientCapabilitiesCompletionItem {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub insertReplaceSupport: Option<Boolean>,
 
     /**
@@ -6034,6 +10684,7 @@ pub struct CompletionClientCapabilitiesCompletionItem {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveSupport: Option<ResolveSupportProperties>,
 
     /**
@@ -6043,6 +10694,7 @@ pub struct CompletionClientCapabilitiesCompletionItem {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub insertTextModeSupport: Option<InsertTextModeValueSet>,
 
     /**
@@ -6051,6 +10703,7 @@ pub struct CompletionClientCapabilitiesCompletionItem {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub labelDetailsSupport: Option<Boolean>,
 }
 
@@ -6067,6 +10720,7 @@ pub struct CompletionItemKindValueSet {
      * the completion items kinds from `Text` to `Reference` as defined in
      * the initial version of the protocol.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub valueSet: Option<Vec<CompletionItemKind>>,
 }
 
@@ -6083,6 +10737,7 @@ pub struct CompletionClientCapabilitiesCompletionListItemDefaults {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub itemDefaults: Option<Vec<String>>,
 }
 
@@ -6091,20 +10746,24 @@ pub struct CompletionClientCapabilities {
     /**
      * Whether completion supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
      * The client supports the following `CompletionItem` specific
      * capabilities.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub completionItem: Option<CompletionClientCapabilitiesCompletionItem>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub completionItemKind: Option<CompletionItemKindValueSet>,
 
     /**
      * The client supports to send additional context information for a
      * `textDocument/completion` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub contextSupport: Option<Boolean>,
 
     /**
@@ -6113,6 +10772,7 @@ pub struct CompletionClientCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub insertTextMode: Option<InsertTextMode>,
 
     /**
@@ -6121,11 +10781,12 @@ pub struct CompletionClientCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub completionList: Option<CompletionClientCapabilitiesCompletionListItemDefaults>,
 }
 
 /// extracted from [CompletionOptions::labelDetailsSupport]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct CompletionItemLabelDetailsSupport {
     /**
      * The server has support for completion item label
@@ -6134,15 +10795,17 @@ pub struct CompletionItemLabelDetailsSupport {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub labelDetailsSupport: Option<Boolean>,
 }
 
 /**
  * Completion options.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
 pub struct CompletionOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
@@ -6158,6 +10821,7 @@ pub struct CompletionOptions {
      * present `console` besides others as a completion item. Characters that
      * make up identifiers don't need to be listed here.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub triggerCharacters: Option<Vec<String>>,
 
     /**
@@ -6171,12 +10835,14 @@ pub struct CompletionOptions {
      *
      * @since 3.2.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub allCommitCharacters: Option<Vec<String>>,
 
     /**
      * The server provides support to resolve additional
      * information for a completion item.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveProvider: Option<Boolean>,
 
     /**
@@ -6185,6 +10851,7 @@ pub struct CompletionOptions {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub completionItem: Option<CompletionItemLabelDetailsSupport>,
 }
 
@@ -6194,7 +10861,7 @@ pub struct CompletionRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends CompletionOptions
     /// extends WorkDoneProgressOptions
@@ -6265,6 +10932,7 @@ pub struct CompletionParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -6272,6 +10940,7 @@ pub struct CompletionParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -6279,9 +10948,13 @@ pub struct CompletionParams {
      * to send this using the client capability
      * `completion.contextSupport === true`
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<CompletionContext>,
 }
 
+/// partial result of `textDocument/completion`
+pub type CompletionPartialResult = Vec<CompletionItem>;
+
 /**
  * How a completion was triggered
  */
@@ -6307,6 +10980,12 @@ pub enum CompletionTriggerKind {
     TriggerForIncompleteCompletions = 3,
 }
 
+impl_display_from_str!(CompletionTriggerKind {
+    Invoked,
+    TriggerCharacter,
+    TriggerForIncompleteCompletions
+});
+
 /**
  * Contains additional information about the context in which a completion
  * request is triggered.
@@ -6323,9 +11002,37 @@ pub struct CompletionContext {
      * complete. Is undefined if
      * `triggerKind !== CompletionTriggerKind.TriggerCharacter`
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub triggerCharacter: Option<String>,
 }
 
+impl CompletionContext {
+    /// completion was invoked explicitly, e.g. by typing ctrl+space
+    pub fn invoked() -> Self {
+        CompletionContext {
+            triggerKind: CompletionTriggerKind::Invoked,
+            triggerCharacter: None,
+        }
+    }
+
+    /// completion was triggered by typing one of the server's
+    /// [CompletionOptions::triggerCharacters]
+    pub fn trigger_character(character: impl Into<String>) -> Self {
+        CompletionContext {
+            triggerKind: CompletionTriggerKind::TriggerCharacter,
+            triggerCharacter: Some(character.into()),
+        }
+    }
+
+    /// completion is re-triggered because the previous list was incomplete
+    pub fn incomplete() -> Self {
+        CompletionContext {
+            triggerKind: CompletionTriggerKind::TriggerForIncompleteCompletions,
+            triggerCharacter: None,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(untagged)]
 pub enum CompletionListItemDefaultsEditRange {
@@ -6341,6 +11048,7 @@ pub struct CompletionListItemDefaults {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub commitCharacters: Option<Vec<String>>,
 
     /**
@@ -6348,6 +11056,7 @@ pub struct CompletionListItemDefaults {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub editRange: Option<CompletionListItemDefaultsEditRange>,
 
     /**
@@ -6355,6 +11064,7 @@ pub struct CompletionListItemDefaults {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub insertTextFormat: Option<InsertTextFormat>,
 
     /**
@@ -6362,6 +11072,7 @@ pub struct CompletionListItemDefaults {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub insertTextMode: Option<InsertTextMode>,
 
     /**
@@ -6369,6 +11080,7 @@ pub struct CompletionListItemDefaults {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<LSPAny>,
 }
 
@@ -6402,6 +11114,7 @@ pub struct CompletionList {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub itemDefaults: Option<CompletionListItemDefaults>,
 
     /**
@@ -6410,11 +11123,23 @@ pub struct CompletionList {
     pub items: Vec<CompletionItem>,
 }
 
+impl CompletionList {
+    /// adds a chunk of items streamed as a [CompletionPartialResult] for the
+    /// same request.
+    ///
+    /// Only chunks of one response accumulate: when an incomplete list is
+    /// recomputed on further typing, the new list replaces this one rather
+    /// than being appended to it.
+    pub fn append_items(&mut self, items: Vec<CompletionItem>) {
+        self.items.extend(items);
+    }
+}
+
 /**
  * Defines whether the insert text in a completion item should be interpreted as
  * plain text or a snippet.
  */
-#[derive(Serialize_repr, Deserialize_repr, Debug)]
+#[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq)]
 #[repr(u8)]
 pub enum InsertTextFormat {
     /**
@@ -6433,19 +11158,20 @@ pub enum InsertTextFormat {
     Snippet = 2,
 }
 
-/**
- * Completion item tags are extra annotations that tweak the rendering of a
- * completion item.
- *
- * @since 3.15.0
- */
-#[derive(Serialize_repr, Deserialize_repr, Debug)]
-#[repr(u8)]
-pub enum CompletionItemTag {
+open_repr_enum! {
     /**
-     * Render a completion as obsolete, usually using a strike-out.
+     * Completion item tags are extra annotations that tweak the rendering of a
+     * completion item.
+     *
+     * @since 3.15.0
      */
-    Deprecated = 1,
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CompletionItemTag {
+        /**
+         * Render a completion as obsolete, usually using a strike-out.
+         */
+        Deprecated = 1,
+    }
 }
 
 /**
@@ -6453,7 +11179,7 @@ pub enum CompletionItemTag {
  *
  * @since 3.16.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct InsertReplaceEdit {
     /**
      * The String to be inserted.
@@ -6471,13 +11197,98 @@ pub struct InsertReplaceEdit {
     pub replace: Range,
 }
 
+impl InsertReplaceEdit {
+    /// debug builds check the spec's rules: both ranges are single-line and
+    /// `insert` is a prefix of `replace`
+    pub fn new(new_text: impl Into<String>, insert: Range, replace: Range) -> Self {
+        debug_assert!(
+            insert.start.line == insert.end.line && replace.start.line == replace.end.line,
+            "insert and replace ranges must be single-line"
+        );
+        debug_assert!(
+            insert.start == replace.start && insert.end <= replace.end,
+            "the insert range must be a prefix of the replace range"
+        );
+        InsertReplaceEdit {
+            newText: new_text.into(),
+            insert,
+            replace,
+        }
+    }
+}
+
+/// checks the spec's rules for a [CompletionItem::textEdit]: its range has to
+/// be single-line and contain `request_pos`, the position completion was
+/// requested at
+pub fn validate_completion_edit(
+    edit: &TextEdit,
+    request_pos: &Position,
+) -> Result<(), CompletionEditError> {
+    if !edit.range.is_single_line() {
+        return Err(CompletionEditError::MultiLine);
+    }
+    if *request_pos < edit.range.start || edit.range.end < *request_pos {
+        return Err(CompletionEditError::PositionOutside);
+    }
+    Ok(())
+}
+
+/// the [CompletionItem::textEdit] for `caps`: an [InsertReplaceEdit] when the
+/// client has `insertReplaceSupport`, otherwise a [TextEdit] over `replace`
+pub fn completion_edit(
+    insert: Range,
+    replace: Range,
+    new_text: String,
+    caps: &CompletionClientCapabilities,
+) -> CompletionItemEditKind {
+    let insert_replace = caps
+        .completionItem
+        .as_ref()
+        .is_some_and(|item| item.insert_replace_support_enabled());
+    if insert_replace {
+        CompletionItemEditKind::InsertReplaceEdit(InsertReplaceEdit::new(new_text, insert, replace))
+    } else {
+        CompletionItemEditKind::TextEdit(TextEdit {
+            range: replace,
+            newText: new_text,
+        })
+    }
+}
+
+/// why [validate_completion_edit] rejected an edit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionEditError {
+    /// the edit's range spans more than one line
+    MultiLine,
+    /// the edit's range doesn't contain the position completion was
+    /// requested at
+    PositionOutside,
+}
+
+impl std::fmt::Display for CompletionEditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompletionEditError::MultiLine => {
+                write!(f, "completion text edit spans multiple lines")
+            }
+            CompletionEditError::PositionOutside => write!(
+                f,
+                "completion text edit doesn't contain the request position"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CompletionEditError {}
+
 /**
  * How whitespace and indentation is handled during completion
  * item insertion.
  *
  * @since 3.16.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize_repr, Deserialize_repr, Debug, PartialEq)]
+#[repr(u8)]
 pub enum InsertTextMode {
     /**
      * The insertion or replace strings is taken as it is. If the
@@ -6505,13 +11316,14 @@ pub enum InsertTextMode {
  *
  * @since 3.17.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct CompletionItemLabelDetails {
     /**
      * An optional String which is rendered less prominently directly after
      * {@link CompletionItem.label label}, without any spacing. Should be
      * used for function signatures or type annotations.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<String>,
 
     /**
@@ -6519,17 +11331,18 @@ pub struct CompletionItemLabelDetails {
      * {@link CompletionItemLabelDetails.detail}. Should be used for fully qualified
      * names or file path.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum CompletionItemEditKind {
     TextEdit(TextEdit),
     InsertReplaceEdit(InsertReplaceEdit),
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct CompletionItem {
     /**
      * The label of this completion item.
@@ -6547,12 +11360,14 @@ pub struct CompletionItem {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub labelDetails: Option<CompletionItemLabelDetails>,
     /**
      * The kind of this completion item. Based of the kind
      * an icon is chosen by the editor. The standardized set
      * of available values is defined in `CompletionItemKind`.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<CompletionItemKind>,
 
     /**
@@ -6560,17 +11375,20 @@ pub struct CompletionItem {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<CompletionItemTag>>,
 
     /**
      * A human-readable String with additional information
      * about this item, like type or symbol information.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<String>,
 
     /**
      * A human-readable String that represents a doc-comment.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentation: Option<MarkupContentOrString>,
 
     /**
@@ -6578,6 +11396,7 @@ pub struct CompletionItem {
      *
      * @deprecated Use `tags` instead if supported.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<Boolean>,
 
     /**
@@ -6587,6 +11406,7 @@ pub struct CompletionItem {
      * tool / client decides which item that is. The rule is that the *first*
      * item of those that match best is selected.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub preselect: Option<Boolean>,
 
     /**
@@ -6594,6 +11414,7 @@ pub struct CompletionItem {
      * with other items. When omitted the label is used
      * as the sort text for this item.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sortText: Option<String>,
 
     /**
@@ -6601,6 +11422,7 @@ pub struct CompletionItem {
      * completion items. When omitted the label is used as the
      * filter text for this item.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub filterText: Option<String>,
 
     /**
@@ -6616,6 +11438,7 @@ pub struct CompletionItem {
      * recommended to use `textEdit` instead since it avoids additional client
      * side interpretation.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub insertText: Option<String>,
 
     /**
@@ -6626,6 +11449,7 @@ pub struct CompletionItem {
      * Please note that the insertTextFormat doesn't apply to
      * `additionalTextEdits`.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub insertTextFormat: Option<InsertTextFormat>,
 
     /**
@@ -6636,6 +11460,7 @@ pub struct CompletionItem {
      * @since 3.16.0
      * @since 3.17.0 - support for `textDocument.completion.insertTextMode`
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub insertTextMode: Option<InsertTextMode>,
 
     /**
@@ -6662,7 +11487,8 @@ pub struct CompletionItem {
      *
      * @since 3.16.0 additional type `InsertReplaceEdit`
      */
-    pub textEdit: Option<CompletionItemKind>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub textEdit: Option<CompletionItemEditKind>,
 
     /**
      * The edit text used if the completion item is part of a CompletionList and
@@ -6676,6 +11502,7 @@ pub struct CompletionItem {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub textEditText: Option<String>,
 
     /**
@@ -6687,6 +11514,7 @@ pub struct CompletionItem {
      * current cursor position (for example adding an import statement at the
      * top of the file if the completion item will insert an unqualified type).
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub additionalTextEdits: Option<Vec<TextEdit>>,
 
     /**
@@ -6695,6 +11523,7 @@ pub struct CompletionItem {
      * commit characters should have `length=1` and that superfluous characters
      * will be ignored.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub commitCharacters: Option<Vec<String>>,
 
     /**
@@ -6702,48 +11531,205 @@ pub struct CompletionItem {
      * *Note* that additional modifications to the current document should be
      * described with the additionalTextEdits-property.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub command: Option<Command>,
 
     /**
      * A data entry field that is preserved on a completion item between
      * a completion and a completion resolve request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<LSPAny>,
 }
 
-/**
- * The kind of a completion entry.
- */
-#[derive(Serialize_repr, Deserialize_repr, Debug)]
-#[repr(u8)]
-pub enum CompletionItemKind {
-    Text = 1,
-    Method = 2,
-    Function = 3,
-    Constructor = 4,
-    Field = 5,
-    Variable = 6,
-    Class = 7,
-    Interface = 8,
-    Module = 9,
-    Property = 10,
-    Unit = 11,
-    Value = 12,
-    Enum = 13,
-    Keyword = 14,
-    Snippet = 15,
-    Color = 16,
-    File = 17,
-    Reference = 18,
-    Folder = 19,
-    EnumMember = 20,
-    Constant = 21,
-    Struct = 22,
-    Event = 23,
-    Operator = 24,
-    TypeParameter = 25,
+impl CompletionItem {
+    pub fn new(label: impl Into<String>) -> Self {
+        CompletionItem {
+            label: label.into(),
+            labelDetails: None,
+            kind: None,
+            tags: None,
+            detail: None,
+            documentation: None,
+            deprecated: None,
+            preselect: None,
+            sortText: None,
+            filterText: None,
+            insertText: None,
+            insertTextFormat: None,
+            insertTextMode: None,
+            textEdit: None,
+            textEditText: None,
+            additionalTextEdits: None,
+            commitCharacters: None,
+            command: None,
+            data: None,
+        }
+    }
+
+    /// sets an [InsertReplaceEdit], for clients with `insertReplaceSupport`
+    pub fn insert_replace(
+        mut self,
+        new_text: impl Into<String>,
+        insert: Range,
+        replace: Range,
+    ) -> Self {
+        self.textEdit = Some(CompletionItemEditKind::InsertReplaceEdit(
+            InsertReplaceEdit::new(new_text, insert, replace),
+        ));
+        self
+    }
+
+    /// folds [CompletionItem::labelDetails] into the label for clients
+    /// without `labelDetailsSupport`, the way a supporting client renders
+    /// them: the detail directly after the label, the description after a
+    /// space
+    pub fn flatten_label_details(&mut self) {
+        let Some(details) = self.labelDetails.take() else {
+            return;
+        };
+        if let Some(detail) = details.detail {
+            self.label.push_str(&detail);
+        }
+        if let Some(description) = details.description {
+            self.label.push(' ');
+            self.label.push_str(&description);
+        }
+    }
+}
+
+open_repr_enum! {
+    /**
+     * The kind of a completion entry.
+     */
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CompletionItemKind {
+        Text = 1,
+        Method = 2,
+        Function = 3,
+        Constructor = 4,
+        Field = 5,
+        Variable = 6,
+        Class = 7,
+        Interface = 8,
+        Module = 9,
+        Property = 10,
+        Unit = 11,
+        Value = 12,
+        Enum = 13,
+        Keyword = 14,
+        Snippet = 15,
+        Color = 16,
+        File = 17,
+        Reference = 18,
+        Folder = 19,
+        EnumMember = 20,
+        Constant = 21,
+        Struct = 22,
+        Event = 23,
+        Operator = 24,
+        TypeParameter = 25,
+    }
+}
+
+impl CompletionItemKind {
+    /// every kind, e.g. for [CompletionItemKindValueSet::valueSet]
+    pub fn all() -> &'static [CompletionItemKind] {
+        &[
+            CompletionItemKind::Text,
+            CompletionItemKind::Method,
+            CompletionItemKind::Function,
+            CompletionItemKind::Constructor,
+            CompletionItemKind::Field,
+            CompletionItemKind::Variable,
+            CompletionItemKind::Class,
+            CompletionItemKind::Interface,
+            CompletionItemKind::Module,
+            CompletionItemKind::Property,
+            CompletionItemKind::Unit,
+            CompletionItemKind::Value,
+            CompletionItemKind::Enum,
+            CompletionItemKind::Keyword,
+            CompletionItemKind::Snippet,
+            CompletionItemKind::Color,
+            CompletionItemKind::File,
+            CompletionItemKind::Reference,
+            CompletionItemKind::Folder,
+            CompletionItemKind::EnumMember,
+            CompletionItemKind::Constant,
+            CompletionItemKind::Struct,
+            CompletionItemKind::Event,
+            CompletionItemKind::Operator,
+            CompletionItemKind::TypeParameter,
+        ]
+    }
+
+    /// the symbol kind of the same name, the reverse of
+    /// [SymbolKind::to_completion_kind]. `None` for the kinds that don't
+    /// describe a symbol: text, unit, value, keyword, snippet, color,
+    /// reference and folder, and for an unknown kind.
+    pub fn to_symbol_kind(&self) -> Option<SymbolKind> {
+        Some(match self {
+            CompletionItemKind::Method => SymbolKind::Method,
+            CompletionItemKind::Function => SymbolKind::Function,
+            CompletionItemKind::Constructor => SymbolKind::Constructor,
+            CompletionItemKind::Field => SymbolKind::Field,
+            CompletionItemKind::Variable => SymbolKind::Variable,
+            CompletionItemKind::Class => SymbolKind::Class,
+            CompletionItemKind::Interface => SymbolKind::Interface,
+            CompletionItemKind::Module => SymbolKind::Module,
+            CompletionItemKind::Property => SymbolKind::Property,
+            CompletionItemKind::Enum => SymbolKind::Enum,
+            CompletionItemKind::File => SymbolKind::File,
+            CompletionItemKind::EnumMember => SymbolKind::EnumMember,
+            CompletionItemKind::Constant => SymbolKind::Constant,
+            CompletionItemKind::Struct => SymbolKind::Struct,
+            CompletionItemKind::Event => SymbolKind::Event,
+            CompletionItemKind::Operator => SymbolKind::Operator,
+            CompletionItemKind::TypeParameter => SymbolKind::TypeParameter,
+            CompletionItemKind::Text
+            | CompletionItemKind::Unit
+            | CompletionItemKind::Value
+            | CompletionItemKind::Keyword
+            | CompletionItemKind::Snippet
+            | CompletionItemKind::Color
+            | CompletionItemKind::Reference
+            | CompletionItemKind::Folder
+            | CompletionItemKind::Unknown(_) => return None,
+        })
+    }
 }
 
+impl_display_from_str!(
+    CompletionItemKind {
+        Text,
+        Method,
+        Function,
+        Constructor,
+        Field,
+        Variable,
+        Class,
+        Interface,
+        Module,
+        Property,
+        Unit,
+        Value,
+        Enum,
+        Keyword,
+        Snippet,
+        Color,
+        File,
+        Reference,
+        Folder,
+        EnumMember,
+        Constant,
+        Struct,
+        Event,
+        Operator,
+        TypeParameter,
+    } | Unknown
+);
+
 /// exctracted from [PublishDiagnosticsClientCapabilities::tagSupport]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PublishDiagnosticsClientCapabilitiesTagSupport {
@@ -6758,6 +11744,7 @@ pub struct PublishDiagnosticsClientCapabilities {
     /**
      * Whether the clients accepts diagnostics with related information.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub relatedInformation: Option<Boolean>,
 
     /**
@@ -6766,6 +11753,7 @@ pub struct PublishDiagnosticsClientCapabilities {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tagSupport: Option<PublishDiagnosticsClientCapabilitiesTagSupport>,
 
     /**
@@ -6774,6 +11762,7 @@ pub struct PublishDiagnosticsClientCapabilities {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub versionSupport: Option<Boolean>,
 
     /**
@@ -6781,6 +11770,7 @@ pub struct PublishDiagnosticsClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub codeDescriptionSupport: Option<Boolean>,
 
     /**
@@ -6790,10 +11780,11 @@ pub struct PublishDiagnosticsClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dataSupport: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PublishDiagnosticsParams {
     /**
      * The URI for which diagnostic information is reported.
@@ -6806,6 +11797,7 @@ pub struct PublishDiagnosticsParams {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<Integer>,
 
     /**
@@ -6814,6 +11806,24 @@ pub struct PublishDiagnosticsParams {
     pub diagnostics: Vec<Diagnostic>,
 }
 
+impl PublishDiagnosticsParams {
+    pub fn new(uri: impl Into<DocumentUri>, diagnostics: Vec<Diagnostic>) -> Self {
+        PublishDiagnosticsParams {
+            uri: uri.into(),
+            version: None,
+            diagnostics,
+        }
+    }
+
+    /// the document version the diagnostics were computed for; only send it
+    /// to clients advertising
+    /// [PublishDiagnosticsClientCapabilities::versionSupport]
+    pub fn with_version(mut self, version: Integer) -> Self {
+        self.version = Some(version);
+        self
+    }
+}
+
 /**
  * Client capabilities specific to diagnostic pull requests.
  *
@@ -6827,12 +11837,14 @@ pub struct DiagnosticClientCapabilities {
      * `(TextDocumentRegistrationOptions & StaticRegistrationOptions)`
      * return value for the corresponding server capability as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
      * Whether the clients supports related documents for document diagnostic
      * pulls.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub relatedDocumentSupport: Option<Boolean>,
 }
 
@@ -6841,15 +11853,17 @@ pub struct DiagnosticClientCapabilities {
  *
  * @since 3.17.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DiagnosticOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
      * An optional identifier under which the diagnostics are
      * managed by the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub identifier: Option<String>,
 
     /**
@@ -6871,17 +11885,18 @@ pub struct DiagnosticOptions {
  *
  * @since 3.17.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DiagnosticRegistrationOptions {
     /// extends TextDocumentRegistrationOptions
     /**
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends DiagnosticOptions,
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends DiagnosticOptions,
@@ -6889,6 +11904,7 @@ pub struct DiagnosticRegistrationOptions {
      * An optional identifier under which the diagnostics are
      * managed by the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub identifier: Option<String>,
 
     /// extends DiagnosticOptions,
@@ -6911,6 +11927,7 @@ pub struct DiagnosticRegistrationOptions {
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -6925,6 +11942,7 @@ pub struct DocumentDiagnosticParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -6932,6 +11950,7 @@ pub struct DocumentDiagnosticParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -6942,11 +11961,13 @@ pub struct DocumentDiagnosticParams {
     /**
      * The additional identifier  provided during registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub identifier: Option<String>,
 
     /**
      * The result id of a previous response if provided.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub previousResultId: Option<String>,
 }
 
@@ -6960,6 +11981,7 @@ pub struct DocumentDiagnosticParams {
  * @since 3.17.0
  */
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
 pub enum DocumentDiagnosticReport {
     RelatedFullDocumentDiagnosticReport(RelatedFullDocumentDiagnosticReport),
     RelatedUnchangedDocumentDiagnosticReport(RelatedUnchangedDocumentDiagnosticReport),
@@ -6970,12 +11992,13 @@ pub enum DocumentDiagnosticReport {
  *
  * @since 3.17.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum DocumentDiagnosticReportKind {
     /**
      * A diagnostic report with a full
      * set of problems.
      */
+    #[default]
     #[serde(rename = "full")]
     Full,
 
@@ -6987,6 +12010,11 @@ pub enum DocumentDiagnosticReportKind {
     Unchanged,
 }
 
+impl_display_from_str!(DocumentDiagnosticReportKind {
+    Full => "full",
+    Unchanged => "unchanged",
+});
+
 /**
  * A diagnostic report with a full set of problems.
  *
@@ -7005,6 +12033,7 @@ pub struct FullDocumentDiagnosticReport {
      * be sent on the next diagnostic request for the
      * same document.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resultId: Option<String>,
 
     /**
@@ -7013,6 +12042,16 @@ pub struct FullDocumentDiagnosticReport {
     pub items: Vec<Diagnostic>,
 }
 
+impl FullDocumentDiagnosticReport {
+    pub fn new(items: Vec<Diagnostic>) -> Self {
+        FullDocumentDiagnosticReport {
+            kind: DocumentDiagnosticReportKind::Full,
+            resultId: None,
+            items,
+        }
+    }
+}
+
 /**
  * A diagnostic report indicating that the last returned
  * report is still accurate.
@@ -7057,6 +12096,7 @@ pub struct RelatedFullDocumentDiagnosticReport {
      * be sent on the next diagnostic request for the
      * same document.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resultId: Option<String>,
 
     /// extends FullDocumentDiagnosticReport
@@ -7078,6 +12118,7 @@ pub struct RelatedFullDocumentDiagnosticReport {
     //     [uri: String /** DocumentUri */]:
     //         FullDocumentDiagnosticReport | UnchangedDocumentDiagnosticReport,
     // },
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub relatedDocuments: Option<BTreeMap<DocumentUri, DocumentDiagnosticReportKind>>,
 }
 
@@ -7117,6 +12158,7 @@ pub struct RelatedUnchangedDocumentDiagnosticReport {
     //     [uri: String /** DocumentUri */]:
     //         FullDocumentDiagnosticReport | UnchangedDocumentDiagnosticReport,
     // },
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub relatedDocuments: Option<BTreeMap<DocumentUri, DocumentDiagnosticReportKind>>,
 }
 
@@ -7131,6 +12173,7 @@ pub struct DocumentDiagnosticReportPartialResult {
     //         [uri: String /** DocumentUri */]:
     //             FullDocumentDiagnosticReport | UnchangedDocumentDiagnosticReport,
     //     },
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub relatedDocuments: Option<BTreeMap<DocumentUri, DocumentDiagnosticReportKind>>,
 }
 
@@ -7155,6 +12198,7 @@ pub struct WorkspaceDiagnosticParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -7162,11 +12206,13 @@ pub struct WorkspaceDiagnosticParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
      * The additional identifier provided during registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub identifier: Option<String>,
 
     /**
@@ -7205,6 +12251,24 @@ pub struct WorkspaceDiagnosticReport {
     pub items: Vec<WorkspaceDocumentDiagnosticReport>,
 }
 
+impl WorkspaceDiagnosticReport {
+    /// every diagnostic of the full reports with the document it belongs to;
+    /// unchanged reports carry no diagnostics and are skipped
+    pub fn all_diagnostics(&self) -> impl Iterator<Item = (&DocumentUri, &Diagnostic)> {
+        self.items.iter().flat_map(|report| {
+            let (uri, diagnostics) = match report {
+                WorkspaceDocumentDiagnosticReport::WorkspaceFullDocumentDiagnosticReport(full) => {
+                    (&full.uri, full.items.as_slice())
+                }
+                WorkspaceDocumentDiagnosticReport::WorkspaceUnchangedDocumentDiagnosticReport(
+                    unchanged,
+                ) => (&unchanged.uri, &[][..]),
+            };
+            diagnostics.iter().map(move |diagnostic| (uri, diagnostic))
+        })
+    }
+}
+
 /**
  * A full document diagnostic report for a workspace diagnostic result.
  *
@@ -7225,6 +12289,7 @@ pub struct WorkspaceFullDocumentDiagnosticReport {
      * be sent on the next diagnostic request for the
      * same document.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resultId: Option<String>,
 
     /// extends FullDocumentDiagnosticReport
@@ -7242,7 +12307,7 @@ pub struct WorkspaceFullDocumentDiagnosticReport {
      * The version number for which the diagnostics are reported.
      * If the document is not marked as open `null` can be provided.
      */
-    pub version: Option<Integer>,
+    pub version: Nullable<Integer>,
 }
 
 /**
@@ -7278,7 +12343,7 @@ pub struct WorkspaceUnchangedDocumentDiagnosticReport {
      * The version number for which the diagnostics are reported.
      * If the document is not marked as open `null` can be provided.
      */
-    pub version: Option<Integer>,
+    pub version: Nullable<Integer>,
 }
 
 /**
@@ -7286,6 +12351,8 @@ pub struct WorkspaceUnchangedDocumentDiagnosticReport {
  *
  * @since 3.17.0
  */
+/// told apart by their required members: only a full report has `items`,
+/// and an unchanged one always carries a `resultId`
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(untagged)]
 pub enum WorkspaceDocumentDiagnosticReport {
@@ -7319,6 +12386,7 @@ pub struct DiagnosticWorkspaceClientCapabilities {
      * and is useful for situation where a server for example detects a project
      * wide change that requires such a calculation.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub refreshSupport: Option<Boolean>,
 }
 
@@ -7331,6 +12399,7 @@ pub struct SignatureHelpClientCapabilitiesSignatureInformationParameterInformati
      *
      * @since 3.14.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub labelOffsetSupport: Option<Boolean>,
 }
 
@@ -7341,6 +12410,7 @@ pub struct SignatureHelpClientCapabilitiesSignatureInformation {
      * Client supports the follow content formats for the documentation
      * property. The order describes the preferred format of the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentationFormat: Option<Vec<MarkupKind>>,
 
     /**
@@ -7355,6 +12425,7 @@ pub struct SignatureHelpClientCapabilitiesSignatureInformation {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub activeParameterSupport: Option<Boolean>,
 }
 
@@ -7363,12 +12434,14 @@ pub struct SignatureHelpClientCapabilities {
     /**
      * Whether signature help supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
      * The client supports the following `SignatureInformation`
      * specific properties.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub signatureInformation: Option<SignatureHelpClientCapabilitiesSignatureInformation>,
 
     /**
@@ -7379,18 +12452,21 @@ pub struct SignatureHelpClientCapabilities {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub contextSupport: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct SignatureHelpOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
      * The characters that trigger signature help
      * automatically.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub triggerCharacters: Option<Vec<String>>,
 
     /**
@@ -7402,6 +12478,7 @@ pub struct SignatureHelpOptions {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub retriggerCharacters: Option<Vec<String>>,
 }
 
@@ -7412,10 +12489,11 @@ pub struct SignatureHelpRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extend SignatureHelpOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extend SignatureHelpOptions
@@ -7423,6 +12501,7 @@ pub struct SignatureHelpRegistrationOptions {
      * The characters that trigger signature help
      * automatically.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub triggerCharacters: Option<Vec<String>>,
 
     /// extend SignatureHelpOptions
@@ -7435,6 +12514,7 @@ pub struct SignatureHelpRegistrationOptions {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub retriggerCharacters: Option<Vec<String>>,
 }
 
@@ -7456,6 +12536,7 @@ pub struct SignatureHelpParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /**
@@ -7465,6 +12546,7 @@ pub struct SignatureHelpParams {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<SignatureHelpContext>,
 }
 
@@ -7491,6 +12573,12 @@ pub enum SignatureHelpTriggerKind {
     ContentChange = 3,
 }
 
+impl_display_from_str!(SignatureHelpTriggerKind {
+    Invoked,
+    TriggerCharacter,
+    ContentChange
+});
+
 /**
  * Additional information about the context in which a signature help request
  * was triggered.
@@ -7510,6 +12598,7 @@ pub struct SignatureHelpContext {
      * This is undefined when triggerKind !==
      * SignatureHelpTriggerKind.TriggerCharacter
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub triggerCharacter: Option<String>,
 
     /**
@@ -7527,6 +12616,7 @@ pub struct SignatureHelpContext {
      * The `activeSignatureHelp` has its `SignatureHelp.activeSignature` field
      * updated based on the user navigating through available signatures.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub activeSignatureHelp: Option<SignatureHelp>,
 }
 
@@ -7554,6 +12644,7 @@ pub struct SignatureHelp {
      * In future version of the protocol this property might become
      * mandatory to better express this.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub activeSignature: Option<UInteger>,
 
     /**
@@ -7565,9 +12656,65 @@ pub struct SignatureHelp {
      * mandatory to better express the active parameter if the
      * active signature does have any.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub activeParameter: Option<UInteger>,
 }
 
+impl SignatureHelp {
+    pub fn new(signatures: Vec<SignatureInformation>) -> Self {
+        SignatureHelp {
+            signatures,
+            activeSignature: None,
+            activeParameter: None,
+        }
+    }
+
+    pub fn active(mut self, signature_index: UInteger, parameter_index: UInteger) -> Self {
+        self.activeSignature = Some(signature_index);
+        self.activeParameter = Some(parameter_index);
+        self
+    }
+
+    /// the label of the active parameter of the active signature, resolving
+    /// the defaults described on [SignatureHelp::activeSignature] and
+    /// [SignatureHelp::activeParameter]; a
+    /// [SignatureInformation::activeParameter] takes precedence. Offset labels
+    /// are sliced out of the signature label.
+    pub fn active_parameter_label(&self) -> Option<&str> {
+        let index = |active: Option<UInteger>, len: usize| match active {
+            Some(active) if (active as usize) < len => active as usize,
+            _ => 0,
+        };
+        let signature = self
+            .signatures
+            .get(index(self.activeSignature, self.signatures.len()))?;
+        let parameters = signature.parameters.as_ref()?;
+        let active = signature.activeParameter.or(self.activeParameter);
+        let parameter = parameters.get(index(active, parameters.len()))?;
+        match &parameter.label {
+            ParameterInformationLabel::String(label) => Some(label),
+            ParameterInformationLabel::StartEndOffsets(start, end) => {
+                let start = utf16_to_byte_offset(&signature.label, *start)?;
+                let end = utf16_to_byte_offset(&signature.label, *end)?;
+                signature.label.get(start..end)
+            }
+        }
+    }
+}
+
+/// the byte offset of the UTF-16 code unit `offset` in `text`, if that falls
+/// on a character boundary
+fn utf16_to_byte_offset(text: &str, offset: UInteger) -> Option<usize> {
+    let mut units = 0;
+    for (byte, c) in text.char_indices() {
+        if units == offset as usize {
+            return Some(byte);
+        }
+        units += c.len_utf16();
+    }
+    (units == offset as usize).then_some(text.len())
+}
+
 /**
  * Represents the signature of something callable. A signature
  * can have a label, like a function-name, a doc-comment, and
@@ -7585,11 +12732,13 @@ pub struct SignatureInformation {
      * The human-readable doc-comment of this signature. Will be shown
      * in the UI but can be omitted.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentation: Option<MarkupContentOrString>,
 
     /**
      * The parameters of this signature.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<Vec<ParameterInformation>>,
 
     /**
@@ -7599,6 +12748,7 @@ pub struct SignatureInformation {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub activeParameter: Option<UInteger>,
 }
 
@@ -7611,7 +12761,7 @@ pub enum ParameterInformationLabel {
 }
 
 /// extracted from [ParameterInformation::documentation] (and several more places)
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum MarkupContentOrString {
     String(String),
@@ -7643,6 +12793,7 @@ pub struct ParameterInformation {
      * The human-readable doc-comment of this parameter. Will be shown
      * in the UI but can be omitted.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub documentation: Option<MarkupContentOrString>,
 }
 
@@ -7682,6 +12833,7 @@ pub struct CodeActionClientCapabilities {
     /**
      * Whether code action supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
@@ -7690,6 +12842,7 @@ pub struct CodeActionClientCapabilities {
      *
      * @since 3.8.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub codeActionLiteralSupport: Option<CodeActionClientCapabilitiesCodeActionLiteralSupport>,
 
     /**
@@ -7697,6 +12850,7 @@ pub struct CodeActionClientCapabilities {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub isPreferredSupport: Option<Boolean>,
 
     /**
@@ -7704,6 +12858,7 @@ pub struct CodeActionClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub disabledSupport: Option<Boolean>,
 
     /**
@@ -7713,6 +12868,7 @@ pub struct CodeActionClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dataSupport: Option<Boolean>,
     /**
      * Whether the client supports resolving additional code action
@@ -7720,6 +12876,7 @@ pub struct CodeActionClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveSupport: Option<CodeActionClientCapabilitiesResolveSupport>,
 
     /**
@@ -7731,12 +12888,14 @@ pub struct CodeActionClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub honorsChangeAnnotations: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct CodeActionOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
@@ -7745,6 +12904,7 @@ pub struct CodeActionOptions {
      * The list of kinds may be generic, such as `CodeActionKind.Refactor`,
      * or the server may list out every specific kind they provide.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub codeActionKinds: Option<Vec<CodeActionKind>>,
 
     /**
@@ -7753,6 +12913,7 @@ pub struct CodeActionOptions {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveProvider: Option<Boolean>,
 }
 
@@ -7763,10 +12924,11 @@ pub struct CodeActionRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends CodeActionOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends CodeActionOptions
@@ -7776,6 +12938,7 @@ pub struct CodeActionRegistrationOptions {
      * The list of kinds may be generic, such as `CodeActionKind.Refactor`,
      * or the server may list out every specific kind they provide.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub codeActionKinds: Option<Vec<CodeActionKind>>,
 
     /// extends CodeActionOptions
@@ -7785,6 +12948,7 @@ pub struct CodeActionRegistrationOptions {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveProvider: Option<Boolean>,
 }
 
@@ -7797,6 +12961,7 @@ pub struct CodeActionParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -7804,6 +12969,7 @@ pub struct CodeActionParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
     /**
      * The document in which the command was invoked.
@@ -7821,6 +12987,18 @@ pub struct CodeActionParams {
     pub context: CodeActionContext,
 }
 
+impl CodeActionParams {
+    pub fn new(uri: impl Into<DocumentUri>, range: Range, context: CodeActionContext) -> Self {
+        CodeActionParams {
+            workDoneToken: None,
+            partialResultToken: None,
+            textDocument: TextDocumentIdentifier::new(uri),
+            range,
+            context,
+        }
+    }
+}
+
 /**
  * The kind of a code action.
  *
@@ -7833,7 +13011,7 @@ pub struct CodeActionParams {
 /**
  * A set of predefined code action kinds.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum CodeActionKind {
     /**
      * Empty kind.
@@ -7921,6 +13099,56 @@ pub enum CodeActionKind {
      */
     #[serde(rename = "source.fixAll")]
     SourceFixAll,
+
+    /// the set of kinds is open, so anything not predefined above ends up here
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl CodeActionKind {
+    /// every predefined kind, e.g. for
+    /// [CodeActionClientCapabilitiesCodeActionKind::valueSet]
+    pub fn all() -> &'static [CodeActionKind] {
+        const ALL: &[CodeActionKind] = &[
+            CodeActionKind::Empty,
+            CodeActionKind::QuickFix,
+            CodeActionKind::Refactor,
+            CodeActionKind::RefactorExtract,
+            CodeActionKind::RefactorInline,
+            CodeActionKind::RefactorRewrite,
+            CodeActionKind::Source,
+            CodeActionKind::SourceOrganizeImports,
+            CodeActionKind::SourceFixAll,
+        ];
+        ALL
+    }
+
+    /// The dot-separated wire value, e.g. `"refactor.extract"`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            CodeActionKind::Empty => "",
+            CodeActionKind::QuickFix => "quickfix",
+            CodeActionKind::Refactor => "refactor",
+            CodeActionKind::RefactorExtract => "refactor.extract",
+            CodeActionKind::RefactorInline => "refactor.inline",
+            CodeActionKind::RefactorRewrite => "refactor.rewrite",
+            CodeActionKind::Source => "source",
+            CodeActionKind::SourceOrganizeImports => "source.organizeImports",
+            CodeActionKind::SourceFixAll => "source.fixAll",
+            CodeActionKind::Other(kind) => kind,
+        }
+    }
+
+    /// Whether `other` is this kind or one of its sub kinds, so `refactor`
+    /// contains `refactor.extract.function` but not `refactoring`.
+    /// This is the rule clients use to filter actions by `CodeActionContext::only`.
+    pub fn contains(&self, other: &CodeActionKind) -> bool {
+        let (kind, other) = (self.as_str(), other.as_str());
+        kind == other
+            || other
+                .strip_prefix(kind)
+                .is_some_and(|rest| rest.starts_with('.'))
+    }
 }
 
 /**
@@ -7945,6 +13173,7 @@ pub struct CodeActionContext {
      * Actions not of this kind are filtered out by the client before being
      * shown. So servers can omit computing them.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub only: Option<Vec<CodeActionKind>>,
 
     /**
@@ -7952,9 +13181,33 @@ pub struct CodeActionContext {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub triggerKind: Option<CodeActionTriggerKind>,
 }
 
+impl CodeActionContext {
+    /// a context carrying the diagnostics overlapping the requested range
+    pub fn new(diagnostics: Vec<Diagnostic>) -> Self {
+        CodeActionContext {
+            diagnostics,
+            only: None,
+            triggerKind: None,
+        }
+    }
+
+    /// restricts the requested actions to `kinds`, e.g.
+    /// `[CodeActionKind::QuickFix]`
+    pub fn only(mut self, kinds: Vec<CodeActionKind>) -> Self {
+        self.only = Some(kinds);
+        self
+    }
+
+    pub fn trigger_kind(mut self, trigger_kind: CodeActionTriggerKind) -> Self {
+        self.triggerKind = Some(trigger_kind);
+        self
+    }
+}
+
 /**
  * The reason why code actions were requested.
  *
@@ -7977,6 +13230,8 @@ pub enum CodeActionTriggerKind {
     Automatic = 2,
 }
 
+impl_display_from_str!(CodeActionTriggerKind { Invoked, Automatic });
+
 /// extracted from CodeAction
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CodeActionDisabled {
@@ -8008,11 +13263,13 @@ pub struct CodeAction {
      *
      * Used to filter code actions.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<CodeActionKind>,
 
     /**
      * The diagnostics that this code action resolves.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub diagnostics: Option<Vec<Diagnostic>>,
 
     /**
@@ -8025,6 +13282,7 @@ pub struct CodeAction {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub isPreferred: Option<Boolean>,
 
     /**
@@ -8046,11 +13304,13 @@ pub struct CodeAction {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub disabled: Option<CodeActionDisabled>,
 
     /**
      * The workspace edit this code action performs.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub edit: Option<WorkspaceEdit>,
 
     /**
@@ -8058,6 +13318,7 @@ pub struct CodeAction {
      * provides an edit and a command, first the edit is
      * executed and then the command.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub command: Option<Command>,
 
     /**
@@ -8066,41 +13327,64 @@ pub struct CodeAction {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<LSPAny>,
 }
 
+/// Command | CodeAction
+///
+/// `Command` is tried first: it requires a string `command`, which a `CodeAction`
+/// never has (its `command` is a nested [Command] object).
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
+pub enum CodeActionOrCommand {
+    Command(Command),
+    CodeAction(CodeAction),
+}
+
+/// result of `textDocument/codeAction`: `(Command | CodeAction)[] | null`
+pub type CodeActionResponse = Nullable<Vec<CodeActionOrCommand>>;
+
+/// partial result of `textDocument/codeAction`
+pub type CodeActionPartialResult = Vec<CodeActionOrCommand>;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DocumentColorClientCapabilities {
     /**
      * Whether document color supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DocumentColorOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DocumentColorRegistrationOptions {
     /// extends TextDocumentRegistrationOptions
     /**
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends StaticRegistrationOptions
     /**
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 
     /// extends DocumentColorOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -8110,6 +13394,7 @@ pub struct DocumentColorParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -8117,6 +13402,7 @@ pub struct DocumentColorParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -8125,6 +13411,22 @@ pub struct DocumentColorParams {
     pub textDocument: TextDocumentIdentifier,
 }
 
+impl DocumentColorParams {
+    pub fn new(uri: impl Into<DocumentUri>) -> Self {
+        DocumentColorParams {
+            workDoneToken: None,
+            partialResultToken: None,
+            textDocument: TextDocumentIdentifier::new(uri),
+        }
+    }
+}
+
+/// result of `textDocument/documentColor`, a plain array
+pub type DocumentColorResult = Vec<ColorInformation>;
+
+/// partial result of `textDocument/documentColor`
+pub type DocumentColorPartialResult = Vec<ColorInformation>;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ColorInformation {
     /**
@@ -8138,10 +13440,26 @@ pub struct ColorInformation {
     pub color: Color,
 }
 
+impl ColorInformation {
+    /// ```
+    /// use rust_lsp_types::{Color, ColorInformation, Range};
+    ///
+    /// let range = Range::from(((0, 7), (0, 14)));
+    /// let info = ColorInformation::new(range, Color::new(1.0, 0.0, 0.0, 1.0));
+    /// assert_eq!(
+    ///     serde_json::to_value(&info).unwrap()["color"],
+    ///     serde_json::json!({ "red": 1.0, "green": 0.0, "blue": 0.0, "alpha": 1.0 })
+    /// );
+    /// ```
+    pub fn new(range: Range, color: Color) -> Self {
+        ColorInformation { range, color }
+    }
+}
+
 /**
  * Represents a color in RGBA space.
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub struct Color {
     /**
      * The red component of this color in the range [0-1].
@@ -8168,12 +13486,50 @@ pub struct Color {
     pub alpha: Decimal,
 }
 
+impl Color {
+    /// a color with every component clamped into `[0, 1]`
+    pub fn new(red: Decimal, green: Decimal, blue: Decimal, alpha: Decimal) -> Self {
+        Color {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+        .clamped()
+    }
+
+    /// whether every component is in `[0, 1]`; `NaN` is not
+    pub fn is_valid(&self) -> bool {
+        [self.red, self.green, self.blue, self.alpha]
+            .iter()
+            .all(|component| (0.0..=1.0).contains(component))
+    }
+
+    /// clamps every component into `[0, 1]`, reading `NaN` as `0`
+    pub fn clamped(self) -> Self {
+        let clamp = |component: Decimal| {
+            if component.is_nan() {
+                0.0
+            } else {
+                component.clamp(0.0, 1.0)
+            }
+        };
+        Color {
+            red: clamp(self.red),
+            green: clamp(self.green),
+            blue: clamp(self.blue),
+            alpha: clamp(self.alpha),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ColorPresentationParams {
     /// extends WorkDoneProgressParams
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -8181,6 +13537,7 @@ pub struct ColorPresentationParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -8199,6 +13556,9 @@ pub struct ColorPresentationParams {
     pub range: Range,
 }
 
+/// partial result of `textDocument/colorPresentation`
+pub type ColorPresentationPartialResult = Vec<ColorPresentation>;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ColorPresentation {
     /**
@@ -8212,26 +13572,62 @@ pub struct ColorPresentation {
      * this presentation for the color. When omitted the
      * [label](#ColorPresentation.label) is used.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub textEdit: Option<TextEdit>,
     /**
      * An optional array of additional [text edits](#TextEdit) that are applied
      * when selecting this color presentation. Edits must not overlap with the
      * main [edit](#ColorPresentation.textEdit) nor with themselves.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub additionalTextEdits: Option<Vec<TextEdit>>,
 }
 
+impl ColorPresentation {
+    /// ```
+    /// use rust_lsp_types::{ColorPresentation, Range, TextEdit};
+    ///
+    /// let presentation = ColorPresentation::new("#ff0000").with_text_edit(TextEdit {
+    ///     range: Range::from(((0, 7), (0, 14))),
+    ///     newText: "#ff0000".into(),
+    /// });
+    /// let json = serde_json::to_value(&presentation).unwrap();
+    /// assert_eq!(json["label"], "#ff0000");
+    /// assert_eq!(json["textEdit"]["newText"], "#ff0000");
+    /// assert!(json.get("additionalTextEdits").is_none());
+    /// ```
+    pub fn new(label: impl Into<String>) -> Self {
+        ColorPresentation {
+            label: label.into(),
+            textEdit: None,
+            additionalTextEdits: None,
+        }
+    }
+
+    pub fn with_text_edit(mut self, text_edit: TextEdit) -> Self {
+        self.textEdit = Some(text_edit);
+        self
+    }
+
+    pub fn with_additional_text_edits(mut self, text_edits: Vec<TextEdit>) -> Self {
+        self.additionalTextEdits = Some(text_edits);
+        self
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DocumentFormattingClientCapabilities {
     /**
      * Whether formatting supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DocumentFormattingOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -8242,10 +13638,11 @@ pub struct DocumentFormattingRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends DocumentFormattingOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -8255,6 +13652,7 @@ pub struct DocumentFormattingParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /**
@@ -8288,6 +13686,7 @@ pub struct FormattingOptions {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub trimTrailingWhitespace: Option<Boolean>,
 
     /**
@@ -8295,6 +13694,7 @@ pub struct FormattingOptions {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub insertFinalNewline: Option<Boolean>,
 
     /**
@@ -8302,6 +13702,7 @@ pub struct FormattingOptions {
      *
      * @since 3.15.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub trimFinalNewlines: Option<Boolean>,
 
     /**
@@ -8312,17 +13713,78 @@ pub struct FormattingOptions {
     pub additional_properties: BTreeMap<String, Value>,
 }
 
+impl FormattingOptions {
+    /// options with only the required tab settings, no further properties
+    pub fn new(tab_size: UInteger, insert_spaces: Boolean) -> Self {
+        FormattingOptions {
+            tabSize: tab_size,
+            insertSpaces: insert_spaces,
+            trimTrailingWhitespace: None,
+            insertFinalNewline: None,
+            trimFinalNewlines: None,
+            additional_properties: BTreeMap::new(),
+        }
+    }
+
+    pub fn trim_trailing_whitespace(mut self, trim_trailing_whitespace: Boolean) -> Self {
+        self.trimTrailingWhitespace = Some(trim_trailing_whitespace);
+        self
+    }
+
+    pub fn insert_final_newline(mut self, insert_final_newline: Boolean) -> Self {
+        self.insertFinalNewline = Some(insert_final_newline);
+        self
+    }
+
+    pub fn trim_final_newlines(mut self, trim_final_newlines: Boolean) -> Self {
+        self.trimFinalNewlines = Some(trim_final_newlines);
+        self
+    }
+
+    /// adds one of the further properties, e.g. a formatter specific option
+    pub fn with_property(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.additional_properties.insert(key.into(), value);
+        self
+    }
+
+    /// the further property `key` if it's a boolean
+    pub fn get_bool(&self, key: &str) -> Option<Boolean> {
+        match self.additional_properties.get(key)? {
+            Value::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// the further property `key` if it's an integer
+    pub fn get_i32(&self, key: &str) -> Option<Integer> {
+        match self.additional_properties.get(key)? {
+            Value::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// the further property `key` if it's a string
+    pub fn get_string(&self, key: &str) -> Option<&str> {
+        match self.additional_properties.get(key)? {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DocumentRangeFormattingClientCapabilities {
     /**
      * Whether formatting supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DocumentRangeFormattingOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -8333,10 +13795,11 @@ pub struct DocumentRangeFormattingRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends DocumentRangeFormattingOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
@@ -8346,6 +13809,7 @@ pub struct DocumentRangeFormattingParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /**
@@ -8369,10 +13833,11 @@ pub struct DocumentOnTypeFormattingClientCapabilities {
     /**
      * Whether on type formatting supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DocumentOnTypeFormattingOptions {
     /**
      * A character on which formatting should be triggered, like `{`.
@@ -8382,9 +13847,22 @@ pub struct DocumentOnTypeFormattingOptions {
     /**
      * More trigger characters.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub moreTriggerCharacter: Option<Vec<String>>,
 }
 
+impl DocumentOnTypeFormattingOptions {
+    /// whether typing `ch` should trigger `textDocument/onTypeFormatting`
+    pub fn triggers_on(&self, ch: &str) -> bool {
+        self.firstTriggerCharacter == ch
+            || self
+                .moreTriggerCharacter
+                .iter()
+                .flatten()
+                .any(|trigger| trigger == ch)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DocumentOnTypeFormattingRegistrationOptions {
     /// extends TextDocumentRegistrationOptions
@@ -8392,7 +13870,7 @@ pub struct DocumentOnTypeFormattingRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends DocumentOnTypeFormattingOptions
     /**
@@ -8403,6 +13881,7 @@ pub struct DocumentOnTypeFormattingRegistrationOptions {
     /**
      * More trigger characters.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub moreTriggerCharacter: Option<Vec<String>>,
 }
 
@@ -8434,6 +13913,22 @@ pub struct DocumentOnTypeFormattingParams {
     pub options: FormattingOptions,
 }
 
+impl DocumentOnTypeFormattingParams {
+    pub fn new(
+        uri: impl Into<DocumentUri>,
+        position: Position,
+        ch: impl Into<String>,
+        options: FormattingOptions,
+    ) -> Self {
+        DocumentOnTypeFormattingParams {
+            textDocument: TextDocumentIdentifier::new(uri),
+            position,
+            ch: ch.into(),
+            options,
+        }
+    }
+}
+
 #[derive(Serialize_repr, Deserialize_repr, Debug)]
 #[repr(u8)]
 pub enum PrepareSupportDefaultBehavior {
@@ -8449,6 +13944,7 @@ pub struct RenameClientCapabilities {
     /**
      * Whether rename supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
@@ -8457,6 +13953,7 @@ pub struct RenameClientCapabilities {
      *
      * @since version 3.12.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub prepareSupport: Option<Boolean>,
 
     /**
@@ -8468,6 +13965,7 @@ pub struct RenameClientCapabilities {
      *
      * @since version 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub prepareSupportDefaultBehavior: Option<PrepareSupportDefaultBehavior>,
 
     /**
@@ -8479,17 +13977,20 @@ pub struct RenameClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub honorsChangeAnnotations: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct RenameOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
      * Renames should be checked and tested before being executed.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub prepareProvider: Option<Boolean>,
 }
 
@@ -8500,15 +14001,17 @@ pub struct RenameRegistrationOptions {
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends RenameOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
      * Renames should be checked and tested before being executed.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub prepareProvider: Option<Boolean>,
 }
 
@@ -8530,6 +14033,7 @@ pub struct RenameParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /**
@@ -8540,6 +14044,94 @@ pub struct RenameParams {
     pub newName: String,
 }
 
+impl RenameParams {
+    /// ```
+    /// use rust_lsp_types::{Position, RenameParams};
+    ///
+    /// let params = RenameParams::new("file:///src/lib.rs", Position::from((4, 8)), "total");
+    /// assert_eq!(
+    ///     serde_json::to_value(&params).unwrap(),
+    ///     serde_json::json!({
+    ///         "textDocument": { "uri": "file:///src/lib.rs" },
+    ///         "position": { "line": 4, "character": 8 },
+    ///         "newName": "total",
+    ///     })
+    /// );
+    /// ```
+    pub fn new(
+        uri: impl Into<DocumentUri>,
+        position: Position,
+        new_name: impl Into<String>,
+    ) -> Self {
+        RenameParams {
+            textDocument: TextDocumentIdentifier::new(uri),
+            position,
+            workDoneToken: None,
+            newName: new_name.into(),
+        }
+    }
+}
+
+/// whether `name` is acceptable as a [RenameParams::newName]; a server should
+/// answer an invalid one with a [ResponseError] (e.g.
+/// [ErrorCodes::RequestFailed]) carrying a message, as the spec asks.
+///
+/// Without a `pattern` a name is valid if it starts with a letter or `_`
+/// followed by letters, digits and `_`, all in the Unicode sense. A `pattern`
+/// is a regular expression in the syntax of the `regex` crate, searched for
+/// in `name` (anchor it with `^...$` to match the whole name); it needs the
+/// `regex` feature and is an [IdentifierPatternError::Unsupported] error
+/// without it.
+pub fn is_valid_identifier(
+    name: &str,
+    pattern: Option<&str>,
+) -> Result<bool, IdentifierPatternError> {
+    match pattern {
+        Some(pattern) => pattern_matches(name, pattern),
+        None => {
+            let mut chars = name.chars();
+            Ok(chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+                && chars.all(|c| c.is_alphanumeric() || c == '_'))
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+fn pattern_matches(name: &str, pattern: &str) -> Result<bool, IdentifierPatternError> {
+    regex::Regex::new(pattern)
+        .map(|pattern| pattern.is_match(name))
+        .map_err(|error| IdentifierPatternError::Invalid(error.to_string()))
+}
+
+#[cfg(not(feature = "regex"))]
+fn pattern_matches(_name: &str, _pattern: &str) -> Result<bool, IdentifierPatternError> {
+    Err(IdentifierPatternError::Unsupported)
+}
+
+/// why [is_valid_identifier] couldn't check a name against a pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdentifierPatternError {
+    /// patterns need the `regex` feature
+    Unsupported,
+    /// the pattern isn't a valid regular expression, with the reason
+    Invalid(String),
+}
+
+impl std::fmt::Display for IdentifierPatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdentifierPatternError::Unsupported => {
+                write!(f, "identifier patterns need the `regex` feature")
+            }
+            IdentifierPatternError::Invalid(reason) => {
+                write!(f, "invalid identifier pattern: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IdentifierPatternError {}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PrepareRenameParams {
     /// extends TextDocumentPositionParams
@@ -8558,9 +14150,57 @@ pub struct PrepareRenameParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 }
 
+impl PrepareRenameParams {
+    /// ```
+    /// use rust_lsp_types::{Position, PrepareRenameParams};
+    ///
+    /// let params = PrepareRenameParams::new("file:///src/lib.rs", Position::from((4, 8)));
+    /// assert_eq!(
+    ///     serde_json::to_value(&params).unwrap(),
+    ///     serde_json::json!({
+    ///         "textDocument": { "uri": "file:///src/lib.rs" },
+    ///         "position": { "line": 4, "character": 8 },
+    ///     })
+    /// );
+    /// ```
+    pub fn new(uri: impl Into<DocumentUri>, position: Position) -> Self {
+        PrepareRenameParams {
+            textDocument: TextDocumentIdentifier::new(uri),
+            position,
+            workDoneToken: None,
+        }
+    }
+}
+
+/// extracted from the result of `textDocument/prepareRename`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PrepareRenamePlaceholder {
+    pub range: Range,
+    pub placeholder: String,
+}
+
+/// extracted from the result of `textDocument/prepareRename`
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PrepareRenameDefaultBehavior {
+    pub defaultBehavior: Boolean,
+}
+
+/// result of `textDocument/prepareRename`, sent as a
+/// `Nullable<PrepareRenameResult>` since `null` means the position can't be
+/// renamed:
+/// `Range | { range: Range, placeholder: string } | { defaultBehavior: boolean }`
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum PrepareRenameResult {
+    Range(Range),
+    RangeWithPlaceholder(PrepareRenamePlaceholder),
+    DefaultBehavior(PrepareRenameDefaultBehavior),
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct LinkedEditingRangeClientCapabilities {
     /**
@@ -8569,26 +14209,29 @@ pub struct LinkedEditingRangeClientCapabilities {
      * `(TextDocumentRegistrationOptions & StaticRegistrationOptions)`
      * return value for the corresponding server capability as well.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct LinkedEditingRangeOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct LinkedEditingRangeRegistrationOptions {
     /// extends TextDocumentRegistrationOptions
     /**
      * A document selector to identify the scope of the registration. If set to
      * null the document selector provided on the client side will be used.
      */
-    pub documentSelector: Option<DocumentSelector>,
+    pub documentSelector: Nullable<DocumentSelector>,
 
     /// extends LinkedEditingRangeOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends StaticRegistrationOptions
@@ -8596,6 +14239,7 @@ pub struct LinkedEditingRangeRegistrationOptions {
      * The id used to register the request. The id can be used to deregister
      * the request again. See also Registration#id.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
 }
 
@@ -8617,6 +14261,7 @@ pub struct LinkedEditingRangeParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 }
 
@@ -8634,6 +14279,7 @@ pub struct LinkedEditingRanges {
      * contents for the given ranges. If no pattern is provided, the client
      * configuration's word pattern will be used.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wordPattern: Option<String>,
 }
 
@@ -8650,6 +14296,7 @@ pub struct WorkspaceSymbolClientCapabilitiesSymbolKind {
      * the symbol kinds from `File` to `Array` as defined in
      * the initial version of the protocol.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub valueSet: Option<Vec<SymbolKind>>,
 }
 
@@ -8677,12 +14324,14 @@ pub struct WorkspaceSymbolClientCapabilities {
     /**
      * Symbol request supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
      * Specific capabilities for the `SymbolKind` in the `workspace/symbol`
      * request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub symbolKind: Option<WorkspaceSymbolClientCapabilitiesSymbolKind>,
 
     /**
@@ -8691,6 +14340,7 @@ pub struct WorkspaceSymbolClientCapabilities {
      *
      * @since 3.16.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tagSupport: Option<WorkspaceSymbolClientCapabilitiesTagSupport>,
 
     /**
@@ -8700,12 +14350,14 @@ pub struct WorkspaceSymbolClientCapabilities {
      *
      * @since 3.17.0 - proposedState
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveSupport: Option<WorkspaceSymbolClientCapabilitiesResolveSupport>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct WorkspaceSymbolOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /**
@@ -8714,6 +14366,7 @@ pub struct WorkspaceSymbolOptions {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveProvider: Option<Boolean>,
 }
 
@@ -8721,6 +14374,7 @@ pub struct WorkspaceSymbolOptions {
 pub struct WorkspaceSymbolRegistrationOptions {
     /// extends WorkspaceSymbolOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
 
     /// extends WorkspaceSymbolOptions
@@ -8730,6 +14384,7 @@ pub struct WorkspaceSymbolRegistrationOptions {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resolveProvider: Option<Boolean>,
 }
 
@@ -8742,6 +14397,7 @@ pub struct WorkspaceSymbolParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
 
     /// extends PartialResultParams
@@ -8749,6 +14405,7 @@ pub struct WorkspaceSymbolParams {
      * An optional token that a server can use to report partial results (e.g.
      * streaming) to the client.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub partialResultToken: Option<ProgressToken>,
 
     /**
@@ -8765,6 +14422,9 @@ pub struct DocumentUriObject {
 }
 
 /// extracted from [WorkspaceSymbol::location]
+///
+/// `Location` comes first, so a uri without a `range` is a
+/// `DocumentUriObject`
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(untagged)]
 pub enum WorkspaceSymbolLocation {
@@ -8792,6 +14452,7 @@ pub struct WorkspaceSymbol {
     /**
      * Tags for this completion item.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<SymbolTag>>,
 
     /**
@@ -8800,6 +14461,7 @@ pub struct WorkspaceSymbol {
      * if necessary). It can't be used to re-infer a hierarchy for the document
      * symbols.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub containerName: Option<String>,
 
     /**
@@ -8815,6 +14477,7 @@ pub struct WorkspaceSymbol {
      * A data entry field that is preserved on a workspace symbol between a
      * workspace symbol request and a workspace symbol resolve request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<LSPAny>,
 }
 
@@ -8828,11 +14491,13 @@ pub struct ConfigurationItem {
     /**
      * The scope to get the configuration section for.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub scopeUri: Option<URI>,
 
     /**
      * The configuration section asked for.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub section: Option<String>,
 }
 
@@ -8843,6 +14508,7 @@ pub struct DidChangeConfigurationClientCapabilities {
      *
      * @since 3.6.0 to support the new pull model.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
@@ -8854,16 +14520,18 @@ pub struct DidChangeConfigurationParams {
     pub settings: LSPAny,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
 pub enum ChangeNotifications {
     String(String),
     Boolean(Boolean),
 }
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct WorkspaceFoldersServerCapabilities {
     /**
      * The server has support for workspace folders
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub supported: Option<Boolean>,
 
     /**
@@ -8875,10 +14543,11 @@ pub struct WorkspaceFoldersServerCapabilities {
      * side. The ID can be used to unregister for these events
      * using the `client/unregisterCapability` request.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub changeNotifications: Option<ChangeNotifications>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct WorkspaceFolder {
     /**
      * The associated URI for this workspace folder.
@@ -8921,7 +14590,7 @@ pub struct WorkspaceFoldersChangeEvent {
  *
  * @since 3.16.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct FileOperationRegistrationOptions {
     /**
      * The actual filters.
@@ -8936,7 +14605,7 @@ pub struct FileOperationRegistrationOptions {
  * @since 3.16.0
  */
 /// pub type FileOperationPatternKind = 'file' | 'folder';
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub enum FileOperationPatternKind {
     /**
      * The pattern matches a file only.
@@ -8955,11 +14624,12 @@ pub enum FileOperationPatternKind {
  *
  * @since 3.16.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct FileOperationPatternOptions {
     /**
      * The pattern should be matched ignoring casing.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ignoreCase: Option<Boolean>,
 }
 
@@ -8969,7 +14639,7 @@ pub struct FileOperationPatternOptions {
  *
  * @since 3.16.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct FileOperationPattern {
     /**
      * The glob pattern to match. Glob patterns can have the following syntax:
@@ -8991,11 +14661,13 @@ pub struct FileOperationPattern {
      *
      * Matches both if undefined.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub matches: Option<FileOperationPatternKind>,
 
     /**
      * Additional options used during matching.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<FileOperationPatternOptions>,
 }
 
@@ -9005,11 +14677,12 @@ pub struct FileOperationPattern {
  *
  * @since 3.16.0
  */
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct FileOperationFilter {
     /**
      * A Uri like `file` or `untitled`.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub scheme: Option<String>,
 
     /**
@@ -9112,6 +14785,7 @@ pub struct DidChangeWatchedFilesClientCapabilities {
      * Please note that the current protocol doesn't support static
      * configuration for file changes from the server side.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 
     /**
@@ -9120,6 +14794,7 @@ pub struct DidChangeWatchedFilesClientCapabilities {
      *
      * @since 3.17.0
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub relativePatternSupport: Option<Boolean>,
 }
 
@@ -9208,6 +14883,7 @@ pub struct FileSystemWatcher {
      * to WatchKind.Create | WatchKind.Change | WatchKind.Delete
      * which is 7.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<WatchKind>,
 }
 
@@ -9278,12 +14954,14 @@ pub struct ExecuteCommandClientCapabilities {
     /**
      * Execute command supports dynamic registration.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dynamicRegistration: Option<Boolean>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ExecuteCommandOptions {
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
     /**
      * The commands to be executed on the server
@@ -9298,6 +14976,7 @@ pub struct ExecuteCommandOptions {
 pub struct ExecuteCommandRegistrationOptions {
     /// extends extends ExecuteCommandOptions
     /// extends WorkDoneProgressOptions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneProgress: Option<Boolean>,
     /// extends extends ExecuteCommandOptions
     /**
@@ -9312,6 +14991,7 @@ pub struct ExecuteCommandParams {
     /**
      * An optional token that a server can use to report work done progress.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub workDoneToken: Option<ProgressToken>,
     /**
      * The identifier of the actual command handler.
@@ -9320,6 +15000,7 @@ pub struct ExecuteCommandParams {
     /**
      * Arguments that the command should be invoked with.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub arguments: Option<Vec<LSPAny>>,
 }
 
@@ -9330,6 +15011,7 @@ pub struct ApplyWorkspaceEditParams {
      * presented in the user struct for example on an undo
      * stack to undo the workspace edit.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
 
     /**
@@ -9350,6 +15032,7 @@ pub struct ApplyWorkspaceEditResult {
      * This may be used by the server for diagnostic logging or to provide
      * a suitable error for a request that triggered the edit.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub failureReason: Option<String>,
 
     /**
@@ -9358,6 +15041,7 @@ pub struct ApplyWorkspaceEditResult {
      * only available if the client signals a `failureHandling` strategy
      * in its client capabilities.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub failedChange: Option<UInteger>,
 }
 
@@ -9402,6 +15086,14 @@ pub enum MessageType {
     Debug = 5,
 }
 
+impl_display_from_str!(MessageType {
+    Error,
+    Warning,
+    Info,
+    Log,
+    Debug
+});
+
 /// extracted out for [ShowMessageRequestClientCapabilities::messageActionItem]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ShowMessageRequestClientCapabilitiesMessageActionItem {
@@ -9410,6 +15102,7 @@ pub struct ShowMessageRequestClientCapabilitiesMessageActionItem {
      * are preserved and sent back to the server in the
      * request's response.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub additionalPropertiesSupport: Option<Boolean>,
 }
 
@@ -9421,6 +15114,7 @@ pub struct ShowMessageRequestClientCapabilities {
     /**
      * Capabilities specific to the `MessageActionItem` type.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub messageActionItem: Option<ShowMessageRequestClientCapabilitiesMessageActionItem>,
 }
 
@@ -9439,6 +15133,7 @@ pub struct ShowMessageRequestParams {
     /**
      * The message action items to present.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub actions: Option<Vec<MessageActionItem>>,
 }
 
@@ -9481,6 +15176,7 @@ pub struct ShowDocumentParams {
      * To show, for example, `https://code.visualstudio.com/`
      * in the default WEB browser set `external` to `true`.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub external: Option<Boolean>,
 
     /**
@@ -9489,6 +15185,7 @@ pub struct ShowDocumentParams {
      * Clients might ignore this property if an external
      * program is started.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub takeFocus: Option<Boolean>,
 
     /**
@@ -9497,6 +15194,7 @@ pub struct ShowDocumentParams {
      * external program is started or the file is not a text
      * file.
      */
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub selection: Option<Range>,
 }
 
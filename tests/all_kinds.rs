@@ -0,0 +1,36 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn symbol_kind_all_lists_every_kind_in_order() {
+    assert_eq!(SymbolKind::all().len(), 26);
+    for (kind, value) in SymbolKind::all().iter().zip(1..) {
+        assert_eq!(serde_json::to_value(kind).unwrap(), json!(value));
+    }
+}
+
+#[test]
+fn completion_item_kind_all_lists_every_kind_in_order() {
+    assert_eq!(CompletionItemKind::all().len(), 25);
+    for (kind, value) in CompletionItemKind::all().iter().zip(1..) {
+        assert_eq!(serde_json::to_value(kind).unwrap(), json!(value));
+    }
+}
+
+#[test]
+fn diagnostic_tag_all_lists_every_tag() {
+    assert_eq!(
+        serde_json::to_value(DiagnosticTag::all()).unwrap(),
+        json!([1, 2])
+    );
+}
+
+#[test]
+fn code_action_kind_all_lists_only_predefined_kinds() {
+    let all = CodeActionKind::all();
+    assert_eq!(all.len(), 9);
+    assert!(all
+        .iter()
+        .all(|kind| !matches!(kind, CodeActionKind::Other(_))));
+    assert!(all.contains(&CodeActionKind::SourceFixAll));
+}
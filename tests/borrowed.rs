@@ -0,0 +1,122 @@
+#![cfg(feature = "borrowed")]
+
+use rust_lsp_types::borrowed::*;
+use rust_lsp_types::{Position, RequestMessage};
+use serde::Deserialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::borrow::Cow;
+use std::cell::Cell;
+
+/// counts the allocations made on the current thread, so tests running in
+/// parallel don't see each other's
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_in<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let value = f();
+    (value, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct HoverParamsRef<'a> {
+    #[serde(borrow)]
+    textDocument: TextDocumentIdentifierRef<'a>,
+    position: Position,
+}
+
+const REQUEST: &str = r#"{
+    "jsonrpc": "2.0",
+    "id": "request-1",
+    "method": "textDocument/hover",
+    "params": {
+        "textDocument": { "uri": "file:///src/main.rs" },
+        "position": { "line": 3, "character": 14 }
+    }
+}"#;
+
+#[test]
+fn borrowed_parse_does_not_allocate() {
+    let json =
+        r#"{ "jsonrpc": "2.0", "method": "$/cancelRequest", "params": { "id": "request-1" } }"#;
+    for _ in 0..1000 {
+        let (message, allocations) =
+            allocations_in(|| serde_json::from_str::<NotificationMessageRef>(json).unwrap());
+        assert_eq!(allocations, 0);
+        assert!(matches!(message.method, Cow::Borrowed("$/cancelRequest")));
+        assert_eq!(message.params.unwrap().get(), r#"{ "id": "request-1" }"#);
+    }
+}
+
+#[test]
+fn borrowed_parse_allocates_no_more_than_skipping_the_message() {
+    // skipping nested JSON makes serde_json keep a stack of the enclosing
+    // brackets, which a raw `params` pays for as well; everything on top of
+    // that would be ours
+    let (_, skipping) =
+        allocations_in(|| serde_json::from_str::<serde::de::IgnoredAny>(REQUEST).unwrap());
+    let (_, owned) = allocations_in(|| serde_json::from_str::<RequestMessage>(REQUEST).unwrap());
+    assert!(skipping < owned);
+
+    for _ in 0..1000 {
+        let ((message, params), allocations) = allocations_in(|| {
+            let message: RequestMessageRef = serde_json::from_str(REQUEST).unwrap();
+            let params: HoverParamsRef = message.params().unwrap().unwrap();
+            (message, params)
+        });
+        assert!(allocations <= skipping, "{allocations} > {skipping}");
+
+        assert!(matches!(
+            message.method,
+            Cow::Borrowed("textDocument/hover")
+        ));
+        assert!(matches!(
+            message.id,
+            RequestIdRef::String(Cow::Borrowed("request-1"))
+        ));
+        assert!(matches!(
+            params.textDocument.uri,
+            Cow::Borrowed("file:///src/main.rs")
+        ));
+        assert_eq!(
+            params.position,
+            Position {
+                line: 3,
+                character: 14
+            }
+        );
+    }
+}
+
+#[test]
+fn escaped_strings_fall_back_to_owned() {
+    let json = r#"{ "jsonrpc": "2.0", "method": "custom\/action" }"#;
+    let message: NotificationMessageRef = serde_json::from_str(json).unwrap();
+    assert!(matches!(message.method, Cow::Owned(ref method) if method == "custom/action"));
+    assert!(matches!(message.jsonrpc, Cow::Borrowed("2.0")));
+}
+
+#[test]
+fn borrowed_identifier_converts_to_owned() {
+    let identifier: TextDocumentIdentifierRef =
+        serde_json::from_str(r#"{ "uri": "file:///src/main.rs" }"#).unwrap();
+    assert_eq!(identifier.into_owned().uri, "file:///src/main.rs");
+}
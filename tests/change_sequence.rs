@@ -0,0 +1,75 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+fn full(text: &str) -> serde_json::Value {
+    json!({ "text": text })
+}
+
+fn edit(start: (u32, u32), end: (u32, u32), text: &str) -> serde_json::Value {
+    json!({ "range": Range::from((start, end)), "text": text })
+}
+
+fn changes(changes: Vec<serde_json::Value>) -> Vec<TextDocumentContentChangeEvent> {
+    serde_json::from_value(changes.into()).unwrap()
+}
+
+#[test]
+fn consistent_sequence_is_valid() {
+    let changes = changes(vec![
+        full("fn main() {\n}\n"),
+        // adds a line, so line 2 of the original becomes line 3
+        edit((0, 11), (0, 11), "\n    println!();"),
+        edit((3, 0), (3, 0), "// end"),
+    ]);
+    assert_eq!(validate_change_sequence(&changes), Ok(()));
+}
+
+#[test]
+fn ranges_without_a_full_change_are_only_checked_for_inversion() {
+    let changes = changes(vec![edit((100, 0), (100, 4), "")]);
+    assert_eq!(validate_change_sequence(&changes), Ok(()));
+}
+
+#[test]
+fn inverted_range_is_rejected() {
+    let changes = changes(vec![edit((0, 0), (0, 1), "a"), edit((2, 5), (2, 1), "b")]);
+    assert_eq!(
+        validate_change_sequence(&changes),
+        Err(ChangeOrderError::InvertedRange { change: 1 })
+    );
+}
+
+#[test]
+fn line_past_the_end_after_a_full_change_is_rejected() {
+    let changes = changes(vec![
+        edit((5, 0), (5, 0), "x"),
+        full("one\ntwo"),
+        edit((2, 0), (2, 0), "three"),
+    ]);
+    assert_eq!(
+        validate_change_sequence(&changes),
+        Err(ChangeOrderError::LineOutOfBounds {
+            change: 2,
+            line: 2,
+            line_count: 2
+        })
+    );
+}
+
+#[test]
+fn line_count_follows_edits_removing_lines() {
+    let changes = changes(vec![
+        full("a\nb\nc\n"),
+        // joins lines 0 to 2, leaving "a\n" and an empty last line
+        edit((0, 1), (2, 1), ""),
+        edit((2, 0), (2, 0), "x"),
+    ]);
+    assert_eq!(
+        validate_change_sequence(&changes),
+        Err(ChangeOrderError::LineOutOfBounds {
+            change: 2,
+            line: 2,
+            line_count: 2
+        })
+    );
+}
@@ -0,0 +1,38 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn color_component_of_1_5_is_clamped() {
+    let color = Color {
+        red: 1.5,
+        green: 0.5,
+        blue: -0.25,
+        alpha: f64::NAN,
+    };
+    assert!(!color.is_valid());
+
+    let clamped = color.clamped();
+    assert!(clamped.is_valid());
+    assert_eq!(
+        (clamped.red, clamped.green, clamped.blue, clamped.alpha),
+        (1.0, 0.5, 0.0, 0.0)
+    );
+    assert_eq!(Color::new(1.5, 0.0, 0.0, 1.0).red, 1.0);
+}
+
+#[test]
+fn progress_of_150_percent_is_clamped() {
+    let begin = WorkDoneProgressBegin::new("indexing").with_percentage(150);
+    assert_eq!(begin.percentage, Some(100));
+
+    let report = WorkDoneProgressReport::new().with_percentage(150);
+    assert_eq!(
+        serde_json::to_value(&report).unwrap(),
+        json!({ "kind": "report", "percentage": 100 })
+    );
+
+    assert_eq!(
+        WorkDoneProgressReport::new().with_percentage(42).percentage,
+        Some(42)
+    );
+}
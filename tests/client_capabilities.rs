@@ -0,0 +1,42 @@
+use rust_lsp_types::*;
+
+#[test]
+fn full_capabilities_round_trip() {
+    let json = serde_json::to_value(ClientCapabilities::full()).unwrap();
+    let back: ClientCapabilities = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(back).unwrap(), json);
+}
+
+#[test]
+fn full_capabilities_support_everything() {
+    let full = ClientCapabilities::full();
+    assert!(full.supports_hierarchical_document_symbols());
+    assert!(full.supports_snippet_completion());
+    assert!(full.supports_work_done_progress());
+    assert!(full.supports_workspace_folders());
+    assert!(full.supports_configuration());
+}
+
+#[test]
+fn minimal_capabilities_support_nothing() {
+    let minimal = ClientCapabilities::minimal();
+    assert!(!minimal.supports_snippet_completion());
+    assert!(!minimal.supports_workspace_folders());
+    assert_eq!(serde_json::to_string(&minimal).unwrap(), "{}");
+}
+
+#[test]
+fn omitted_capability_reads_as_disabled() {
+    let sync: TextDocumentSyncClientCapabilities =
+        serde_json::from_value(serde_json::json!({ "didSave": true })).unwrap();
+    assert!(sync.did_save_enabled());
+    assert!(!sync.will_save_enabled());
+    assert!(!sync.will_save_wait_until_enabled());
+    assert!(!sync.dynamic_registration_enabled());
+
+    let workspace: ClientCapabilitiesWorkspace =
+        serde_json::from_value(serde_json::json!({ "applyEdit": false })).unwrap();
+    assert!(!workspace.apply_edit_enabled());
+    assert!(!workspace.workspace_folders_enabled());
+    assert!(!workspace.configuration_enabled());
+}
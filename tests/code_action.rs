@@ -0,0 +1,94 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn quickfix_only_code_action_params() {
+    let range = Range::from(((4, 0), (4, 12)));
+    let context = CodeActionContext::new(vec![Diagnostic::new(range, "unused variable")])
+        .only(vec![CodeActionKind::QuickFix])
+        .trigger_kind(CodeActionTriggerKind::Invoked);
+    let params = CodeActionParams::new("file:///src/lib.rs", range, context);
+
+    let json = serde_json::to_value(&params).unwrap();
+    assert_eq!(json["textDocument"]["uri"], "file:///src/lib.rs");
+    assert_eq!(json["context"]["only"], json!(["quickfix"]));
+    assert_eq!(json["context"]["triggerKind"], 1);
+    assert_eq!(
+        json["context"]["diagnostics"][0]["message"],
+        "unused variable"
+    );
+
+    let back: CodeActionParams = serde_json::from_value(json).unwrap();
+    assert_eq!(back.context.only, Some(vec![CodeActionKind::QuickFix]));
+}
+
+#[test]
+fn code_action_context_omits_unset_filters() {
+    assert_eq!(
+        serde_json::to_value(CodeActionContext::new(Vec::new())).unwrap(),
+        json!({ "diagnostics": [] })
+    );
+}
+
+#[test]
+fn mixed_commands_and_code_actions() {
+    let json = json!([
+        { "title": "Organize imports", "command": "editor.organizeImports" },
+        {
+            "title": "Remove unused variable",
+            "kind": "quickfix",
+            "command": { "title": "Remove", "command": "rust.removeUnused", "arguments": [4] }
+        },
+        { "title": "Extract function", "kind": "refactor.extract" }
+    ]);
+    let response: CodeActionResponse = serde_json::from_value(json.clone()).unwrap();
+    let Nullable::Value(items) = &response else {
+        panic!("expected a list of actions");
+    };
+    assert_eq!(items.len(), 3);
+
+    let CodeActionOrCommand::Command(command) = &items[0] else {
+        panic!("expected a command");
+    };
+    assert_eq!(command.command, "editor.organizeImports");
+
+    let CodeActionOrCommand::CodeAction(action) = &items[1] else {
+        panic!("expected a code action with a nested command");
+    };
+    assert_eq!(action.kind, Some(CodeActionKind::QuickFix));
+    assert_eq!(
+        action.command.as_ref().map(|c| c.command.as_str()),
+        Some("rust.removeUnused")
+    );
+
+    assert!(matches!(&items[2], CodeActionOrCommand::CodeAction(a) if a.command.is_none()));
+    assert_eq!(serde_json::to_value(&response).unwrap(), json);
+}
+
+#[test]
+fn kind_contains_itself_and_its_sub_kinds() {
+    assert!(CodeActionKind::Refactor.contains(&CodeActionKind::Refactor));
+    assert!(CodeActionKind::Refactor.contains(&CodeActionKind::RefactorExtract));
+    assert!(CodeActionKind::Refactor.contains(&CodeActionKind::RefactorRewrite));
+    assert!(!CodeActionKind::RefactorExtract.contains(&CodeActionKind::Refactor));
+
+    assert!(!CodeActionKind::QuickFix.contains(&CodeActionKind::Refactor));
+    assert!(!CodeActionKind::QuickFix.contains(&CodeActionKind::RefactorExtract));
+    assert!(!CodeActionKind::Source.contains(&CodeActionKind::QuickFix));
+}
+
+#[test]
+fn other_kinds_follow_the_same_hierarchy() {
+    let function = CodeActionKind::Other("refactor.extract.function".into());
+    assert!(CodeActionKind::Refactor.contains(&function));
+    assert!(CodeActionKind::RefactorExtract.contains(&function));
+    assert!(!function.contains(&CodeActionKind::RefactorExtract));
+
+    // a shared prefix isn't enough, it has to end at a `.`
+    let refactoring = CodeActionKind::Other("refactoring".into());
+    assert!(!CodeActionKind::Refactor.contains(&refactoring));
+
+    let custom = CodeActionKind::Other("rust.clippy".into());
+    assert!(custom.contains(&CodeActionKind::Other("rust.clippy.fix".into())));
+    assert!(!custom.contains(&CodeActionKind::QuickFix));
+}
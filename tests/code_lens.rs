@@ -0,0 +1,46 @@
+use rust_lsp_types::*;
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Serialize)]
+struct RunTest<'a> {
+    name: &'a str,
+    line: u32,
+}
+
+#[test]
+fn unresolved_lens_resolves_to_a_command() {
+    let range = Range::from(((12, 0), (12, 14)));
+    let lens = CodeLens::new(range).with_data(LSPAny::from("tests::parses"));
+    assert_eq!(
+        serde_json::to_value(&lens).unwrap(),
+        json!({ "range": range, "data": "tests::parses" })
+    );
+
+    let command = Command::new("Run test", "rust.runTest")
+        .with_argument(RunTest {
+            name: "tests::parses",
+            line: 12,
+        })
+        .unwrap()
+        .with_argument(Option::<String>::None)
+        .unwrap();
+    let resolved = lens.resolve(command);
+    assert!(lens.command.is_none());
+    assert_eq!(resolved.range, lens.range);
+    assert_eq!(resolved.data, lens.data);
+
+    // arguments are sent as plain json, not tagged with their LSPAny variant
+    assert_eq!(
+        serde_json::to_value(&resolved).unwrap(),
+        json!({
+            "range": range,
+            "command": {
+                "title": "Run test",
+                "command": "rust.runTest",
+                "arguments": [{ "name": "tests::parses", "line": 12 }, null]
+            },
+            "data": "tests::parses"
+        })
+    );
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "lsp-types-compat")]
+
+use rust_lsp_types::*;
+
+#[test]
+fn position_converts_both_ways() {
+    let position = Position {
+        line: 3,
+        character: 7,
+    };
+    let upstream: ::lsp_types::Position = position.into();
+    assert_eq!(upstream, ::lsp_types::Position::new(3, 7));
+    assert_eq!(Position::from(upstream), position);
+}
+
+#[test]
+fn range_converts_both_ways() {
+    let upstream = ::lsp_types::Range::new(
+        ::lsp_types::Position::new(0, 1),
+        ::lsp_types::Position::new(2, 3),
+    );
+    let range = Range::from(upstream);
+    assert_eq!(range.end.character, 3);
+    assert_eq!(::lsp_types::Range::from(range), upstream);
+}
+
+#[test]
+fn location_needs_a_valid_uri_upstream() {
+    let range = Range::from(::lsp_types::Range::default());
+    let location = Location::new("file:///tmp/a.rs", range);
+    let upstream = ::lsp_types::Location::try_from(location.clone()).unwrap();
+    assert_eq!(upstream.uri.as_str(), "file:///tmp/a.rs");
+    assert_eq!(Location::try_from(upstream).unwrap(), location);
+
+    assert!(::lsp_types::Location::try_from(Location::new("not a uri", range)).is_err());
+}
+
+#[test]
+fn diagnostic_converts_both_ways() {
+    let diagnostic =
+        Diagnostic::new(Range::from(::lsp_types::Range::default()), "unused").with_code("E0001");
+    let upstream = ::lsp_types::Diagnostic::try_from(diagnostic.clone()).unwrap();
+    assert_eq!(upstream.message, "unused");
+    assert_eq!(
+        upstream.code,
+        Some(::lsp_types::NumberOrString::String("E0001".into()))
+    );
+    assert_eq!(Diagnostic::try_from(upstream).unwrap(), diagnostic);
+}
+
+#[test]
+fn negative_severities_do_not_convert() {
+    let mut upstream =
+        ::lsp_types::Diagnostic::new_simple(::lsp_types::Range::default(), "x".into());
+    upstream.severity = Some(::lsp_types::DiagnosticSeverity::ERROR);
+    assert_eq!(
+        Diagnostic::try_from(upstream.clone()).unwrap().severity,
+        Some(DiagnosticSeverity::Error)
+    );
+
+    upstream.severity = serde_json::from_str("-1").ok();
+    assert!(Diagnostic::try_from(upstream).is_err());
+}
+
+#[test]
+fn completion_item_drops_text_edit_text_upstream() {
+    let mut item: CompletionItem =
+        serde_json::from_str(r#"{"label":"foo","kind":3,"textEditText":"foo()"}"#).unwrap();
+    let upstream = ::lsp_types::CompletionItem::try_from(item).unwrap();
+    assert_eq!(upstream.label, "foo");
+    assert_eq!(
+        upstream.kind,
+        Some(::lsp_types::CompletionItemKind::FUNCTION)
+    );
+
+    item = CompletionItem::try_from(upstream).unwrap();
+    assert_eq!(item.kind, Some(CompletionItemKind::Function));
+    assert_eq!(item.textEditText, None);
+}
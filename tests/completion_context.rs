@@ -0,0 +1,31 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn constructors_set_kind_and_character() {
+    assert_eq!(
+        serde_json::to_value(CompletionContext::invoked()).unwrap(),
+        json!({ "triggerKind": 1 })
+    );
+    assert_eq!(
+        serde_json::to_value(CompletionContext::trigger_character(".")).unwrap(),
+        json!({ "triggerKind": 2, "triggerCharacter": "." })
+    );
+    assert_eq!(
+        serde_json::to_value(CompletionContext::incomplete()).unwrap(),
+        json!({ "triggerKind": 3 })
+    );
+}
+
+#[test]
+fn trigger_character_keeps_the_character() {
+    let context = CompletionContext::trigger_character("::");
+    assert!(matches!(
+        context.triggerKind,
+        CompletionTriggerKind::TriggerCharacter
+    ));
+    assert_eq!(context.triggerCharacter.as_deref(), Some("::"));
+
+    assert!(CompletionContext::invoked().triggerCharacter.is_none());
+    assert!(CompletionContext::incomplete().triggerCharacter.is_none());
+}
@@ -0,0 +1,84 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+fn caps(json: serde_json::Value) -> CompletionClientCapabilities {
+    serde_json::from_value(json).unwrap()
+}
+
+fn insert() -> Range {
+    Range::from(((2, 4), (2, 8)))
+}
+
+fn replace() -> Range {
+    Range::from(((2, 4), (2, 12)))
+}
+
+#[test]
+fn insert_replace_support_gives_an_insert_replace_edit() {
+    let caps = caps(json!({ "completionItem": { "insertReplaceSupport": true } }));
+    let edit = completion_edit(insert(), replace(), "println!".into(), &caps);
+    assert!(matches!(
+        edit,
+        CompletionItemEditKind::InsertReplaceEdit(ref edit)
+            if edit.insert == insert() && edit.replace == replace() && edit.newText == "println!"
+    ));
+}
+
+#[test]
+fn without_insert_replace_support_the_edit_covers_replace() {
+    for caps in [
+        caps(json!({})),
+        caps(json!({ "completionItem": {} })),
+        caps(json!({ "completionItem": { "insertReplaceSupport": false } })),
+    ] {
+        let edit = completion_edit(insert(), replace(), "println!".into(), &caps);
+        assert!(matches!(
+            edit,
+            CompletionItemEditKind::TextEdit(ref edit)
+                if edit.range == replace() && edit.newText == "println!"
+        ));
+    }
+}
+
+fn text_edit(range: Range) -> TextEdit {
+    TextEdit {
+        range,
+        newText: "println!".into(),
+    }
+}
+
+#[test]
+fn edit_containing_the_position_is_valid() {
+    let position = Position::from((2, 6));
+    assert_eq!(
+        validate_completion_edit(&text_edit(replace()), &position),
+        Ok(())
+    );
+    // the ends of the range count as inside
+    assert_eq!(
+        validate_completion_edit(&text_edit(replace()), &Position::from((2, 12))),
+        Ok(())
+    );
+}
+
+#[test]
+fn multi_line_edit_is_rejected() {
+    let edit = text_edit(Range::from(((2, 4), (3, 0))));
+    assert_eq!(
+        validate_completion_edit(&edit, &Position::from((2, 6))),
+        Err(CompletionEditError::MultiLine)
+    );
+}
+
+#[test]
+fn edit_not_containing_the_position_is_rejected() {
+    let edit = text_edit(replace());
+    assert_eq!(
+        validate_completion_edit(&edit, &Position::from((2, 13))),
+        Err(CompletionEditError::PositionOutside)
+    );
+    assert_eq!(
+        validate_completion_edit(&edit, &Position::from((1, 6))),
+        Err(CompletionEditError::PositionOutside)
+    );
+}
@@ -0,0 +1,36 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn streamed_chunks_accumulate_into_the_list() {
+    let first: CompletionPartialResult =
+        serde_json::from_value(json!([{ "label": "push" }, { "label": "pop" }])).unwrap();
+    let second: CompletionPartialResult =
+        serde_json::from_value(json!([{ "label": "len" }])).unwrap();
+
+    let mut list: CompletionList =
+        serde_json::from_value(json!({ "isIncomplete": true, "items": [] })).unwrap();
+    list.append_items(first);
+    list.append_items(second);
+
+    let labels: Vec<&str> = list.items.iter().map(|item| item.label.as_str()).collect();
+    assert_eq!(labels, ["push", "pop", "len"]);
+    assert!(list.isIncomplete);
+}
+
+#[test]
+fn appending_keeps_the_items_already_in_the_list() {
+    let mut list: CompletionList = serde_json::from_value(json!({
+        "isIncomplete": false,
+        "items": [{ "label": "iter" }],
+    }))
+    .unwrap();
+    list.append_items(vec![CompletionItem::new("into_iter")]);
+    assert_eq!(
+        serde_json::to_value(&list).unwrap(),
+        json!({
+            "isIncomplete": false,
+            "items": [{ "label": "iter" }, { "label": "into_iter" }],
+        })
+    );
+}
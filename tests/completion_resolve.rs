@@ -0,0 +1,30 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn completion_item_tag_99_round_trips() {
+    let tag: CompletionItemTag = serde_json::from_value(json!(99)).unwrap();
+    assert_eq!(tag, CompletionItemTag::Unknown(99));
+    assert_eq!(serde_json::to_value(tag).unwrap(), json!(99));
+}
+
+#[test]
+fn resolve_keeps_unknown_completion_item_tags() {
+    // what a client sends back in `completionItem/resolve`
+    let json = json!({
+        "label": "old_api",
+        "tags": [1, 99],
+        "data": { "id": 7 },
+    });
+    let item: CompletionItem = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(
+        item.tags.as_deref(),
+        Some(
+            &[
+                CompletionItemTag::Deprecated,
+                CompletionItemTag::Unknown(99)
+            ][..]
+        )
+    );
+    assert_eq!(serde_json::to_value(&item).unwrap(), json);
+}
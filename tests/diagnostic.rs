@@ -0,0 +1,90 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn add_related_appends_in_order() {
+    let mut diagnostic = Diagnostic::new(Range::from(((3, 8), (3, 11))), "unused variable `foo`");
+    assert!(diagnostic.relatedInformation.is_none());
+
+    diagnostic.add_related(DiagnosticRelatedInformation::new(
+        Location::new("file:///src/lib.rs", Range::from(((1, 4), (1, 7)))),
+        "`foo` is declared here",
+    ));
+    diagnostic.add_related(DiagnosticRelatedInformation::new(
+        Location::new("file:///src/main.rs", Range::from(((9, 0), (9, 3)))),
+        "and shadowed here",
+    ));
+
+    let json = serde_json::to_value(&diagnostic).unwrap();
+    assert_eq!(
+        json["relatedInformation"],
+        json!([
+            {
+                "location": {
+                    "uri": "file:///src/lib.rs",
+                    "range": { "start": { "line": 1, "character": 4 }, "end": { "line": 1, "character": 7 } }
+                },
+                "message": "`foo` is declared here"
+            },
+            {
+                "location": {
+                    "uri": "file:///src/main.rs",
+                    "range": { "start": { "line": 9, "character": 0 }, "end": { "line": 9, "character": 3 } }
+                },
+                "message": "and shadowed here"
+            }
+        ])
+    );
+}
+
+#[test]
+fn code_and_code_description_serialize_together() {
+    let diagnostic = Diagnostic::new(Range::from(((0, 0), (0, 5))), "mismatched types")
+        .with_code("E0308")
+        .with_code_description(CodeDescription::new(
+            "https://doc.rust-lang.org/error_codes/E0308.html",
+        ));
+    let json = serde_json::to_value(&diagnostic).unwrap();
+    assert_eq!(json["code"], "E0308");
+    assert_eq!(
+        json["codeDescription"],
+        json!({ "href": "https://doc.rust-lang.org/error_codes/E0308.html" })
+    );
+
+    let back: Diagnostic = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        back.codeDescription,
+        Some(CodeDescription::new(
+            "https://doc.rust-lang.org/error_codes/E0308.html"
+        ))
+    );
+}
+
+#[test]
+fn with_code_takes_a_number_or_a_string() {
+    let range = Range::from(((0, 0), (0, 1)));
+    let numeric = Diagnostic::new(range, "numeric").with_code(42);
+    assert_eq!(numeric.code, Some(IntegerOrString::Integer(42)));
+    assert_eq!(serde_json::to_value(&numeric).unwrap()["code"], 42);
+
+    let string = Diagnostic::new(range, "string").with_code("E0277");
+    assert_eq!(string.code, Some(IntegerOrString::String("E0277".into())));
+    assert_eq!(serde_json::to_value(&string).unwrap()["code"], "E0277");
+}
+
+#[test]
+fn full_report_serializes_its_kind() {
+    let report = FullDocumentDiagnosticReport::new(vec![Diagnostic::new(
+        Range::from(((2, 0), (2, 4))),
+        "dead code",
+    )]);
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["kind"], "full");
+    assert_eq!(json["items"][0]["message"], "dead code");
+    assert!(json.get("resultId").is_none());
+
+    assert_eq!(
+        serde_json::to_value(FullDocumentDiagnosticReport::new(Vec::new())).unwrap(),
+        json!({ "kind": "full", "items": [] })
+    );
+}
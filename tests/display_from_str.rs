@@ -0,0 +1,107 @@
+use rust_lsp_types::*;
+use serde::Serialize;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// checks that every variant parses back from its `to_string`, comparing
+/// the wire values since the enums don't implement `PartialEq`
+fn assert_round_trips<T>(variants: impl IntoIterator<Item = (T, &'static str)>)
+where
+    T: Display + FromStr<Err = String> + Serialize,
+{
+    for (variant, text) in variants {
+        assert_eq!(variant.to_string(), text);
+        let parsed: T = text.parse().unwrap();
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            serde_json::to_value(&variant).unwrap()
+        );
+    }
+}
+
+#[test]
+fn text_document_save_reason_round_trips() {
+    assert_round_trips([
+        (TextDocumentSaveReason::Manual, "Manual"),
+        (TextDocumentSaveReason::AfterDelay, "AfterDelay"),
+        (TextDocumentSaveReason::FocusOut, "FocusOut"),
+    ]);
+}
+
+#[test]
+fn completion_trigger_kind_round_trips() {
+    assert_round_trips([
+        (CompletionTriggerKind::Invoked, "Invoked"),
+        (CompletionTriggerKind::TriggerCharacter, "TriggerCharacter"),
+        (
+            CompletionTriggerKind::TriggerForIncompleteCompletions,
+            "TriggerForIncompleteCompletions",
+        ),
+    ]);
+}
+
+#[test]
+fn signature_help_trigger_kind_round_trips() {
+    assert_round_trips([
+        (SignatureHelpTriggerKind::Invoked, "Invoked"),
+        (
+            SignatureHelpTriggerKind::TriggerCharacter,
+            "TriggerCharacter",
+        ),
+        (SignatureHelpTriggerKind::ContentChange, "ContentChange"),
+    ]);
+}
+
+#[test]
+fn code_action_trigger_kind_round_trips() {
+    assert_round_trips([
+        (CodeActionTriggerKind::Invoked, "Invoked"),
+        (CodeActionTriggerKind::Automatic, "Automatic"),
+    ]);
+}
+
+#[test]
+fn message_type_round_trips() {
+    assert_round_trips([
+        (MessageType::Error, "Error"),
+        (MessageType::Warning, "Warning"),
+        (MessageType::Info, "Info"),
+        (MessageType::Log, "Log"),
+        (MessageType::Debug, "Debug"),
+    ]);
+}
+
+#[test]
+fn unknown_names_are_rejected() {
+    assert!("Fatal".parse::<MessageType>().is_err());
+    assert!("manual".parse::<TextDocumentSaveReason>().is_err());
+    assert!("1".parse::<CodeActionTriggerKind>().is_err());
+}
+
+#[test]
+fn moniker_enums_use_their_wire_names() {
+    let levels = [
+        (UniquenessLevel::Document, "document"),
+        (UniquenessLevel::Project, "project"),
+        (UniquenessLevel::Group, "group"),
+        (UniquenessLevel::Scheme, "scheme"),
+        (UniquenessLevel::Global, "global"),
+    ];
+    for (level, text) in levels {
+        assert_eq!(serde_json::to_value(level).unwrap(), text);
+    }
+    assert_round_trips(levels);
+
+    let kinds = [
+        (MonikerKind::Import, "import"),
+        (MonikerKind::Export, "export"),
+        (MonikerKind::Local, "local"),
+    ];
+    for (kind, text) in kinds {
+        assert_eq!(serde_json::to_value(kind).unwrap(), text);
+    }
+    assert_round_trips(kinds);
+
+    assert!("Global".parse::<UniquenessLevel>().is_err());
+    assert!("Import".parse::<MonikerKind>().is_err());
+}
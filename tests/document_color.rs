@@ -0,0 +1,42 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn document_color_params_round_trip() {
+    let params = DocumentColorParams::new("file:///styles/main.css");
+    let json = serde_json::to_value(&params).unwrap();
+    assert_eq!(
+        json,
+        json!({ "textDocument": { "uri": "file:///styles/main.css" } })
+    );
+
+    let back: DocumentColorParams = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(back.textDocument.uri, "file:///styles/main.css");
+    assert_eq!(serde_json::to_value(&back).unwrap(), json);
+}
+
+#[test]
+fn two_color_result_round_trips() {
+    let result: DocumentColorResult = vec![
+        ColorInformation::new(((0, 7), (0, 14)).into(), Color::new(1.0, 0.0, 0.0, 1.0)),
+        ColorInformation::new(((3, 11), (3, 29)).into(), Color::new(0.0, 0.5, 1.0, 0.25)),
+    ];
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(
+        json,
+        json!([
+            {
+                "range": { "start": { "line": 0, "character": 7 }, "end": { "line": 0, "character": 14 } },
+                "color": { "red": 1.0, "green": 0.0, "blue": 0.0, "alpha": 1.0 },
+            },
+            {
+                "range": { "start": { "line": 3, "character": 11 }, "end": { "line": 3, "character": 29 } },
+                "color": { "red": 0.0, "green": 0.5, "blue": 1.0, "alpha": 0.25 },
+            },
+        ])
+    );
+
+    let back: DocumentColorResult = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(back.len(), 2);
+    assert_eq!(serde_json::to_value(&back).unwrap(), json);
+}
@@ -0,0 +1,63 @@
+use rust_lsp_types::*;
+
+fn pattern_filter(pattern: &str) -> DocumentFilter {
+    DocumentFilter {
+        language: None,
+        scheme: None,
+        pattern: Some(pattern.to_owned()),
+    }
+}
+
+#[test]
+fn document_filter_matches_decoded_paths() {
+    let filter = pattern_filter("**/my file.ts");
+    assert!(filter.matches("file:///tmp/my%20file.ts", "typescript"));
+    assert!(!filter.matches("file:///tmp/my%20files.ts", "typescript"));
+
+    let filter = pattern_filter("**/a#b.ts");
+    assert!(filter.matches("file:///tmp/a%23b.ts", "typescript"));
+    assert!(!filter.matches("file:///tmp/a#b.ts", "typescript"));
+}
+
+#[test]
+fn document_filter_ignores_the_fragment() {
+    let filter = pattern_filter("**/*.ts");
+    assert!(filter.matches("file:///tmp/a.ts#L10", "typescript"));
+}
+
+#[test]
+fn notebook_document_filter_matches_decoded_paths() {
+    let filter =
+        NotebookDocumentFilter::new(None, None, Some("**/my notebook.ipynb".into())).unwrap();
+    assert!(filter.matches("file:///tmp/my%20notebook.ipynb", "jupyter-notebook"));
+}
+
+#[cfg(unix)]
+#[test]
+fn file_paths_round_trip_through_filters() {
+    let uri = document_uri_from_file_path(std::path::Path::new("/tmp/my file#1.ts")).unwrap();
+    assert_eq!(uri, "file:///tmp/my%20file%231.ts");
+    assert!(pattern_filter("**/my file#1.ts").matches(&uri, "typescript"));
+}
+
+#[test]
+fn notebook_document_filter_with_only_a_notebook_type() {
+    let filter = NotebookDocumentFilter::new(Some("jupyter-notebook".into()), None, None).unwrap();
+    assert!(filter.matches("file:///tmp/a.ipynb", "jupyter-notebook"));
+    assert!(filter.matches("untitled:Untitled-1", "jupyter-notebook"));
+    assert!(!filter.matches("file:///tmp/a.ipynb", "interactive"));
+    assert_eq!(
+        serde_json::to_value(&filter).unwrap(),
+        serde_json::json!({ "notebookType": "jupyter-notebook" })
+    );
+}
+
+#[test]
+fn notebook_document_filter_without_fields_is_rejected() {
+    assert!(NotebookDocumentFilter::new(None, None, None).is_err());
+    assert!(serde_json::from_str::<NotebookDocumentFilter>("{}").is_err());
+
+    let filter: NotebookDocumentFilter =
+        serde_json::from_str(r#"{ "notebookType": "jupyter-notebook" }"#).unwrap();
+    assert_eq!(filter.notebookType.as_deref(), Some("jupyter-notebook"));
+}
@@ -0,0 +1,32 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn unresolved_link_needs_resolve() {
+    let link = DocumentLink::new(Range::from(((0, 10), (0, 24))));
+    assert!(link.needs_resolve());
+    assert_eq!(
+        serde_json::to_value(&link).unwrap(),
+        json!({ "range": { "start": { "line": 0, "character": 10 }, "end": { "line": 0, "character": 24 } } })
+    );
+}
+
+#[test]
+fn resolving_fills_in_the_target() {
+    let unresolved: DocumentLink = serde_json::from_value(json!({
+        "range": { "start": { "line": 2, "character": 4 }, "end": { "line": 2, "character": 16 } },
+        "data": { "crate": "serde" }
+    }))
+    .unwrap();
+    assert!(unresolved.needs_resolve());
+
+    let resolved = unresolved
+        .with_target("https://docs.rs/serde")
+        .with_tooltip("Open the serde docs");
+    assert!(!resolved.needs_resolve());
+
+    let json = serde_json::to_value(&resolved).unwrap();
+    assert_eq!(json["target"], "https://docs.rs/serde");
+    assert_eq!(json["tooltip"], "Open the serde docs");
+    assert_eq!(json["data"], json!({ "crate": "serde" }));
+}
@@ -0,0 +1,53 @@
+use rust_lsp_types::*;
+
+fn diagnostics_batch(uri: &DocumentUri) -> Vec<PublishDiagnosticsParams> {
+    let range = Range {
+        start: Position {
+            line: 0,
+            character: 0,
+        },
+        end: Position {
+            line: 0,
+            character: 1,
+        },
+    };
+    (0..3)
+        .map(|i| {
+            PublishDiagnosticsParams::new(
+                uri.clone(),
+                vec![Diagnostic::new(range, format!("error {i}"))],
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn document_uri_serializes_as_a_plain_string() {
+    let uri = DocumentUri::from("file:///tmp/a.rs");
+    let batch = diagnostics_batch(&uri);
+    let json = serde_json::to_value(&batch[0]).unwrap();
+    assert_eq!(json["uri"], "file:///tmp/a.rs");
+
+    let back: PublishDiagnosticsParams = serde_json::from_value(json).unwrap();
+    assert_eq!(back.uri, uri);
+    assert_eq!(back.uri, "file:///tmp/a.rs");
+}
+
+#[test]
+fn document_uri_converts_to_and_from_strings() {
+    let uri = DocumentUri::from(String::from("file:///tmp/a.rs"));
+    assert_eq!(uri.as_str(), "file:///tmp/a.rs");
+    assert!(uri.starts_with("file://"));
+    assert_eq!(uri.to_string(), "file:///tmp/a.rs");
+    assert_eq!(String::from(uri), "file:///tmp/a.rs");
+}
+
+#[cfg(feature = "shared-uri")]
+#[test]
+fn cloned_document_uris_share_storage() {
+    let uri = DocumentUri::from("file:///tmp/a.rs");
+    let batch = diagnostics_batch(&uri);
+    for params in &batch {
+        assert!(std::ptr::eq(params.uri.as_str(), uri.as_str()));
+    }
+}
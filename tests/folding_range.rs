@@ -0,0 +1,64 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn predefined_kinds_serialize_as_strings() {
+    for (kind, value) in [
+        (FoldingRangeKind::Comment, "comment"),
+        (FoldingRangeKind::Imports, "imports"),
+        (FoldingRangeKind::Region, "region"),
+    ] {
+        assert_eq!(serde_json::to_value(&kind).unwrap(), value);
+        assert_eq!(
+            serde_json::from_value::<FoldingRangeKind>(value.into()).unwrap(),
+            kind
+        );
+    }
+}
+
+#[test]
+fn custom_kind_round_trips() {
+    let kind: FoldingRangeKind = serde_json::from_value(json!("docstring")).unwrap();
+    assert_eq!(kind, FoldingRangeKind::Other("docstring".into()));
+    assert_eq!(serde_json::to_value(&kind).unwrap(), "docstring");
+}
+
+#[test]
+fn fold_all_comments_filters_by_kind() {
+    let ranges: Vec<FoldingRange> = serde_json::from_value(json!([
+        { "startLine": 0, "endLine": 3, "kind": "comment" },
+        { "startLine": 5, "endLine": 8, "kind": "imports" },
+        { "startLine": 10, "endLine": 20 },
+        { "startLine": 22, "endLine": 25, "kind": "docstring" },
+        { "startLine": 30, "endLine": 31, "kind": "comment" },
+    ]))
+    .unwrap();
+
+    let comments: Vec<UInteger> = ranges
+        .iter()
+        .filter(|range| range.is_kind(FoldingRangeKind::Comment))
+        .map(|range| range.startLine)
+        .collect();
+    assert_eq!(comments, [0, 30]);
+
+    assert!(ranges[3].is_kind(FoldingRangeKind::Other("docstring".into())));
+    assert!(!ranges[2].is_kind(FoldingRangeKind::Region));
+}
+
+#[test]
+fn lines_builds_a_whole_line_fold() {
+    let fold = FoldingRange::lines(2, 6).with_kind(FoldingRangeKind::Comment);
+    assert!(fold.validate(10));
+    assert_eq!(
+        serde_json::to_value(&fold).unwrap(),
+        json!({ "startLine": 2, "endLine": 6, "kind": "comment" })
+    );
+}
+
+#[test]
+fn validate_rejects_out_of_bounds_and_inverted_folds() {
+    // line 10 doesn't exist in a 10-line document
+    assert!(!FoldingRange::lines(4, 10).validate(10));
+    assert!(FoldingRange::lines(4, 9).validate(10));
+    assert!(!FoldingRange::lines(6, 5).validate(10));
+}
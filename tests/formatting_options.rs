@@ -0,0 +1,63 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn tab_size_2_with_trailing_whitespace_trimming() {
+    let options = FormattingOptions::new(2, true).trim_trailing_whitespace(true);
+    assert_eq!(
+        serde_json::to_value(&options).unwrap(),
+        json!({ "tabSize": 2, "insertSpaces": true, "trimTrailingWhitespace": true })
+    );
+}
+
+#[test]
+fn unset_options_are_left_out() {
+    assert_eq!(
+        serde_json::to_value(FormattingOptions::new(8, false)).unwrap(),
+        json!({ "tabSize": 8, "insertSpaces": false })
+    );
+}
+
+#[test]
+fn newline_setters_serialize() {
+    let options = FormattingOptions::new(4, true)
+        .insert_final_newline(true)
+        .trim_final_newlines(false);
+    assert_eq!(
+        serde_json::to_value(&options).unwrap(),
+        json!({
+            "tabSize": 4,
+            "insertSpaces": true,
+            "insertFinalNewline": true,
+            "trimFinalNewlines": false,
+        })
+    );
+}
+
+#[test]
+fn custom_properties_are_read_typed() {
+    let options: FormattingOptions = serde_json::from_value(json!({
+        "tabSize": 4,
+        "insertSpaces": true,
+        "alignAssignments": true,
+        "quoteStyle": "single",
+        "maxWidth": 100,
+    }))
+    .unwrap();
+
+    assert_eq!(options.get_bool("alignAssignments"), Some(true));
+    assert_eq!(options.get_string("quoteStyle"), Some("single"));
+    assert_eq!(options.get_i32("maxWidth"), Some(100));
+}
+
+#[test]
+fn mistyped_or_missing_properties_read_as_none() {
+    let options = FormattingOptions::new(4, true)
+        .with_property("quoteStyle", Value::String("single".into()))
+        .trim_trailing_whitespace(true);
+
+    assert_eq!(options.get_bool("quoteStyle"), None);
+    assert_eq!(options.get_string("alignAssignments"), None);
+    // the predefined options aren't further properties
+    assert_eq!(options.get_bool("trimTrailingWhitespace"), None);
+}
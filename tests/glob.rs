@@ -0,0 +1,75 @@
+use rust_lsp_types::*;
+
+#[test]
+fn glob_expands_braces() {
+    let glob = Glob::new("**/*.{ts,js}");
+    assert!(glob.matches("/src/a.ts"));
+    assert!(glob.matches("/src/a.js"));
+    assert!(!glob.matches("/src/a.rs"));
+
+    let nested = Glob::new("/src/{a,b{1,2}}.rs");
+    assert!(nested.matches("/src/a.rs"));
+    assert!(nested.matches("/src/b2.rs"));
+    assert!(!nested.matches("/src/b.rs"));
+}
+
+#[test]
+fn glob_star_stays_in_its_segment() {
+    let glob = Glob::new("/src/*.rs");
+    assert!(glob.matches("/src/lib.rs"));
+    assert!(!glob.matches("/src/a/lib.rs"));
+    assert!(Glob::new("/src/?é.rs").matches("/src/xé.rs"));
+    assert!(Glob::new("/src/[!0-9]*.rs").matches("/src/lib.rs"));
+    assert!(!Glob::new("/src/[!0-9]*.rs").matches("/src/1.rs"));
+}
+
+#[test]
+fn glob_star_star_matches_any_number_of_segments() {
+    let glob = Glob::new("/src/**/mod.rs");
+    assert!(glob.matches("/src/mod.rs"));
+    assert!(glob.matches("/src/a/b/mod.rs"));
+    assert!(!glob.matches("/lib/a/mod.rs"));
+}
+
+#[test]
+fn glob_star_star_backtracking_is_bounded() {
+    let glob = Glob::new("**/a/**/a/**/a/**/a/**/a/**/a/**/b");
+    // without the memo table this backtracks through every way of splitting
+    // the path between the `**`s and doesn't finish
+    let path = "/a".repeat(200);
+    assert!(!glob.matches(&path));
+}
+
+#[test]
+fn compiled_selector_matches_like_the_selector() {
+    let selector: DocumentSelector =
+        serde_json::from_str(r#"[{"language":"rust"},{"scheme":"file","pattern":"**/*.{ts,js}"}]"#)
+            .unwrap();
+    let compiled = CompiledDocumentSelector::new(&selector);
+    for (uri, language) in [
+        ("file:///a.rs", "rust"),
+        ("file:///a.ts", "typescript"),
+        ("untitled:///a.ts", "typescript"),
+        ("file:///a%20b.js", "javascript"),
+        ("file:///a.py", "python"),
+    ] {
+        assert_eq!(
+            compiled.matches(uri, language),
+            selector_matches(&selector, uri, language),
+            "{uri}"
+        );
+    }
+}
+
+#[test]
+fn alternatives_with_wildcards_each_get_a_fresh_table() {
+    let glob = Glob::new("{**/b/*.rs,src/**/*.ts,**}");
+    assert!(glob.matches("/a/b/c.rs"));
+    assert!(glob.matches("src/x/y.ts"));
+    // the first two alternatives fail on this path before the last matches
+    assert!(glob.matches("src/x/y.py"));
+
+    let glob = Glob::new("{**/b/*.rs,src/**/*.ts}");
+    assert!(!glob.matches("src/x/y.py"));
+    assert!(glob.matches("src/b/y.rs"));
+}
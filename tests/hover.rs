@@ -0,0 +1,37 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn marked_string_array_becomes_markdown() {
+    let contents: HoverContents = serde_json::from_value(json!([
+        "Returns the *length* of the slice.",
+        { "language": "rust", "value": "pub fn len(&self) -> usize" },
+        { "language": "markdown", "value": "```\nnested\n```" }
+    ]))
+    .unwrap();
+    assert!(matches!(contents, HoverContents::MarkedStringArray(_)));
+
+    let markup = contents.into_markup();
+    assert_eq!(markup.kind, MarkupKind::Markdown);
+    assert_eq!(
+        markup.value,
+        "Returns the *length* of the slice.\n\n\
+         ```rust\npub fn len(&self) -> usize\n```\n\n\
+         ````markdown\n```\nnested\n```\n````"
+    );
+}
+
+#[test]
+fn markup_content_is_kept() {
+    let contents = HoverContents::MarkupContent(MarkupContent {
+        kind: MarkupKind::PlainText,
+        value: "len".into(),
+    });
+    assert_eq!(
+        contents.into_markup(),
+        MarkupContent {
+            kind: MarkupKind::PlainText,
+            value: "len".into(),
+        }
+    );
+}
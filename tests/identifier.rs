@@ -0,0 +1,42 @@
+use rust_lsp_types::*;
+
+#[test]
+fn default_rule_accepts_identifiers() {
+    for name in ["foo", "_foo", "foo_1", "héllo"] {
+        assert_eq!(is_valid_identifier(name, None), Ok(true), "{name}");
+    }
+}
+
+#[test]
+fn default_rule_rejects_non_identifiers() {
+    for name in ["", "1foo", "foo-bar", "foo bar"] {
+        assert_eq!(is_valid_identifier(name, None), Ok(false), "{name}");
+    }
+}
+
+#[cfg(not(feature = "regex"))]
+#[test]
+fn patterns_need_the_regex_feature() {
+    assert_eq!(
+        is_valid_identifier("foo", Some("^foo$")),
+        Err(IdentifierPatternError::Unsupported)
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn patterns_are_regular_expressions() {
+    assert_eq!(is_valid_identifier("foo", Some("^(foo|bar)$")), Ok(true));
+    assert_eq!(is_valid_identifier("baz", Some("^(foo|bar)$")), Ok(false));
+    assert_eq!(is_valid_identifier("héllo", Some(r"^\w+$")), Ok(true));
+    assert_eq!(is_valid_identifier("a-b", Some(r"^[a-z]+$")), Ok(false));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn invalid_patterns_are_errors() {
+    assert!(matches!(
+        is_valid_identifier("foo", Some("(foo")),
+        Err(IdentifierPatternError::Invalid(_))
+    ));
+}
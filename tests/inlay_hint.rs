@@ -0,0 +1,86 @@
+use rust_lsp_types::*;
+use serde_json::{json, Value};
+
+fn caps(properties: &[&str]) -> InlayHintClientCapabilities {
+    serde_json::from_value(json!({ "resolveSupport": { "properties": properties } })).unwrap()
+}
+
+fn hint(label: Value) -> InlayHint {
+    serde_json::from_value(json!({
+        "position": { "line": 3, "character": 9 },
+        "label": label,
+        "data": { "id": 1 },
+    }))
+    .unwrap()
+}
+
+#[test]
+fn tooltip_is_resolvable_but_label_is_not() {
+    let caps = caps(&["tooltip"]);
+    let hint = hint(json!([{ "value": ": Vec<u8>" }]));
+    assert!(hint.can_resolve(&caps));
+    assert_eq!(hint.resolvable_properties(&caps), ["tooltip"]);
+}
+
+#[test]
+fn label_parts_are_resolvable_when_listed_and_unset() {
+    let caps = caps(&["tooltip", "label.location"]);
+    let parts = hint(json!([{ "value": ": Vec<u8>" }]));
+    assert_eq!(
+        parts.resolvable_properties(&caps),
+        ["tooltip", "label.location"]
+    );
+
+    // a plain string label has no parts to resolve
+    let string = hint(json!(": Vec<u8>"));
+    assert_eq!(string.resolvable_properties(&caps), ["tooltip"]);
+}
+
+#[test]
+fn set_properties_are_not_resolvable() {
+    let caps = caps(&["tooltip"]);
+    let mut hint = hint(json!(": Vec<u8>"));
+    hint.tooltip = serde_json::from_value(json!("the inferred type")).unwrap();
+    assert!(!hint.can_resolve(&caps));
+}
+
+#[test]
+fn nothing_is_resolvable_without_resolve_support() {
+    let caps: InlayHintClientCapabilities = serde_json::from_value(json!({})).unwrap();
+    assert!(!hint(json!(": Vec<u8>")).can_resolve(&caps));
+
+    // names that aren't inlay hint properties are left out
+    assert!(!hint(json!(": Vec<u8>")).can_resolve(&self::caps(&["position.line", "label"])));
+}
+
+#[test]
+fn label_is_a_string_or_parts() {
+    let string: InlayHintLabel = serde_json::from_value(json!(": usize")).unwrap();
+    assert!(matches!(&string, InlayHintLabel::String(label) if label == ": usize"));
+
+    let parts: InlayHintLabel = serde_json::from_value(json!([
+        { "value": ": " },
+        {
+            "value": "Vec",
+            "location": {
+                "uri": "file:///std/vec.rs",
+                "range": { "start": { "line": 396, "character": 11 }, "end": { "line": 396, "character": 14 } }
+            }
+        },
+        { "value": "<u8>" }
+    ]))
+    .unwrap();
+    let InlayHintLabel::InlayHintLabelPartArray(parts) = parts else {
+        panic!("expected label parts");
+    };
+    assert_eq!(parts.len(), 3);
+    assert!(parts[0].location.is_none());
+    assert_eq!(
+        parts[1].location,
+        Some(Location::new(
+            "file:///std/vec.rs",
+            Range::from(((396, 11), (396, 14)))
+        ))
+    );
+    assert_eq!(parts[2].value, "<u8>");
+}
@@ -0,0 +1,52 @@
+use rust_lsp_types::*;
+use serde_json::{json, Value};
+
+fn range() -> Value {
+    json!({ "start": { "line": 4, "character": 8 }, "end": { "line": 4, "character": 13 } })
+}
+
+fn round_trip(json: Value) -> InlineValue {
+    let value: InlineValue = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&value).unwrap(), json);
+    value
+}
+
+#[test]
+fn inline_value_text_round_trips() {
+    let value = round_trip(json!({ "range": range(), "text": "count = 3" }));
+    assert!(matches!(value, InlineValue::InlineValueText(ref text) if text.text == "count = 3"));
+}
+
+#[test]
+fn inline_value_variable_lookup_round_trips() {
+    let value = round_trip(json!({
+        "range": range(),
+        "variableName": "count",
+        "caseSensitiveLookup": true,
+    }));
+    assert!(matches!(
+        value,
+        InlineValue::InlineValueVariableLookup(ref lookup)
+            if lookup.variableName.as_deref() == Some("count") && lookup.caseSensitiveLookup
+    ));
+}
+
+#[test]
+fn inline_value_evaluatable_expression_round_trips() {
+    let value = round_trip(json!({ "range": range(), "expression": "count * 2" }));
+    assert!(matches!(
+        value,
+        InlineValue::InlineValueEvaluatableExpression(ref expression)
+            if expression.expression.as_deref() == Some("count * 2")
+    ));
+}
+
+#[test]
+fn evaluatable_expression_without_expression_stays_an_expression() {
+    let value = round_trip(json!({ "range": range() }));
+    assert!(matches!(
+        value,
+        InlineValue::InlineValueEvaluatableExpression(ref expression)
+            if expression.expression.is_none()
+    ));
+}
@@ -0,0 +1,46 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn insert_replace_edit_serializes_both_ranges() {
+    let edit = InsertReplaceEdit::new(
+        "println!",
+        ((2, 4), (2, 8)).into(),
+        ((2, 4), (2, 12)).into(),
+    );
+    assert_eq!(
+        serde_json::to_value(&edit).unwrap(),
+        json!({
+            "newText": "println!",
+            "insert": { "start": { "line": 2, "character": 4 }, "end": { "line": 2, "character": 8 } },
+            "replace": { "start": { "line": 2, "character": 4 }, "end": { "line": 2, "character": 12 } },
+        })
+    );
+}
+
+#[test]
+fn completion_item_insert_replace_sets_the_text_edit() {
+    let item = CompletionItem::new("println!").insert_replace(
+        "println!",
+        ((2, 4), (2, 8)).into(),
+        ((2, 4), (2, 12)).into(),
+    );
+    assert!(matches!(
+        item.textEdit,
+        Some(CompletionItemEditKind::InsertReplaceEdit(ref edit)) if edit.newText == "println!"
+    ));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "the insert range must be a prefix of the replace range")]
+fn non_prefix_insert_range_fails_in_debug_builds() {
+    InsertReplaceEdit::new("x", ((2, 6), (2, 8)).into(), ((2, 4), (2, 12)).into());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "insert and replace ranges must be single-line")]
+fn multi_line_ranges_fail_in_debug_builds() {
+    InsertReplaceEdit::new("x", ((2, 4), (3, 0)).into(), ((2, 4), (3, 0)).into());
+}
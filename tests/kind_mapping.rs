@@ -0,0 +1,43 @@
+use rust_lsp_types::*;
+
+#[test]
+fn symbol_kinds_map_to_completion_kinds() {
+    for (symbol, completion) in [
+        (SymbolKind::Function, CompletionItemKind::Function),
+        (SymbolKind::Struct, CompletionItemKind::Struct),
+        (SymbolKind::Namespace, CompletionItemKind::Module),
+        (SymbolKind::Key, CompletionItemKind::Property),
+        (SymbolKind::Boolean, CompletionItemKind::Value),
+        (SymbolKind::Null, CompletionItemKind::Value),
+        (SymbolKind::Unknown(99), CompletionItemKind::Text),
+    ] {
+        assert_eq!(symbol.to_completion_kind(), completion, "{symbol:?}");
+    }
+}
+
+#[test]
+fn completion_kinds_map_to_symbol_kinds() {
+    for (completion, symbol) in [
+        (CompletionItemKind::Method, Some(SymbolKind::Method)),
+        (CompletionItemKind::EnumMember, Some(SymbolKind::EnumMember)),
+        (
+            CompletionItemKind::TypeParameter,
+            Some(SymbolKind::TypeParameter),
+        ),
+        (CompletionItemKind::Keyword, None),
+        (CompletionItemKind::Snippet, None),
+        (CompletionItemKind::Folder, None),
+        (CompletionItemKind::Unknown(99), None),
+    ] {
+        assert_eq!(completion.to_symbol_kind(), symbol, "{completion:?}");
+    }
+}
+
+#[test]
+fn mapped_symbol_kinds_map_back() {
+    for kind in CompletionItemKind::all() {
+        if let Some(symbol) = kind.to_symbol_kind() {
+            assert_eq!(symbol.to_completion_kind(), *kind);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+fn item(label_details: serde_json::Value) -> CompletionItem {
+    serde_json::from_value(json!({ "label": "push", "labelDetails": label_details })).unwrap()
+}
+
+#[test]
+fn flattening_folds_detail_and_description_into_the_label() {
+    let mut item = item(json!({ "detail": "(value: T)", "description": "Vec<T>" }));
+    assert_eq!(item.label, "push");
+
+    item.flatten_label_details();
+    assert_eq!(item.label, "push(value: T) Vec<T>");
+    assert!(item.labelDetails.is_none());
+}
+
+#[test]
+fn flattening_with_only_one_part() {
+    let mut detail_only = item(json!({ "detail": "(value: T)" }));
+    detail_only.flatten_label_details();
+    assert_eq!(detail_only.label, "push(value: T)");
+
+    let mut description_only = item(json!({ "description": "Vec<T>" }));
+    description_only.flatten_label_details();
+    assert_eq!(description_only.label, "push Vec<T>");
+}
+
+#[test]
+fn flattening_without_label_details_keeps_the_label() {
+    let mut item = CompletionItem::new("push");
+    item.flatten_label_details();
+    assert_eq!(item.label, "push");
+}
@@ -0,0 +1,63 @@
+use rust_lsp_types::*;
+
+fn position(line: UInteger, character: UInteger) -> Position {
+    Position { line, character }
+}
+
+fn replace(text: &str, start: Position, end: Position, new_text: &str) -> String {
+    let mut text = text.to_owned();
+    let change = TextDocumentContentChangeEvent::TextDocumentContentChangeEventWithRange(
+        TextDocumentContentChangeEventWithRange {
+            range: Range { start, end },
+            rangeLength: None,
+            text: new_text.to_owned(),
+        },
+    );
+    apply_content_changes(&mut text, &[change], PositionEncodingKind::UTF16);
+    text
+}
+
+#[test]
+fn clamp_to_counts_every_line_break() {
+    for text in ["ab\rcd", "ab\ncd", "ab\r\ncd"] {
+        assert_eq!(
+            position(1, 5).clamp_to(text, PositionEncodingKind::UTF16),
+            position(1, 2),
+            "{text:?}"
+        );
+        assert_eq!(
+            position(0, 5).clamp_to(text, PositionEncodingKind::UTF16),
+            position(0, 2),
+            "{text:?}"
+        );
+    }
+    assert_eq!(
+        position(3, 0).clamp_to("a\r", PositionEncodingKind::UTF16),
+        position(1, 0)
+    );
+}
+
+#[test]
+fn content_changes_count_every_line_break() {
+    assert_eq!(
+        replace("ab\rcd", position(1, 0), position(1, 1), "x"),
+        "ab\rxd"
+    );
+    assert_eq!(
+        replace("ab\r\ncd", position(1, 1), position(1, 2), "x"),
+        "ab\r\ncx"
+    );
+    assert_eq!(
+        replace("ab\rcd", position(0, 9), position(1, 0), ""),
+        "abcd"
+    );
+}
+
+#[test]
+fn text_edits_from_diff_splits_on_lone_carriage_returns() {
+    let edits = text_edits_from_diff("a\rb\rc", "a\rx\rc", PositionEncodingKind::UTF16);
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].range.start, position(1, 0));
+    assert_eq!(edits[0].range.end, position(2, 0));
+    assert_eq!(edits[0].newText, "x\r");
+}
@@ -0,0 +1,41 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn lsp_any_keeps_large_integers() {
+    let any: LSPAny = serde_json::from_value(json!(9999999999u64)).unwrap();
+    assert_eq!(any, LSPAny::Integer64(9999999999));
+    assert_eq!(serde_json::to_string(&any).unwrap(), "9999999999");
+
+    let any: LSPAny = serde_json::from_str("18446744073709551615").unwrap();
+    assert_eq!(any, LSPAny::UInteger64(u64::MAX));
+    assert_eq!(serde_json::to_string(&any).unwrap(), "18446744073709551615");
+
+    let any: LSPAny = serde_json::from_str("-9999999999").unwrap();
+    assert_eq!(any, LSPAny::Integer64(-9999999999));
+}
+
+#[test]
+fn lsp_any_round_trips() {
+    let json = json!({
+        "int": -1,
+        "uint": 4000000000u32,
+        "big": 9999999999u64,
+        "decimal": 0.5,
+        "bool": true,
+        "null": null,
+        "array": ["a", 1],
+    });
+    let any: LSPAny = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&any).unwrap(), json);
+}
+
+#[test]
+fn lsp_any_from_wide_integers_matches_deserialized() {
+    for value in [0i64, -1, 4000000000, 9999999999, i64::MIN] {
+        let any: LSPAny = serde_json::from_value(json!(value)).unwrap();
+        assert_eq!(LSPAny::from(value), any);
+    }
+    let any: LSPAny = serde_json::from_value(json!(u64::MAX)).unwrap();
+    assert_eq!(LSPAny::from(u64::MAX), any);
+}
@@ -0,0 +1,46 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+fn completion_options() -> CompletionOptions {
+    serde_json::from_value(json!({ "triggerCharacters": ["."] })).unwrap()
+}
+
+#[test]
+fn hover_only_and_completion_only_merge_into_both() {
+    let hover = ServerCapabilities::default().hover(true);
+    let completion = ServerCapabilities::default().completion(completion_options());
+    assert_eq!(
+        serde_json::to_value(hover.merge(completion)).unwrap(),
+        json!({
+            "hoverProvider": true,
+            "completionProvider": { "triggerCharacters": ["."] },
+        })
+    );
+}
+
+#[test]
+fn self_wins_when_both_set_a_capability() {
+    let hover_options: HoverOptions =
+        serde_json::from_value(json!({ "workDoneProgress": true })).unwrap();
+    let ours = ServerCapabilities::default().hover(false).definition(true);
+    let theirs = ServerCapabilities::default()
+        .hover(hover_options)
+        .references(true);
+    assert_eq!(
+        serde_json::to_value(ours.merge(theirs)).unwrap(),
+        json!({
+            "hoverProvider": false,
+            "definitionProvider": true,
+            "referencesProvider": true,
+        })
+    );
+}
+
+#[test]
+fn merging_empty_capabilities_changes_nothing() {
+    let merged = ServerCapabilities::default().merge(ServerCapabilities::default().rename(true));
+    assert_eq!(
+        serde_json::to_value(merged).unwrap(),
+        json!({ "renameProvider": true })
+    );
+}
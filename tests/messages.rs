@@ -0,0 +1,90 @@
+use rust_lsp_types::*;
+
+#[test]
+fn unknown_method_is_reported() {
+    let message: AnyMessage = r#"{"jsonrpc":"2.0","id":1,"method":"foo/bar","params":{}}"#
+        .parse()
+        .unwrap();
+    assert!(matches!(
+        message.require_known_method(),
+        Err(ParseError::UnknownMethod(method)) if method == "foo/bar"
+    ));
+}
+
+#[test]
+fn protocol_methods_and_responses_pass() {
+    let request: AnyMessage = r#"{"jsonrpc":"2.0","id":1,"method":"shutdown"}"#.parse().unwrap();
+    assert!(request.require_known_method().is_ok());
+
+    let notification: AnyMessage = r#"{"jsonrpc":"2.0","method":"exit"}"#.parse().unwrap();
+    assert!(notification.require_known_method().is_ok());
+
+    let response: AnyMessage = r#"{"jsonrpc":"2.0","id":1,"result":null}"#.parse().unwrap();
+    assert!(response.require_known_method().is_ok());
+}
+
+#[test]
+fn malformed_params_name_the_method() {
+    let request =
+        r#"{"jsonrpc":"2.0","id":1,"method":"textDocument/hover","params":{"position":1}}"#
+            .parse::<AnyMessage>()
+            .unwrap()
+            .into_request()
+            .unwrap();
+    assert!(matches!(
+        request.params_as::<HoverParams1>(),
+        Err(ParseError::InvalidParams { method, .. }) if method == "textDocument/hover"
+    ));
+}
+
+#[test]
+fn bad_messages_are_rejected() {
+    for message in [
+        r#"{"jsonrpc":"2.0"}"#,
+        r#"{"jsonrpc":"2.0","id":1}"#,
+        r#"{"jsonrpc":"2.0","id":1,"result":1,"error":{"code":1,"message":"x"}}"#,
+        r#"[1]"#,
+        r#"{"jsonrpc""#,
+    ] {
+        assert!(
+            matches!(
+                message.parse::<AnyMessage>(),
+                Err(ParseError::InvalidMessage(_))
+            ),
+            "{message}"
+        );
+    }
+}
+
+#[test]
+fn message_kinds_are_told_apart() {
+    let notification: AnyMessage = r#"{"jsonrpc":"2.0","method":"exit"}"#.parse().unwrap();
+    assert!(matches!(
+        notification.into_request(),
+        Err(ParseError::NotARequest)
+    ));
+
+    let response: AnyMessage = r#"{"jsonrpc":"2.0","id":1,"result":null}"#.parse().unwrap();
+    assert!(matches!(
+        response.into_notification(),
+        Err(ParseError::NotANotification)
+    ));
+}
+
+#[test]
+fn absent_params_decode_as_no_params() {
+    let request = r#"{"jsonrpc":"2.0","id":1,"method":"shutdown"}"#
+        .parse::<AnyMessage>()
+        .unwrap()
+        .into_request()
+        .unwrap();
+    assert!(request.params.is_none());
+    assert_eq!(request.params_as::<NoParams>().unwrap(), NoParams);
+
+    let notification = r#"{"jsonrpc":"2.0","method":"exit"}"#
+        .parse::<AnyMessage>()
+        .unwrap()
+        .into_notification()
+        .unwrap();
+    assert_eq!(notification.params_as::<NoParams>().unwrap(), NoParams);
+}
@@ -0,0 +1,122 @@
+use rust_lsp_types::*;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+const NOTEBOOK: &str = "file:///analysis.ipynb";
+
+fn cell_uri(n: u32) -> String {
+    format!("vscode-notebook-cell:/analysis.ipynb#cell{n}")
+}
+
+fn notebook() -> NotebookDocument {
+    serde_json::from_value(json!({
+        "uri": NOTEBOOK,
+        "notebookType": "jupyter-notebook",
+        "version": 1,
+        "cells": [
+            { "kind": 2, "document": cell_uri(1) },
+            { "kind": 1, "document": cell_uri(2) },
+        ],
+    }))
+    .unwrap()
+}
+
+fn cell_documents() -> BTreeMap<DocumentUri, TextDocumentItem> {
+    [
+        TextDocumentItem::new(cell_uri(1), LanguageId::Python, 1, "import pandas\n"),
+        TextDocumentItem::new(cell_uri(2), LanguageId::Markdown, 1, "# Results\n"),
+    ]
+    .into_iter()
+    .map(|item| (item.uri.clone(), item))
+    .collect()
+}
+
+fn cell_documents_of(notebook: &NotebookDocument) -> Vec<&str> {
+    notebook
+        .cells
+        .iter()
+        .map(|cell| cell.document.as_str())
+        .collect()
+}
+
+#[test]
+fn inserting_a_cell_splices_it_in_and_opens_its_document() {
+    let mut notebook = notebook();
+    let mut documents = cell_documents();
+    let change: NotebookDocumentChangeEvent = serde_json::from_value(json!({
+        "cells": {
+            "structure": {
+                "array": { "start": 1, "deleteCount": 0, "cells": [{ "kind": 2, "document": cell_uri(3) }] },
+                "didOpen": [{ "uri": cell_uri(3), "languageId": "python", "version": 1, "text": "df.head()" }],
+            },
+        },
+    }))
+    .unwrap();
+
+    notebook.apply_change(change, &mut documents, PositionEncodingKind::UTF16);
+    assert_eq!(
+        cell_documents_of(&notebook),
+        [cell_uri(1), cell_uri(3), cell_uri(2)]
+    );
+    assert_eq!(documents[&DocumentUri::from(cell_uri(3))].text, "df.head()");
+    assert_eq!(documents.len(), 3);
+}
+
+#[test]
+fn removing_a_cell_closes_its_document() {
+    let mut notebook = notebook();
+    let mut documents = cell_documents();
+    let change: NotebookDocumentChangeEvent = serde_json::from_value(json!({
+        "cells": {
+            "structure": {
+                "array": { "start": 0, "deleteCount": 1 },
+                "didClose": [{ "uri": cell_uri(1) }],
+            },
+        },
+    }))
+    .unwrap();
+
+    notebook.apply_change(change, &mut documents, PositionEncodingKind::UTF16);
+    assert_eq!(cell_documents_of(&notebook), [cell_uri(2)]);
+    assert!(!documents.contains_key(&DocumentUri::from(cell_uri(1))));
+}
+
+#[test]
+fn cell_text_changes_edit_the_cell_document() {
+    let mut notebook = notebook();
+    let mut documents = cell_documents();
+    let change: NotebookDocumentChangeEvent = serde_json::from_value(json!({
+        "cells": {
+            "textContent": [{
+                "document": { "uri": cell_uri(1), "version": 2 },
+                "changes": [{
+                    "range": { "start": { "line": 0, "character": 7 }, "end": { "line": 0, "character": 13 } },
+                    "text": "numpy",
+                }],
+            }],
+        },
+    }))
+    .unwrap();
+
+    notebook.apply_change(change, &mut documents, PositionEncodingKind::UTF16);
+    let document = &documents[&DocumentUri::from(cell_uri(1))];
+    assert_eq!(document.text, "import numpy\n");
+    assert_eq!(document.version, 2);
+    // the other cell and the cell array are untouched
+    assert_eq!(
+        documents[&DocumentUri::from(cell_uri(2))].text,
+        "# Results\n"
+    );
+    assert_eq!(cell_documents_of(&notebook), [cell_uri(1), cell_uri(2)]);
+}
+
+#[test]
+fn cell_by_uri_finds_each_cell_of_a_two_cell_notebook() {
+    let notebook = notebook();
+    let first = notebook.cell_by_uri(&cell_uri(1)).unwrap();
+    assert!(matches!(first.kind, NotebookCellKind::Code));
+    let second = notebook.cell_by_uri(&cell_uri(2)).unwrap();
+    assert!(matches!(second.kind, NotebookCellKind::Markup));
+    assert!(notebook.cell_by_uri(&cell_uri(3)).is_none());
+    assert!(notebook.cell_by_uri(NOTEBOOK).is_none());
+}
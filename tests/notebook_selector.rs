@@ -0,0 +1,60 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn cells_only_notebook_selector() {
+    let json = json!({ "cells": [{ "language": "python" }] });
+    let selector: NotebookDocumentSyncOptionsNotebookSelector =
+        serde_json::from_value(json.clone()).unwrap();
+    assert!(matches!(
+        selector,
+        NotebookDocumentSyncOptionsNotebookSelector::NotebookDocumentSyncOptionsNotebookSelectorCells(
+            ref cells
+        ) if cells.notebook.is_none() && cells.cells[0].language == "python"
+    ));
+    assert_eq!(serde_json::to_value(&selector).unwrap(), json);
+}
+
+#[test]
+fn notebook_only_notebook_selector() {
+    let json = json!({ "notebook": "jupyter-notebook" });
+    let selector: NotebookDocumentSyncOptionsNotebookSelector =
+        serde_json::from_value(json.clone()).unwrap();
+    assert!(matches!(
+        selector,
+        NotebookDocumentSyncOptionsNotebookSelector::NotebookDocumentSyncOptionsNotebookSelectorNotebook(
+            NotebookDocumentSyncOptionsNotebookSelectorNotebook {
+                notebook: StringOrNotebookDocumentFilter::String(ref notebook),
+                cells: None,
+            }
+        ) if notebook == "jupyter-notebook"
+    ));
+    assert_eq!(serde_json::to_value(&selector).unwrap(), json);
+}
+
+#[test]
+fn notebook_selector_with_a_filter() {
+    let json = json!({
+        "notebook": { "notebookType": "jupyter-notebook", "scheme": "file" },
+        "cells": [{ "language": "python" }],
+    });
+    let selector: NotebookDocumentSyncOptionsNotebookSelector =
+        serde_json::from_value(json.clone()).unwrap();
+    assert!(matches!(
+        selector,
+        NotebookDocumentSyncOptionsNotebookSelector::NotebookDocumentSyncOptionsNotebookSelectorNotebook(
+            NotebookDocumentSyncOptionsNotebookSelectorNotebook {
+                notebook: StringOrNotebookDocumentFilter::NotebookDocumentFilter(_),
+                cells: Some(_),
+            }
+        )
+    ));
+    assert_eq!(serde_json::to_value(&selector).unwrap(), json);
+}
+
+#[test]
+fn notebook_selector_without_notebook_or_cells_is_rejected() {
+    assert!(
+        serde_json::from_value::<NotebookDocumentSyncOptionsNotebookSelector>(json!({})).is_err()
+    );
+}
@@ -0,0 +1,50 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn nullable_null_serializes_as_null() {
+    let options = TextDocumentRegistrationOptions {
+        documentSelector: Nullable::Null,
+    };
+    assert_eq!(
+        serde_json::to_value(&options).unwrap(),
+        json!({ "documentSelector": null })
+    );
+}
+
+#[test]
+fn nullable_distinguishes_null_from_missing() {
+    let null: TextDocumentRegistrationOptions =
+        serde_json::from_value(json!({ "documentSelector": null })).unwrap();
+    assert!(null.documentSelector.is_null());
+
+    let missing = serde_json::from_value::<TextDocumentRegistrationOptions>(json!({}));
+    assert!(missing.is_err());
+}
+
+#[test]
+fn nullable_value_round_trips() {
+    let json = json!({ "documentSelector": [{ "language": "rust" }] });
+    let options: TextDocumentRegistrationOptions = serde_json::from_value(json.clone()).unwrap();
+    assert!(
+        matches!(options.documentSelector, Nullable::Value(ref selector) if selector.len() == 1)
+    );
+    assert_eq!(serde_json::to_value(&options).unwrap(), json);
+}
+
+#[test]
+fn nullable_converts_to_and_from_option() {
+    assert_eq!(Nullable::from(Some(1)), Nullable::Value(1));
+    assert_eq!(Nullable::<i32>::from(None), Nullable::Null);
+    assert_eq!(Nullable::Value(1).into_option(), Some(1));
+    assert_eq!(Nullable::Value(1).as_ref(), Nullable::Value(&1));
+    assert_eq!(Nullable::<i32>::default(), Nullable::Null);
+}
+
+#[test]
+fn null_response_result_is_kept() {
+    let json = json!({ "jsonrpc": "2.0", "id": 1, "result": null });
+    let response: ResponseMessage = serde_json::from_value(json.clone()).unwrap();
+    assert!(matches!(response.result, Some(Nullable::Null)));
+    assert_eq!(serde_json::to_value(&response).unwrap(), json);
+}
@@ -0,0 +1,46 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+fn options() -> DocumentOnTypeFormattingOptions {
+    serde_json::from_value(json!({
+        "firstTriggerCharacter": "}",
+        "moreTriggerCharacter": [";", "\n"],
+    }))
+    .unwrap()
+}
+
+#[test]
+fn matching_trigger_characters() {
+    let options = options();
+    assert!(options.triggers_on("}"));
+    assert!(options.triggers_on(";"));
+    assert!(options.triggers_on("\n"));
+}
+
+#[test]
+fn non_matching_trigger_character() {
+    assert!(!options().triggers_on("{"));
+
+    let first_only: DocumentOnTypeFormattingOptions =
+        serde_json::from_value(json!({ "firstTriggerCharacter": "}" })).unwrap();
+    assert!(!first_only.triggers_on(";"));
+}
+
+#[test]
+fn on_type_formatting_params_serialize() {
+    let params = DocumentOnTypeFormattingParams::new(
+        "file:///src/lib.rs",
+        Position::from((7, 1)),
+        "}",
+        FormattingOptions::new(4, true),
+    );
+    assert_eq!(
+        serde_json::to_value(&params).unwrap(),
+        json!({
+            "textDocument": { "uri": "file:///src/lib.rs" },
+            "position": { "line": 7, "character": 1 },
+            "ch": "}",
+            "options": { "tabSize": 4, "insertSpaces": true },
+        })
+    );
+}
@@ -0,0 +1,44 @@
+use rust_lsp_types::*;
+
+#[test]
+fn symbol_kind_keeps_unknown_values() {
+    for value in [99u32, 300, u32::MAX] {
+        let kind: SymbolKind = serde_json::from_value(value.into()).unwrap();
+        assert_eq!(kind, SymbolKind::Unknown(value));
+        assert_eq!(serde_json::to_value(kind).unwrap(), value);
+    }
+    let kind: SymbolKind = serde_json::from_str("12").unwrap();
+    assert_eq!(kind, SymbolKind::Function);
+}
+
+#[test]
+fn completion_item_tag_keeps_unknown_values() {
+    let tags: Vec<CompletionItemTag> = serde_json::from_str("[1,300]").unwrap();
+    assert_eq!(
+        tags,
+        [
+            CompletionItemTag::Deprecated,
+            CompletionItemTag::Unknown(300)
+        ]
+    );
+    assert_eq!(serde_json::to_string(&tags).unwrap(), "[1,300]");
+}
+
+#[test]
+fn unknown_kinds_display_and_parse_as_numbers() {
+    assert_eq!(CompletionItemKind::Unknown(300).to_string(), "300");
+    assert_eq!("300".parse(), Ok(CompletionItemKind::Unknown(300)));
+    assert_eq!("Method".parse(), Ok(CompletionItemKind::Method));
+    assert!("2".parse::<CompletionItemKind>().is_err());
+}
+
+#[test]
+fn unknown_severities_sort_after_hints() {
+    assert!(DiagnosticSeverity::Hint < DiagnosticSeverity::Unknown(300));
+    assert!(DiagnosticSeverity::Error < DiagnosticSeverity::Warning);
+}
+
+#[test]
+fn negative_kinds_are_rejected() {
+    assert!(serde_json::from_str::<SymbolKind>("-1").is_err());
+}
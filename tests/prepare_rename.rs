@@ -0,0 +1,46 @@
+use rust_lsp_types::*;
+use serde_json::{json, Value};
+
+fn range() -> Value {
+    json!({ "start": { "line": 2, "character": 4 }, "end": { "line": 2, "character": 9 } })
+}
+
+fn parse(json: Value) -> Nullable<PrepareRenameResult> {
+    let result = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&result).unwrap(), json);
+    result
+}
+
+#[test]
+fn prepare_rename_range() {
+    assert!(matches!(
+        parse(range()),
+        Nullable::Value(PrepareRenameResult::Range(range)) if range == Range::from(((2, 4), (2, 9)))
+    ));
+}
+
+#[test]
+fn prepare_rename_range_with_placeholder() {
+    assert!(matches!(
+        parse(json!({ "range": range(), "placeholder": "total" })),
+        Nullable::Value(PrepareRenameResult::RangeWithPlaceholder(ref result))
+            if result.placeholder == "total" && result.range == Range::from(((2, 4), (2, 9)))
+    ));
+}
+
+#[test]
+fn prepare_rename_default_behavior() {
+    assert!(matches!(
+        parse(json!({ "defaultBehavior": true })),
+        Nullable::Value(PrepareRenameResult::DefaultBehavior(
+            PrepareRenameDefaultBehavior {
+                defaultBehavior: true
+            }
+        ))
+    ));
+}
+
+#[test]
+fn prepare_rename_null() {
+    assert!(parse(Value::Null).is_null());
+}
@@ -0,0 +1,28 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+fn progress(value: serde_json::Value) -> ProgressParams<LSPAny> {
+    serde_json::from_value(json!({ "token": "indexing-1", "value": value })).unwrap()
+}
+
+#[test]
+fn progress_value_reads_as_a_report() {
+    let params = progress(json!({ "kind": "report", "message": "3/10 files", "percentage": 30 }));
+    let report: WorkDoneProgressReport = params.value_as().unwrap();
+    assert_eq!(report.message.as_deref(), Some("3/10 files"));
+    assert_eq!(report.percentage, Some(30));
+}
+
+#[test]
+fn progress_value_of_another_kind_is_rejected() {
+    let params = progress(json!({ "kind": "end", "message": "done" }));
+    assert!(params.value_as::<WorkDoneProgressReport>().is_err());
+    assert!(params.value_as::<WorkDoneProgressEnd>().is_ok());
+}
+
+#[test]
+fn progress_value_reads_as_a_partial_result() {
+    let params = progress(json!([{ "label": "push" }]));
+    let items: CompletionPartialResult = params.value_as().unwrap();
+    assert_eq!(items[0].label, "push");
+}
@@ -0,0 +1,287 @@
+use rust_lsp_types::ServerCapabilitiesProviders::*;
+use rust_lsp_types::*;
+use serde_json::{json, Value};
+use std::fmt::Debug;
+
+fn variant<T: Debug>(provider: &Option<T>) -> String {
+    let debug = format!("{:?}", provider.as_ref().expect("provider is set"));
+    debug[..debug.find('(').unwrap()].to_string()
+}
+
+fn provider_variants(capabilities: &ServerCapabilities) -> Vec<(&'static str, String)> {
+    vec![
+        ("textDocumentSync", variant(&capabilities.textDocumentSync)),
+        (
+            "notebookDocumentSync",
+            variant(&capabilities.notebookDocumentSync),
+        ),
+        ("hoverProvider", variant(&capabilities.hoverProvider)),
+        (
+            "declarationProvider",
+            variant(&capabilities.declarationProvider),
+        ),
+        (
+            "definitionProvider",
+            variant(&capabilities.definitionProvider),
+        ),
+        (
+            "typeDefinitionProvider",
+            variant(&capabilities.typeDefinitionProvider),
+        ),
+        (
+            "implementationProvider",
+            variant(&capabilities.implementationProvider),
+        ),
+        (
+            "referencesProvider",
+            variant(&capabilities.referencesProvider),
+        ),
+        (
+            "documentHighlightProvider",
+            variant(&capabilities.documentHighlightProvider),
+        ),
+        (
+            "documentSymbolProvider",
+            variant(&capabilities.documentSymbolProvider),
+        ),
+        (
+            "codeActionProvider",
+            variant(&capabilities.codeActionProvider),
+        ),
+        ("colorProvider", variant(&capabilities.colorProvider)),
+        (
+            "documentFormattingProvider",
+            variant(&capabilities.documentFormattingProvider),
+        ),
+        (
+            "documentRangeFormattingProvider",
+            variant(&capabilities.documentRangeFormattingProvider),
+        ),
+        ("renameProvider", variant(&capabilities.renameProvider)),
+        (
+            "foldingRangeProvider",
+            variant(&capabilities.foldingRangeProvider),
+        ),
+        (
+            "selectionRangeProvider",
+            variant(&capabilities.selectionRangeProvider),
+        ),
+        (
+            "linkedEditingRangeProvider",
+            variant(&capabilities.linkedEditingRangeProvider),
+        ),
+        (
+            "callHierarchyProvider",
+            variant(&capabilities.callHierarchyProvider),
+        ),
+        (
+            "semanticTokensProvider",
+            variant(&capabilities.semanticTokensProvider),
+        ),
+        ("monikerProvider", variant(&capabilities.monikerProvider)),
+        (
+            "typeHierarchyProvider",
+            variant(&capabilities.typeHierarchyProvider),
+        ),
+        (
+            "inlineValueProvider",
+            variant(&capabilities.inlineValueProvider),
+        ),
+        (
+            "inlayHintProvider",
+            variant(&capabilities.inlayHintProvider),
+        ),
+        (
+            "diagnosticProvider",
+            variant(&capabilities.diagnosticProvider),
+        ),
+        (
+            "workspaceSymbolProvider",
+            variant(&capabilities.workspaceSymbolProvider),
+        ),
+    ]
+}
+
+/// one of the capability samples in `tests/fixtures`, shared with
+/// `round_trip.rs`
+fn fixture(name: &str) -> Value {
+    let path = format!("{}/tests/fixtures/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    let json = std::fs::read_to_string(&path).unwrap_or_else(|error| panic!("{path}: {error}"));
+    serde_json::from_str(&json).unwrap()
+}
+
+/// deserializes `json`, checks which union variant every provider landed
+/// on, and checks that serializing it again gives back the same json
+fn assert_round_trip(json: Value, expected: &[(&str, &str)]) {
+    let capabilities: ServerCapabilities = serde_json::from_value(json.clone()).unwrap();
+    for (field, variant) in provider_variants(&capabilities) {
+        let (_, want) = expected
+            .iter()
+            .find(|(name, _)| *name == field)
+            .unwrap_or_else(|| panic!("no expected variant for {field}"));
+        assert_eq!(variant, *want, "{field}");
+    }
+    assert_eq!(serde_json::to_value(&capabilities).unwrap(), json);
+}
+
+fn notebook_selector() -> Value {
+    json!([{ "notebook": "jupyter-notebook" }])
+}
+
+#[test]
+fn boolean_providers_round_trip() {
+    assert_round_trip(
+        fixture("server_capabilities_boolean_providers"),
+        &[
+            ("textDocumentSync", "TextDocumentSyncKind"),
+            (
+                "notebookDocumentSync",
+                "NotebookDocumentSyncRegistrationOptions",
+            ),
+            ("hoverProvider", "Boolean"),
+            ("declarationProvider", "Boolean"),
+            ("definitionProvider", "Boolean"),
+            ("typeDefinitionProvider", "Boolean"),
+            ("implementationProvider", "Boolean"),
+            ("referencesProvider", "Boolean"),
+            ("documentHighlightProvider", "Boolean"),
+            ("documentSymbolProvider", "Boolean"),
+            ("codeActionProvider", "Boolean"),
+            ("colorProvider", "Boolean"),
+            ("documentFormattingProvider", "Boolean"),
+            ("documentRangeFormattingProvider", "Boolean"),
+            ("renameProvider", "Boolean"),
+            ("foldingRangeProvider", "Boolean"),
+            ("selectionRangeProvider", "Boolean"),
+            ("linkedEditingRangeProvider", "Boolean"),
+            ("callHierarchyProvider", "Boolean"),
+            ("semanticTokensProvider", "Boolean"),
+            ("monikerProvider", "Boolean"),
+            ("typeHierarchyProvider", "Boolean"),
+            ("inlineValueProvider", "Boolean"),
+            ("inlayHintProvider", "Boolean"),
+            ("diagnosticProvider", "DiagnosticOptions"),
+            ("workspaceSymbolProvider", "Boolean"),
+        ],
+    );
+}
+
+#[test]
+fn options_providers_round_trip() {
+    assert_round_trip(
+        fixture("server_capabilities_options_providers"),
+        &[
+            ("textDocumentSync", "TextDocumentSyncOptions"),
+            // every field of the registration options is optional, so plain
+            // notebook options can't be told apart from them on the wire
+            (
+                "notebookDocumentSync",
+                "NotebookDocumentSyncRegistrationOptions",
+            ),
+            ("hoverProvider", "HoverOptions"),
+            ("declarationProvider", "DeclarationOptions"),
+            ("definitionProvider", "DefinitionOptions"),
+            ("typeDefinitionProvider", "TypeDefinitionOptions"),
+            ("implementationProvider", "ImplementationOptions"),
+            ("referencesProvider", "ReferenceOptions"),
+            ("documentHighlightProvider", "DocumentHighlightOptions"),
+            ("documentSymbolProvider", "DocumentSymbolOptions"),
+            ("codeActionProvider", "CodeActionOptions"),
+            ("colorProvider", "DocumentColorOptions"),
+            ("documentFormattingProvider", "DocumentFormattingOptions"),
+            (
+                "documentRangeFormattingProvider",
+                "DocumentRangeFormattingOptions",
+            ),
+            ("renameProvider", "RenameOptions"),
+            ("foldingRangeProvider", "FoldingRangeOptions"),
+            ("selectionRangeProvider", "SelectionRangeOptions"),
+            ("linkedEditingRangeProvider", "LinkedEditingRangeOptions"),
+            ("callHierarchyProvider", "CallHierarchyOptions"),
+            ("semanticTokensProvider", "SemanticTokensOptions"),
+            ("monikerProvider", "MonikerOptions"),
+            ("typeHierarchyProvider", "TypeHierarchyOptions"),
+            ("inlineValueProvider", "InlineValueOptions"),
+            ("inlayHintProvider", "InlayHintOptions"),
+            ("diagnosticProvider", "DiagnosticOptions"),
+            ("workspaceSymbolProvider", "WorkspaceSymbolOptions"),
+        ],
+    );
+}
+
+#[test]
+fn registration_options_providers_round_trip() {
+    assert_round_trip(
+        fixture("server_capabilities_registration_providers"),
+        &[
+            ("textDocumentSync", "TextDocumentSyncKind"),
+            (
+                "notebookDocumentSync",
+                "NotebookDocumentSyncRegistrationOptions",
+            ),
+            ("hoverProvider", "Boolean"),
+            ("declarationProvider", "DeclarationRegistrationOptions"),
+            ("definitionProvider", "Boolean"),
+            (
+                "typeDefinitionProvider",
+                "TypeDefinitionRegistrationOptions",
+            ),
+            (
+                "implementationProvider",
+                "ImplementationRegistrationOptions",
+            ),
+            ("referencesProvider", "Boolean"),
+            ("documentHighlightProvider", "Boolean"),
+            ("documentSymbolProvider", "Boolean"),
+            ("codeActionProvider", "Boolean"),
+            ("colorProvider", "DocumentColorRegistrationOptions"),
+            ("documentFormattingProvider", "Boolean"),
+            ("documentRangeFormattingProvider", "Boolean"),
+            ("renameProvider", "Boolean"),
+            ("foldingRangeProvider", "FoldingRangeRegistrationOptions"),
+            (
+                "selectionRangeProvider",
+                "SelectionRangeRegistrationOptions",
+            ),
+            (
+                "linkedEditingRangeProvider",
+                "LinkedEditingRangeRegistrationOptions",
+            ),
+            ("callHierarchyProvider", "CallHierarchyRegistrationOptions"),
+            (
+                "semanticTokensProvider",
+                "SemanticTokensRegistrationOptions",
+            ),
+            ("monikerProvider", "MonikerRegistrationOptions"),
+            ("typeHierarchyProvider", "TypeHierarchyRegistrationOptions"),
+            ("inlineValueProvider", "InlineValueRegistrationOptions"),
+            ("inlayHintProvider", "InlayHintRegistrationOptions"),
+            ("diagnosticProvider", "DiagnosticRegistrationOptions"),
+            ("workspaceSymbolProvider", "Boolean"),
+        ],
+    );
+}
+
+#[test]
+fn notebook_document_sync_options_serialize_like_registration_options() {
+    let options: NotebookDocumentSyncOptions =
+        serde_json::from_value(json!({ "notebookSelector": notebook_selector() })).unwrap();
+    let provider = NotebookDocumentSync::from(options);
+    let json = serde_json::to_value(&provider).unwrap();
+    assert_eq!(json, json!({ "notebookSelector": notebook_selector() }));
+
+    let back: NotebookDocumentSync = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&back).unwrap(), json);
+}
+
+#[test]
+fn boolean_providers_convert_from_bool() {
+    let capabilities = ServerCapabilities::default()
+        .hover(true)
+        .definition(true)
+        .rename(false);
+    assert_eq!(
+        serde_json::to_value(&capabilities).unwrap(),
+        json!({ "hoverProvider": true, "definitionProvider": true, "renameProvider": false })
+    );
+}
@@ -0,0 +1,45 @@
+use rust_lsp_types::*;
+
+#[test]
+fn single_line_ranges() {
+    let word = Range::from(((4, 2), (4, 9)));
+    assert!(word.is_single_line());
+    assert_eq!(word.line_count(), 1);
+
+    let empty = Range::from(((7, 3), (7, 3)));
+    assert!(empty.is_single_line());
+    assert_eq!(empty.line_count(), 1);
+}
+
+#[test]
+fn multi_line_ranges() {
+    let block = Range::from(((2, 0), (5, 1)));
+    assert!(!block.is_single_line());
+    assert_eq!(block.line_count(), 4);
+
+    // up to the start of the next line still touches that line
+    let line = Range::from(((3, 0), (4, 0)));
+    assert!(!line.is_single_line());
+    assert_eq!(line.line_count(), 2);
+}
+
+#[test]
+fn normalized_swaps_an_inverted_range() {
+    let inverted = Range {
+        start: Position::from((6, 2)),
+        end: Position::from((3, 8)),
+    };
+    assert!(!inverted.is_valid());
+    let normalized = inverted.normalized();
+    assert!(normalized.is_valid());
+    assert_eq!(normalized, Range::from(((3, 8), (6, 2))));
+
+    let same_line = Range::from(((1, 9), (1, 4))).normalized();
+    assert_eq!(same_line, Range::from(((1, 4), (1, 9))));
+}
+
+#[test]
+fn normalized_keeps_a_valid_range() {
+    let range = Range::from(((3, 8), (6, 2)));
+    assert_eq!(range.normalized(), range);
+}
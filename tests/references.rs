@@ -0,0 +1,25 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn reference_request_includes_the_declaration() {
+    let params = ReferenceParams::new("file:///src/lib.rs", Position::from((10, 4)), true);
+    let json = serde_json::to_value(&params).unwrap();
+    assert_eq!(json["context"], json!({ "includeDeclaration": true }));
+    assert_eq!(
+        json,
+        json!({
+            "textDocument": { "uri": "file:///src/lib.rs" },
+            "position": { "line": 10, "character": 4 },
+            "context": { "includeDeclaration": true },
+        })
+    );
+}
+
+#[test]
+fn reference_context_can_exclude_the_declaration() {
+    assert_eq!(
+        serde_json::to_value(ReferenceContext::new(false)).unwrap(),
+        json!({ "includeDeclaration": false })
+    );
+}
@@ -0,0 +1,38 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn unregistration_uses_the_misspelled_wire_key() {
+    let params = UnregistrationParams::new(vec![Unregistration::new(
+        "watch-1",
+        "workspace/didChangeWatchedFiles",
+    )]);
+    let json = serde_json::to_value(&params).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "unregisterations": [
+                { "id": "watch-1", "method": "workspace/didChangeWatchedFiles" },
+            ],
+        })
+    );
+    assert!(json.get("unregistrations").is_none());
+
+    let back: UnregistrationParams = serde_json::from_value(json).unwrap();
+    assert_eq!(back.unregisterations[0].id, "watch-1");
+}
+
+#[test]
+fn registration_params_round_trip() {
+    let json = json!({
+        "registrations": [{
+            "id": "watch-1",
+            "method": "workspace/didChangeWatchedFiles",
+            "registerOptions": { "watchers": [{ "globPattern": "**/*.rs" }] },
+        }],
+    });
+    let registration: Registration =
+        serde_json::from_value(json["registrations"][0].clone()).unwrap();
+    let params = RegistrationParams::new(vec![registration]);
+    assert_eq!(serde_json::to_value(&params).unwrap(), json);
+}
@@ -0,0 +1,30 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn request_id_past_i32_round_trips() {
+    let json = json!({ "jsonrpc": "2.0", "id": 9999999999u64, "method": "shutdown" });
+    let request: RequestMessage = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(request.id, RequestId::Integer(9999999999));
+    assert_eq!(serde_json::to_value(&request).unwrap(), json);
+}
+
+#[test]
+fn response_echoes_a_large_request_id() {
+    let json = json!({ "jsonrpc": "2.0", "id": 9999999999u64, "result": null });
+    let response: ResponseMessage = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(response.id, Nullable::Value(RequestId::Integer(9999999999)));
+    assert_eq!(serde_json::to_value(&response).unwrap(), json);
+}
+
+#[test]
+fn cancel_params_accept_a_large_id() {
+    let params: CancelParams = serde_json::from_value(json!({ "id": 9999999999u64 })).unwrap();
+    assert_eq!(params.id, RequestId::from(9999999999i64));
+}
+
+#[test]
+fn string_ids_stay_strings() {
+    let params: CancelParams = serde_json::from_value(json!({ "id": "9999999999" })).unwrap();
+    assert_eq!(params.id, RequestId::String("9999999999".into()));
+}
@@ -0,0 +1,48 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+fn strict(json: &str) -> serde_json::Result<ResponseMessage> {
+    ResponseMessage::deserialize_strict(&mut serde_json::Deserializer::from_str(json))
+}
+
+#[test]
+fn response_with_only_a_result_is_accepted() {
+    let response = strict(r#"{ "jsonrpc": "2.0", "id": 1, "result": { "ok": true } }"#).unwrap();
+    assert!(response.result.is_some());
+    assert!(response.error.is_none());
+
+    let null = strict(r#"{ "jsonrpc": "2.0", "id": 1, "result": null }"#).unwrap();
+    assert!(matches!(null.result, Some(Nullable::Null)));
+}
+
+#[test]
+fn response_with_only_an_error_is_accepted() {
+    let response = strict(
+        r#"{ "jsonrpc": "2.0", "id": 1, "error": { "code": -32601, "message": "unknown method" } }"#,
+    )
+    .unwrap();
+    assert!(response.result.is_none());
+    assert_eq!(response.error.unwrap().code, ErrorCodes::MethodNotFound);
+}
+
+#[test]
+fn response_with_both_is_rejected() {
+    let error = strict(
+        r#"{ "jsonrpc": "2.0", "id": 1, "result": null, "error": { "code": -32603, "message": "x" } }"#,
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("both a result and an error"));
+}
+
+#[test]
+fn response_with_neither_is_rejected() {
+    let error = strict(r#"{ "jsonrpc": "2.0", "id": 1 }"#).unwrap_err();
+    assert!(error.to_string().contains("neither a result nor an error"));
+}
+
+#[test]
+fn strict_deserialization_works_from_a_value() {
+    let value = json!({ "jsonrpc": "2.0", "id": null, "error": { "code": -32700, "message": "parse error" } });
+    let response = ResponseMessage::deserialize_strict(value).unwrap();
+    assert!(response.id.is_null());
+}
@@ -0,0 +1,98 @@
+//! Round-trips the JSON samples in `tests/fixtures` through their types. Each
+//! sample is written the way the type puts it on the wire, so the fixtures
+//! double as a record of the wire format.
+
+use std::fmt::Debug;
+
+use rust_lsp_types::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+fn fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    std::fs::read_to_string(&path).unwrap_or_else(|error| panic!("{path}: {error}"))
+}
+
+/// decodes the fixture as `T` and checks that encoding it gives the fixture
+/// back, returning the decoded value
+fn wire_round_trip<T: Serialize + DeserializeOwned>(name: &str) -> T {
+    let json = fixture(name);
+    let value: T = serde_json::from_str(&json).unwrap_or_else(|error| panic!("{name}: {error}"));
+    let expected: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_value(&value).unwrap(), expected, "{name}");
+    value
+}
+
+/// [wire_round_trip], and `from_str(to_string(x)) == x`
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(name: &str) {
+    let value: T = wire_round_trip(name);
+    let encoded = serde_json::to_string(&value).unwrap();
+    assert_eq!(
+        serde_json::from_str::<T>(&encoded).unwrap(),
+        value,
+        "{name}"
+    );
+}
+
+#[test]
+fn position() {
+    round_trip::<Position>("position");
+}
+
+#[test]
+fn range() {
+    round_trip::<Range>("range");
+}
+
+#[test]
+fn diagnostic() {
+    round_trip::<Diagnostic>("diagnostic");
+}
+
+#[test]
+fn completion_item() {
+    round_trip::<CompletionItem>("completion_item");
+}
+
+#[test]
+fn workspace_edit() {
+    round_trip::<WorkspaceEdit>("workspace_edit");
+}
+
+#[test]
+fn hover() {
+    round_trip::<Hover>("hover");
+}
+
+#[test]
+fn server_capabilities() {
+    round_trip::<ServerCapabilities>("server_capabilities");
+}
+
+#[test]
+fn boolean_providers() {
+    round_trip::<ServerCapabilities>("server_capabilities_boolean_providers");
+}
+
+#[test]
+fn options_providers() {
+    round_trip::<ServerCapabilities>("server_capabilities_options_providers");
+}
+
+#[test]
+fn registration_options_providers() {
+    round_trip::<ServerCapabilities>("server_capabilities_registration_providers");
+}
+
+#[test]
+fn response_with_null_id() {
+    let response: ResponseMessage = wire_round_trip("response_null_id");
+    assert!(response.id.is_null());
+}
+
+#[test]
+fn response_with_null_result() {
+    let response: ResponseMessage = wire_round_trip("response_null_result");
+    assert!(matches!(response.result, Some(Nullable::Null)));
+}
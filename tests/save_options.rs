@@ -0,0 +1,33 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+fn sync_options(save: serde_json::Value) -> TextDocumentSyncOptions {
+    serde_json::from_value(json!({ "openClose": true, "save": save })).unwrap()
+}
+
+#[test]
+fn save_accepts_a_bare_boolean() {
+    let options = sync_options(json!(true));
+    let save = options.save.as_ref().unwrap();
+    assert!(matches!(save, BooleanOrSaveOptions::Boolean(true)));
+    assert!(!save.include_text());
+    assert_eq!(serde_json::to_value(&options).unwrap()["save"], true);
+}
+
+#[test]
+fn save_accepts_save_options() {
+    let options = sync_options(json!({ "includeText": true }));
+    let save = options.save.as_ref().unwrap();
+    assert!(matches!(save, BooleanOrSaveOptions::SaveOptions(_)));
+    assert!(save.include_text());
+    assert_eq!(
+        serde_json::to_value(&options).unwrap()["save"],
+        json!({ "includeText": true })
+    );
+}
+
+#[test]
+fn include_text_defaults_to_false() {
+    assert!(!sync_options(json!(false)).save.unwrap().include_text());
+    assert!(!sync_options(json!({})).save.unwrap().include_text());
+}
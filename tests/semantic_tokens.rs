@@ -0,0 +1,151 @@
+use rust_lsp_types::*;
+
+fn tokens(data: Vec<UInteger>) -> SemanticTokens {
+    SemanticTokens {
+        resultId: None,
+        data,
+    }
+}
+
+const TEXT: &str = "let foo = bar;\nfoo";
+
+#[test]
+fn validate_rejects_incomplete_tokens() {
+    assert_eq!(
+        tokens(vec![0, 0, 3, 0]).validate(TEXT, PositionEncodingKind::UTF16, false, false),
+        Err(TokenError::IncompleteToken)
+    );
+}
+
+#[test]
+fn validate_flags_overlapping_tokens_when_not_allowed() {
+    // `let foo` then `foo` starting inside it
+    let overlapping = tokens(vec![0, 0, 7, 0, 0, 0, 4, 3, 0, 0]);
+    assert_eq!(
+        overlapping.validate(TEXT, PositionEncodingKind::UTF16, false, false),
+        Err(TokenError::Overlapping { token: 1 })
+    );
+    assert_eq!(
+        overlapping.validate(TEXT, PositionEncodingKind::UTF16, false, true),
+        Ok(())
+    );
+}
+
+#[test]
+fn validate_flags_multiline_tokens_when_not_allowed() {
+    let multiline = tokens(vec![0, 10, 8, 0, 0]);
+    assert_eq!(
+        multiline.validate(TEXT, PositionEncodingKind::UTF16, false, false),
+        Err(TokenError::Multiline { token: 0 })
+    );
+    assert_eq!(
+        multiline.validate(TEXT, PositionEncodingKind::UTF16, true, false),
+        Ok(())
+    );
+}
+
+#[test]
+fn validate_counts_lone_carriage_returns_as_line_breaks() {
+    let second_line = tokens(vec![1, 0, 3, 0, 0]);
+    assert_eq!(
+        second_line.validate("let\rfoo", PositionEncodingKind::UTF16, false, false),
+        Ok(())
+    );
+}
+
+/// applies `delta` to `prev` the way a client does
+fn apply_delta(prev: &[UInteger], delta: &SemanticTokensDelta) -> Vec<UInteger> {
+    let mut data = prev.to_vec();
+    for edit in delta.edits.iter().rev() {
+        let start = edit.start as usize;
+        data.splice(
+            start..start + edit.deleteCount as usize,
+            edit.data.iter().flatten().copied(),
+        );
+    }
+    data
+}
+
+#[test]
+fn delta_replaces_a_token_in_the_middle() {
+    let prev = [0, 0, 3, 0, 0, 0, 4, 3, 1, 0, 1, 0, 3, 0, 0];
+    let next = [0, 0, 3, 0, 0, 0, 4, 3, 2, 0, 1, 0, 3, 0, 0];
+    let delta = compute_semantic_tokens_delta(&prev, &next, "2".into());
+    assert_eq!(
+        serde_json::to_value(&delta).unwrap(),
+        serde_json::json!({ "resultId": "2", "edits": [{ "start": 8, "deleteCount": 1, "data": [2] }] })
+    );
+    assert_eq!(apply_delta(&prev, &delta), next);
+}
+
+#[test]
+fn delta_appends_tokens() {
+    let prev = [0, 0, 3, 0, 0];
+    let next = [0, 0, 3, 0, 0, 1, 0, 3, 1, 0];
+    let delta = compute_semantic_tokens_delta(&prev, &next, "2".into());
+    assert_eq!(
+        serde_json::to_value(&delta).unwrap(),
+        serde_json::json!({
+            "resultId": "2",
+            "edits": [{ "start": 5, "deleteCount": 0, "data": [1, 0, 3, 1, 0] }],
+        })
+    );
+    assert_eq!(apply_delta(&prev, &delta), next);
+}
+
+#[test]
+fn delta_of_equal_arrays_has_no_edits() {
+    let data = [0, 0, 3, 0, 0];
+    let delta = compute_semantic_tokens_delta(&data, &data, "2".into());
+    assert!(delta.edits.is_empty());
+    assert_eq!(delta.resultId.as_deref(), Some("2"));
+}
+
+#[test]
+fn delta_removes_trailing_tokens() {
+    let prev = [0, 0, 3, 0, 0, 1, 0, 3, 1, 0];
+    let next = [0, 0, 3, 0, 0];
+    let delta = compute_semantic_tokens_delta(&prev, &next, "2".into());
+    assert_eq!(apply_delta(&prev, &delta), next);
+    assert!(delta.edits[0].data.is_none());
+}
+
+#[test]
+fn legend_resolves_indices_and_bits() {
+    let legend = SemanticTokensLegend::new(
+        vec![
+            SemanticTokenTypes::Keyword,
+            SemanticTokenTypes::Variable,
+            SemanticTokenTypes::Function,
+        ],
+        vec![
+            SemanticTokenModifiers::Declaration,
+            SemanticTokenModifiers::Readonly,
+            SemanticTokenModifiers::Static,
+        ],
+    );
+    assert_eq!(
+        serde_json::to_value(&legend).unwrap(),
+        serde_json::json!({
+            "tokenTypes": ["keyword", "variable", "function"],
+            "tokenModifiers": ["declaration", "readonly", "static"]
+        })
+    );
+
+    assert_eq!(legend.type_index(SemanticTokenTypes::Keyword), Some(0));
+    assert_eq!(legend.type_index(SemanticTokenTypes::Function), Some(2));
+    assert_eq!(legend.type_index(SemanticTokenTypes::Macro), None);
+
+    assert_eq!(
+        legend.modifier_bit(SemanticTokenModifiers::Declaration),
+        Some(0b001)
+    );
+    assert_eq!(
+        legend.modifier_bit(SemanticTokenModifiers::Static),
+        Some(0b100)
+    );
+    assert_eq!(
+        legend.modifier_bit(SemanticTokenModifiers::Definition),
+        None
+    );
+}
@@ -0,0 +1,41 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+fn help(active_signature: u32, active_parameter: u32) -> SignatureHelp {
+    serde_json::from_value(json!({
+        "signatures": [
+            {
+                "label": "fn push(&mut self, value: T)",
+                "parameters": [{ "label": "&mut self" }, { "label": "value: T" }]
+            },
+            {
+                "label": "fn insert(&mut self, index: usize, element: T)",
+                "parameters": [
+                    { "label": [10, 19] },
+                    { "label": [21, 33] },
+                    { "label": [35, 45] }
+                ]
+            }
+        ],
+        "activeSignature": active_signature,
+        "activeParameter": active_parameter
+    }))
+    .unwrap()
+}
+
+#[test]
+fn string_label_of_the_active_signature() {
+    assert_eq!(help(0, 1).active_parameter_label(), Some("value: T"));
+}
+
+#[test]
+fn offset_label_is_sliced_from_the_signature() {
+    assert_eq!(help(1, 1).active_parameter_label(), Some("index: usize"));
+    assert_eq!(help(1, 2).active_parameter_label(), Some("element: T"));
+}
+
+#[test]
+fn out_of_range_indices_fall_back_to_the_first() {
+    assert_eq!(help(5, 1).active_parameter_label(), Some("value: T"));
+    assert_eq!(help(1, 9).active_parameter_label(), Some("&mut self"));
+}
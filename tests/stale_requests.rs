@@ -0,0 +1,33 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+fn stale_request_support() -> StaleRequestSupport {
+    serde_json::from_value(json!({
+        "cancel": true,
+        "retryOnContentModified": ["textDocument/semanticTokens/full", "textDocument/inlayHint"],
+    }))
+    .unwrap()
+}
+
+#[test]
+fn method_in_the_retry_list_is_retried() {
+    assert!(stale_request_support().should_retry("textDocument/inlayHint"));
+}
+
+#[test]
+fn method_not_in_the_retry_list_is_not_retried() {
+    let support = stale_request_support();
+    assert!(!support.should_retry("textDocument/hover"));
+    assert!(!support.should_retry("textDocument/semanticTokens"));
+}
+
+#[test]
+fn content_modified_errors_are_recognized() {
+    let modified: ResponseError =
+        serde_json::from_value(json!({ "code": -32801, "message": "content modified" })).unwrap();
+    assert!(modified.is_content_modified());
+
+    let cancelled: ResponseError =
+        serde_json::from_value(json!({ "code": -32800, "message": "cancelled" })).unwrap();
+    assert!(!cancelled.is_content_modified());
+}
@@ -0,0 +1,87 @@
+use rust_lsp_types::*;
+use serde_json::{json, Value};
+
+fn range(start: (u32, u32), end: (u32, u32)) -> Value {
+    serde_json::to_value(Range::from((start, end))).unwrap()
+}
+
+fn point() -> DocumentSymbol {
+    serde_json::from_value(json!({
+        "name": "Point",
+        "kind": 23,
+        "range": range((0, 0), (3, 1)),
+        "selectionRange": range((0, 7), (0, 12)),
+        "children": [
+            {
+                "name": "x",
+                "kind": 8,
+                "range": range((1, 4), (1, 14)),
+                "selectionRange": range((1, 4), (1, 5)),
+            },
+            {
+                "name": "y",
+                "kind": 8,
+                "range": range((2, 4), (2, 14)),
+                "selectionRange": range((2, 4), (2, 5)),
+            },
+        ],
+    }))
+    .unwrap()
+}
+
+#[test]
+fn two_level_hierarchy_flattens_into_three_symbols() {
+    let symbols = SymbolInformation::from_document_symbol(&point(), "file:///src/point.rs");
+    let flat: Vec<(&str, Option<&str>)> = symbols
+        .iter()
+        .map(|symbol| (symbol.name.as_str(), symbol.containerName.as_deref()))
+        .collect();
+    assert_eq!(
+        flat,
+        [("Point", None), ("x", Some("Point")), ("y", Some("Point"))]
+    );
+
+    assert!(symbols
+        .iter()
+        .all(|symbol| symbol.location.uri == "file:///src/point.rs"));
+    assert_eq!(symbols[0].kind, SymbolKind::Struct);
+    assert_eq!(symbols[1].location.range, Range::from(((1, 4), (1, 14))));
+}
+
+#[test]
+fn flat_symbols_rebuild_the_hierarchy() {
+    let mut flat = SymbolInformation::from_document_symbol(&point(), "file:///src/point.rs");
+    // the order of flat symbols doesn't matter
+    flat.reverse();
+
+    let roots = DocumentSymbol::from_symbol_informations(flat);
+    assert_eq!(roots.len(), 1);
+    let point = &roots[0];
+    assert_eq!(point.name, "Point");
+    assert_eq!(point.range, Range::from(((0, 0), (3, 1))));
+
+    let children: Vec<&str> = point
+        .children
+        .iter()
+        .flatten()
+        .map(|child| child.name.as_str())
+        .collect();
+    assert_eq!(children, ["x", "y"]);
+    assert!(point
+        .children
+        .iter()
+        .flatten()
+        .all(|child| child.children.is_none()));
+}
+
+#[test]
+fn unrelated_flat_symbols_stay_roots() {
+    let symbols: Vec<SymbolInformation> = serde_json::from_value(json!([
+        { "name": "a", "kind": 12, "location": { "uri": "file:///a.rs", "range": range((0, 0), (1, 1)) } },
+        { "name": "b", "kind": 12, "location": { "uri": "file:///a.rs", "range": range((3, 0), (4, 1)) } },
+    ]))
+    .unwrap();
+    let roots = DocumentSymbol::from_symbol_informations(symbols);
+    let names: Vec<&str> = roots.iter().map(|symbol| symbol.name.as_str()).collect();
+    assert_eq!(names, ["a", "b"]);
+}
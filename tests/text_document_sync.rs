@@ -0,0 +1,28 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+fn change(change: serde_json::Value) -> TextDocumentContentChangeEvent {
+    serde_json::from_value(change).unwrap()
+}
+
+#[test]
+fn only_incremental_is_incremental() {
+    assert!(!TextDocumentSyncKind::None.is_incremental());
+    assert!(!TextDocumentSyncKind::Full.is_incremental());
+    assert!(TextDocumentSyncKind::Incremental.is_incremental());
+}
+
+#[test]
+fn should_send_full_per_kind() {
+    let ranged = change(json!({ "range": Range::from(((0, 0), (0, 3))), "text": "let" }));
+    let whole = change(json!({ "text": "let x = 1;\n" }));
+
+    assert!(!TextDocumentSyncKind::None.should_send_full(&ranged));
+    assert!(!TextDocumentSyncKind::None.should_send_full(&whole));
+
+    assert!(TextDocumentSyncKind::Full.should_send_full(&ranged));
+    assert!(TextDocumentSyncKind::Full.should_send_full(&whole));
+
+    assert!(!TextDocumentSyncKind::Incremental.should_send_full(&ranged));
+    assert!(TextDocumentSyncKind::Incremental.should_send_full(&whole));
+}
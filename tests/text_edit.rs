@@ -0,0 +1,29 @@
+use rust_lsp_types::*;
+
+fn edit(range: Range, text: &str) -> TextEdit {
+    TextEdit {
+        range,
+        newText: text.into(),
+    }
+}
+
+#[test]
+fn bounding_range_of_scattered_edits() {
+    let edits = [
+        edit(Range::from(((4, 2), (4, 6))), "self"),
+        edit(Range::from(((1, 8), (2, 0))), ""),
+        edit(Range::from(((9, 0), (9, 0))), "}\n"),
+        edit(Range::from(((1, 10), (1, 12))), "::"),
+    ];
+    assert_eq!(
+        edits_bounding_range(&edits),
+        Some(Range::from(((1, 8), (9, 0))))
+    );
+}
+
+#[test]
+fn bounding_range_of_a_single_edit_is_its_range() {
+    let range = Range::from(((3, 4), (3, 9)));
+    assert_eq!(edits_bounding_range(&[edit(range, "value")]), Some(range));
+    assert_eq!(edits_bounding_range(&[]), None);
+}
@@ -0,0 +1,38 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+/// what a server sends for `$/logTrace` under the negotiated trace level
+fn log_trace(trace: TraceValue) -> LogTraceParams {
+    let params = LogTraceParams::new("received textDocument/hover");
+    if should_log_verbose(trace) {
+        params.verbose("params: {\"position\":{\"line\":3,\"character\":14}}")
+    } else {
+        params
+    }
+}
+
+#[test]
+fn verbose_content_is_included_only_under_verbose() {
+    assert!(log_trace(TraceValue::Verbose).verbose.is_some());
+    assert!(log_trace(TraceValue::Messages).verbose.is_none());
+    assert!(log_trace(TraceValue::Off).verbose.is_none());
+}
+
+#[test]
+fn log_trace_omits_missing_verbose() {
+    assert_eq!(
+        serde_json::to_value(log_trace(TraceValue::Messages)).unwrap(),
+        json!({ "message": "received textDocument/hover" })
+    );
+    let verbose = serde_json::to_value(log_trace(TraceValue::Verbose)).unwrap();
+    assert_eq!(verbose["message"], "received textDocument/hover");
+    assert!(verbose["verbose"].is_string());
+}
+
+#[test]
+fn set_trace_serializes_the_value() {
+    assert_eq!(
+        serde_json::to_value(SetTraceParams::new(TraceValue::Verbose)).unwrap(),
+        json!({ "value": "verbose" })
+    );
+}
@@ -0,0 +1,15 @@
+use rust_lsp_types::*;
+
+#[test]
+fn next_version_increments() {
+    let id = VersionedTextDocumentIdentifier::new("file:///a.rs", 1);
+    let next = id.next_version().unwrap();
+    assert_eq!(next.version, 2);
+    assert_eq!(next.uri, "file:///a.rs");
+}
+
+#[test]
+fn next_version_stops_at_integer_max() {
+    let id = VersionedTextDocumentIdentifier::new("file:///a.rs", Integer::MAX);
+    assert!(id.next_version().is_none());
+}
@@ -0,0 +1,64 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn begin_always_serializes_its_kind() {
+    let begin = WorkDoneProgressBegin::new("indexing");
+    assert_eq!(
+        serde_json::to_value(&begin).unwrap(),
+        json!({ "kind": "begin", "title": "indexing" })
+    );
+    let begin = begin.with_percentage(10);
+    assert_eq!(serde_json::to_value(&begin).unwrap()["kind"], "begin");
+}
+
+#[test]
+fn payload_of_another_kind_is_not_a_begin() {
+    let end = json!({ "kind": "end", "title": "indexing" });
+    assert!(serde_json::from_value::<WorkDoneProgressBegin>(end).is_err());
+
+    let begin: WorkDoneProgressBegin =
+        serde_json::from_value(json!({ "kind": "begin", "title": "indexing" })).unwrap();
+    assert_eq!(begin.title, "indexing");
+}
+
+#[test]
+fn report_and_end_serialize_their_kinds() {
+    assert_eq!(
+        serde_json::to_value(WorkDoneProgressReport::new()).unwrap(),
+        json!({ "kind": "report" })
+    );
+    let end: WorkDoneProgressEnd = serde_json::from_value(json!({ "kind": "end" })).unwrap();
+    assert_eq!(
+        serde_json::to_value(&end).unwrap(),
+        json!({ "kind": "end" })
+    );
+}
+
+fn enable<T: WithWorkDoneProgress>(options: &mut T) {
+    assert_eq!(options.work_done_progress(), None);
+    options.set_work_done_progress(Some(true));
+    assert_eq!(options.work_done_progress(), Some(true));
+}
+
+#[test]
+fn provider_options_share_work_done_progress() {
+    let mut hover = HoverOptions {
+        workDoneProgress: None,
+    };
+    enable(&mut hover);
+    assert_eq!(
+        serde_json::to_value(&hover).unwrap(),
+        json!({ "workDoneProgress": true })
+    );
+
+    let mut completion = CompletionOptions::default();
+    enable(&mut completion);
+    assert_eq!(
+        serde_json::to_value(&completion).unwrap(),
+        json!({ "workDoneProgress": true })
+    );
+
+    completion.set_work_done_progress(None);
+    assert_eq!(serde_json::to_value(&completion).unwrap(), json!({}));
+}
@@ -0,0 +1,48 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+fn diagnostic(line: u32, message: &str) -> serde_json::Value {
+    json!({
+        "range": { "start": { "line": line, "character": 0 }, "end": { "line": line, "character": 5 } },
+        "message": message,
+    })
+}
+
+#[test]
+fn all_diagnostics_walks_full_reports_and_skips_unchanged_ones() {
+    let report: WorkspaceDiagnosticReport = serde_json::from_value(json!({
+        "items": [
+            {
+                "kind": "full",
+                "uri": "file:///src/lib.rs",
+                "version": 3,
+                "items": [diagnostic(1, "unused import"), diagnostic(8, "dead code")],
+            },
+            {
+                "kind": "unchanged",
+                "uri": "file:///src/main.rs",
+                "version": null,
+                "resultId": "main-2",
+            },
+        ],
+    }))
+    .unwrap();
+
+    let all: Vec<(&str, &str)> = report
+        .all_diagnostics()
+        .map(|(uri, diagnostic)| (uri.as_str(), diagnostic.message.as_str()))
+        .collect();
+    assert_eq!(
+        all,
+        [
+            ("file:///src/lib.rs", "unused import"),
+            ("file:///src/lib.rs", "dead code"),
+        ]
+    );
+}
+
+#[test]
+fn all_diagnostics_of_an_empty_report() {
+    let report: WorkspaceDiagnosticReport = serde_json::from_value(json!({ "items": [] })).unwrap();
+    assert_eq!(report.all_diagnostics().count(), 0);
+}
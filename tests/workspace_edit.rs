@@ -0,0 +1,140 @@
+use rust_lsp_types::*;
+use serde_json::json;
+
+#[test]
+fn annotated_and_plain_edits_in_one_array() {
+    let json = json!([
+        {
+            "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 0, "character": 3 } },
+            "newText": "let",
+            "annotationId": "rename"
+        },
+        {
+            "range": { "start": { "line": 2, "character": 4 }, "end": { "line": 2, "character": 4 } },
+            "newText": ";"
+        }
+    ]);
+    let edits: Vec<TextEditOrAnnotatedTextEdit> = serde_json::from_value(json.clone()).unwrap();
+
+    let TextEditOrAnnotatedTextEdit::AnnotatedTextEdit(annotated) = &edits[0] else {
+        panic!("expected the annotationId to be kept");
+    };
+    assert_eq!(annotated.annotationId, "rename");
+    assert_eq!(annotated.range, Range::from(((0, 0), (0, 3))));
+
+    assert_eq!(
+        edits[1],
+        TextEditOrAnnotatedTextEdit::TextEdit(TextEdit {
+            range: Range::from(((2, 4), (2, 4))),
+            newText: ";".into(),
+        })
+    );
+    assert_eq!(serde_json::to_value(&edits).unwrap(), json);
+}
+
+fn edit(line: u32, text: &str) -> TextEdit {
+    TextEdit {
+        range: Range::from(((line, 0), (line, 0))),
+        newText: text.into(),
+    }
+}
+
+#[test]
+fn empty_workspace_edit() {
+    let edit = WorkspaceEdit::default();
+    assert!(edit.is_empty());
+    assert_eq!(edit.edit_count(), 0);
+
+    let edit = WorkspaceEdit {
+        changes: Some([("file:///a.rs".into(), Vec::new())].into()),
+        ..WorkspaceEdit::default()
+    };
+    assert!(edit.is_empty());
+}
+
+#[test]
+fn changes_count_each_text_edit() {
+    let edit = WorkspaceEdit {
+        changes: Some(
+            [
+                ("file:///a.rs".into(), vec![edit(0, "a"), edit(1, "b")]),
+                ("file:///b.rs".into(), vec![edit(3, "c")]),
+            ]
+            .into(),
+        ),
+        ..WorkspaceEdit::default()
+    };
+    assert!(!edit.is_empty());
+    assert_eq!(edit.edit_count(), 3);
+}
+
+#[test]
+fn document_changes_count_file_operations_as_one() {
+    let text_edit = TextDocumentEdit {
+        textDocument: OptionalVersionedTextDocumentIdentifier {
+            uri: "file:///a.rs".into(),
+            version: Nullable::Value(4),
+        },
+        edits: vec![
+            TextEditOrAnnotatedTextEdit::TextEdit(edit(0, "a")),
+            TextEditOrAnnotatedTextEdit::TextEdit(edit(1, "b")),
+        ],
+    };
+    let edit = WorkspaceEdit {
+        documentChanges: Some(WorkspaceEditDocumentChanges::DocumentChanges(vec![
+            DocumentChange::CreateFile(CreateFile::new("file:///b.rs")),
+            DocumentChange::TextDocumentEdit(text_edit),
+            DocumentChange::RenameFile(RenameFile::new("file:///c.rs", "file:///d.rs")),
+        ])),
+        ..WorkspaceEdit::default()
+    };
+    assert!(!edit.is_empty());
+    assert_eq!(edit.edit_count(), 4);
+
+    let create_only = WorkspaceEdit {
+        documentChanges: Some(WorkspaceEditDocumentChanges::DocumentChanges(vec![
+            DocumentChange::CreateFile(CreateFile::new("file:///b.rs")),
+        ])),
+        ..WorkspaceEdit::default()
+    };
+    assert!(!create_only.is_empty());
+    assert_eq!(create_only.edit_count(), 1);
+}
+
+#[test]
+fn annotations_get_distinct_ids() {
+    let mut workspace_edit = WorkspaceEdit::default();
+    let rename = workspace_edit.add_annotation(ChangeAnnotation::new("Rename symbol"));
+    let confirm = workspace_edit.add_annotation(ChangeAnnotation {
+        needsConfirmation: Some(true),
+        ..ChangeAnnotation::new("Delete file")
+    });
+    assert_ne!(rename, confirm);
+
+    workspace_edit.documentChanges = Some(WorkspaceEditDocumentChanges::TextDocumentEdit(vec![
+        TextDocumentEdit {
+            textDocument: OptionalVersionedTextDocumentIdentifier {
+                uri: "file:///a.rs".into(),
+                version: Nullable::Null,
+            },
+            edits: vec![TextEditOrAnnotatedTextEdit::AnnotatedTextEdit(
+                AnnotatedTextEdit {
+                    range: Range::from(((0, 4), (0, 7))),
+                    newText: "bar".into(),
+                    annotationId: rename.clone(),
+                },
+            )],
+        },
+    ]));
+    assert_eq!(workspace_edit.edit_count(), 1);
+
+    let json = serde_json::to_value(&workspace_edit).unwrap();
+    let annotations = &json["changeAnnotations"];
+    assert_eq!(annotations.as_object().unwrap().len(), 2);
+    assert_eq!(annotations[&rename]["label"], "Rename symbol");
+    assert_eq!(annotations[&confirm]["needsConfirmation"], true);
+    assert_eq!(
+        json["documentChanges"][0]["edits"][0]["annotationId"],
+        json!(rename)
+    );
+}
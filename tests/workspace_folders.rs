@@ -0,0 +1,76 @@
+use rust_lsp_types::*;
+use serde_json::{json, Value};
+
+fn params(fields: Value) -> InitializeParams {
+    let mut json = json!({ "processId": null, "rootUri": null, "capabilities": {} });
+    json.as_object_mut()
+        .unwrap()
+        .extend(fields.as_object().unwrap().clone());
+    serde_json::from_value(json).unwrap()
+}
+
+fn folders(params: &InitializeParams) -> Vec<(String, String)> {
+    params
+        .workspace_folders_normalized()
+        .into_iter()
+        .map(|folder| (folder.uri.to_string(), folder.name))
+        .collect()
+}
+
+#[test]
+fn workspace_folders_take_precedence() {
+    let params = params(json!({
+        "workspaceFolders": [
+            { "uri": "file:///work/app", "name": "app" },
+            { "uri": "file:///work/lib", "name": "lib" },
+        ],
+        "rootUri": "file:///work",
+        "rootPath": "/elsewhere",
+    }));
+    assert_eq!(
+        folders(&params),
+        [
+            ("file:///work/app".into(), "app".into()),
+            ("file:///work/lib".into(), "lib".into()),
+        ]
+    );
+}
+
+#[test]
+fn empty_workspace_folders_mean_no_folder_is_open() {
+    let params = params(json!({ "workspaceFolders": [], "rootUri": "file:///work" }));
+    assert!(folders(&params).is_empty());
+}
+
+#[test]
+fn root_uri_comes_before_root_path() {
+    let params = params(json!({ "rootUri": "file:///work/app/", "rootPath": "/elsewhere" }));
+    assert_eq!(
+        folders(&params),
+        [("file:///work/app/".into(), "app".into())]
+    );
+}
+
+#[test]
+fn percent_encoded_root_uri_gives_a_decoded_name() {
+    let params = params(json!({ "rootUri": "file:///work/my%20project" }));
+    assert_eq!(
+        folders(&params),
+        [("file:///work/my%20project".into(), "my project".into())]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn root_path_is_the_last_resort() {
+    let params = params(json!({ "rootPath": "/work/my project" }));
+    assert_eq!(
+        folders(&params),
+        [("file:///work/my%20project".into(), "my project".into())]
+    );
+}
+
+#[test]
+fn no_root_gives_no_folders() {
+    assert!(folders(&params(json!({}))).is_empty());
+}